@@ -1,8 +1,27 @@
 // ABOUTME: Configuration module for para-ssg with blog-specific settings
 // ABOUTME: Handles site-wide configuration including GitHub integration for comments
 
+use crate::parser::{Bibliography, FrontmatterSchema, Glossary, QueryRecord, ShortcodeTemplates};
+use crate::utils::GitHistory;
 use crate::Result;
-use std::path::Path;
+use chrono_tz::Tz;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Read a boolean flag from an environment variable
+///
+/// `1`, `true`, `yes` and `on` enable it and `0`, `false`, `no` and `off`
+/// disable it, in any case; an unset or unrecognised value gives `default`.
+fn env_flag(name: &str, default: bool) -> bool {
+    match std::env::var(name)
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Ok("1" | "true" | "yes" | "on") => true,
+        Ok("0" | "false" | "no" | "off") => false,
+        _ => default,
+    }
+}
 
 /// Blog-specific configuration
 #[derive(Debug, Clone)]
@@ -30,9 +49,7 @@ impl BlogConfig {
         Self {
             github_owner: std::env::var("PARA_SSG_GITHUB_OWNER").unwrap_or_default(),
             github_repo: std::env::var("PARA_SSG_GITHUB_REPO").unwrap_or_default(),
-            comments_enabled: std::env::var("PARA_SSG_COMMENTS_ENABLED")
-                .map(|v| v.to_lowercase() != "false")
-                .unwrap_or(true),
+            comments_enabled: env_flag("PARA_SSG_COMMENTS_ENABLED", true),
         }
     }
 
@@ -48,16 +65,422 @@ impl Default for BlogConfig {
     }
 }
 
+/// What feed entries carry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedContent {
+    /// The full rendered document
+    #[default]
+    Full,
+    /// A plain-text summary of the document
+    Summary,
+}
+
+/// Atom and RSS feed configuration
+#[derive(Debug, Clone)]
+pub struct FeedConfig {
+    /// Full content or summaries in entries
+    pub content: FeedContent,
+    /// Also generate a feed for each PARA category
+    pub categories: bool,
+    /// Also generate a feed for each tag
+    pub tags: bool,
+    /// Maximum number of entries per feed
+    pub limit: usize,
+}
+
+impl FeedConfig {
+    /// Create feed configuration from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            content: match std::env::var("PARA_SSG_FEED_CONTENT").as_deref() {
+                Ok("summary") => FeedContent::Summary,
+                _ => FeedContent::Full,
+            },
+            categories: env_flag("PARA_SSG_FEED_CATEGORIES", defaults.categories),
+            tags: env_flag("PARA_SSG_FEED_TAGS", defaults.tags),
+            limit: std::env::var("PARA_SSG_FEED_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.limit),
+        }
+    }
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            content: FeedContent::Full,
+            categories: false,
+            tags: false,
+            limit: 20,
+        }
+    }
+}
+
+/// sitemap.xml and robots.txt configuration
+#[derive(Debug, Clone, Default)]
+pub struct SitemapConfig {
+    /// Paths, relative to the site root, that robots.txt disallows and the
+    /// sitemap leaves out
+    pub disallow: Vec<String>,
+}
+
+impl SitemapConfig {
+    /// Create sitemap configuration from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            disallow: std::env::var("PARA_SSG_ROBOTS_DISALLOW")
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(|path| path.trim().to_string())
+                        .filter(|path| !path.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Page sizes of paginated listings; `0` puts every entry on one page
+#[derive(Debug, Clone)]
+pub struct PaginationConfig {
+    /// Entries per page of category and subdirectory listings
+    pub page_size: usize,
+    /// Posts per page of the blog listing
+    pub blog_page_size: usize,
+}
+
+impl PaginationConfig {
+    /// Create pagination configuration from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            page_size: std::env::var("PARA_SSG_PAGE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.page_size),
+            blog_page_size: std::env::var("PARA_SSG_BLOG_PAGE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.blog_page_size),
+        }
+    }
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            page_size: 20,
+            blog_page_size: 10,
+        }
+    }
+}
+
+/// Markdown parsing configuration
+#[derive(Debug, Clone, Default)]
+pub struct ParserConfig {
+    /// Root directory for code block includes with absolute paths
+    /// (`include="/code/lib.rs"`). Defaults to the input directory.
+    pub include_root: Option<PathBuf>,
+
+    /// Additional callout types beyond the Obsidian built-ins
+    pub callout_types: Vec<CustomCallout>,
+
+    /// Timezone for frontmatter dates written without one. Defaults to UTC.
+    pub default_timezone: Option<Tz>,
+
+    /// Frontmatter schema file. Defaults to `frontmatter-schema.toml` in the
+    /// input directory when that file exists.
+    pub schema_path: Option<PathBuf>,
+
+    /// Loaded frontmatter schema, applied to every document
+    pub schema: Option<FrontmatterSchema>,
+
+    /// Take missing `created`/`modified` dates from git history instead of
+    /// the file modification time
+    pub git_dates: bool,
+
+    /// Git history loaded for the input directory when `git_dates` is set
+    pub git_history: Option<Arc<GitHistory>>,
+
+    /// Whether inline `#hashtags` are shown as tag links or stripped
+    pub inline_tags: InlineTagMode,
+
+    /// Language of documents without a `lang` key or filename suffix.
+    /// Defaults to `en`.
+    pub default_language: Option<String>,
+
+    /// How wiki link targets, heading IDs and search text are normalized
+    pub unicode_matching: UnicodeMatching,
+
+    /// Additional frontmatter relationship keys beyond the built-ins
+    pub relationship_types: Vec<RelationshipType>,
+
+    /// Documents that `query` blocks are evaluated against, built once all
+    /// documents have been parsed
+    pub query_index: Option<Arc<Vec<QueryRecord>>>,
+
+    /// Glossary terms whose first occurrences are linked, built once all
+    /// documents have been parsed
+    pub glossary: Option<Arc<Glossary>>,
+
+    /// Theme directory holding `shortcodes/*.html` templates. Defaults to
+    /// `.theme` in the input directory.
+    pub theme_dir: Option<PathBuf>,
+
+    /// Shortcode templates loaded from the theme directory
+    pub shortcodes: Option<Arc<ShortcodeTemplates>>,
+
+    /// Render documents whose frontmatter fails to parse (body only)
+    /// instead of skipping them
+    pub recover_frontmatter: bool,
+
+    /// Development build (watch mode): recovered documents show a warning
+    /// banner on the page
+    pub dev_build: bool,
+
+    /// Bibliography file (`.bib` or CSL-JSON) for `[@key]` citations.
+    /// Defaults to `references.bib` or `references.json` in the input
+    /// directory when one exists.
+    pub bibliography_path: Option<PathBuf>,
+
+    /// Loaded site bibliography; documents may add their own with a
+    /// `bibliography` frontmatter key
+    pub bibliography: Option<Arc<Bibliography>>,
+
+    /// How citations and bibliographies are formatted
+    pub citation_style: CitationStyle,
+}
+
+/// How inline `#hashtags` in note bodies are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InlineTagMode {
+    /// Render as links to the tag's page
+    #[default]
+    Link,
+    /// Remove from the rendered text (they still count as tags)
+    Strip,
+}
+
+/// How `[@key]` citations and bibliographies are formatted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// `(Smith 2020, p. 12)`, with references sorted by author
+    #[default]
+    AuthorDate,
+    /// `[1, p. 12]`, with references numbered in citation order
+    Numeric,
+}
+
+/// How names are compared in wiki link lookup, heading IDs and search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnicodeMatching {
+    /// Exact match after NFC normalization
+    Strict,
+    /// NFC, full-width forms folded to ASCII, and case folding; `-`, `_`
+    /// and runs of whitespace are equivalent in link targets
+    #[default]
+    Normalized,
+    /// As `Normalized`, with diacritics removed (`Café` matches `Cafe`)
+    FoldDiacritics,
+}
+
+impl UnicodeMatching {
+    /// Name used in `PARA_SSG_UNICODE_MATCHING` and the search index
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnicodeMatching::Strict => "strict",
+            UnicodeMatching::Normalized => "normalized",
+            UnicodeMatching::FoldDiacritics => "fold-diacritics",
+        }
+    }
+}
+
+/// A user-defined callout type (`> [!decision] ...`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomCallout {
+    /// Type name used in the `[!name]` marker
+    pub name: String,
+    /// Icon shown before the callout title
+    pub icon: String,
+    /// Optional accent color (any CSS color value)
+    pub color: Option<String>,
+}
+
+impl CustomCallout {
+    /// Parse a list of custom callouts from `name:icon[:color]` entries
+    /// separated by commas, e.g. `decision:⚖:#8B5CF6,idea:💡`
+    pub fn parse_list(value: &str) -> Vec<Self> {
+        value
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.trim().splitn(3, ':');
+                let name = parts.next()?.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                Some(Self {
+                    name: name.to_lowercase(),
+                    icon: parts.next().unwrap_or("").trim().to_string(),
+                    color: parts
+                        .next()
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A frontmatter key that links a document to others (`implements: [[Spec]]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipType {
+    /// Frontmatter key holding the targets
+    pub key: String,
+    /// Heading for the targets on the linking document
+    pub label: String,
+    /// Heading for the linking document on each target
+    pub inverse_label: String,
+}
+
+impl RelationshipType {
+    /// Create a relationship type
+    pub fn new(key: &str, label: &str, inverse_label: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            label: label.to_string(),
+            inverse_label: inverse_label.to_string(),
+        }
+    }
+
+    /// Parse a list of relationship types from `key:Label[:Inverse label]`
+    /// entries separated by commas, e.g. `supersedes:Supersedes:Superseded by`
+    ///
+    /// Without an inverse label the label is used in both directions.
+    pub fn parse_list(value: &str) -> Vec<Self> {
+        value
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.trim().splitn(3, ':');
+                let key = parts.next()?.trim();
+                if key.is_empty() {
+                    return None;
+                }
+                let label = parts
+                    .next()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .unwrap_or(key);
+                let inverse_label = parts
+                    .next()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .unwrap_or(label);
+                Some(Self::new(key, label, inverse_label))
+            })
+            .collect()
+    }
+}
+
+impl ParserConfig {
+    /// Create a new parser configuration with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Language of documents without a `lang` key or filename suffix
+    pub fn language(&self) -> &str {
+        self.default_language
+            .as_deref()
+            .unwrap_or(crate::parser::DEFAULT_LANGUAGE)
+    }
+
+    /// Create parser configuration from environment variables
+    pub fn from_env() -> Self {
+        Self {
+            include_root: std::env::var("PARA_SSG_INCLUDE_ROOT")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            callout_types: std::env::var("PARA_SSG_CALLOUT_TYPES")
+                .map(|v| CustomCallout::parse_list(&v))
+                .unwrap_or_default(),
+            default_timezone: std::env::var("PARA_SSG_TIMEZONE")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .and_then(|v| match v.parse::<Tz>() {
+                    Ok(tz) => Some(tz),
+                    Err(_) => {
+                        eprintln!(
+                            "⚠️  Unknown timezone '{}' in PARA_SSG_TIMEZONE, using UTC",
+                            v
+                        );
+                        None
+                    }
+                }),
+            schema_path: std::env::var("PARA_SSG_SCHEMA")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            schema: None,
+            git_dates: env_flag("PARA_SSG_GIT_DATES", false),
+            git_history: None,
+            inline_tags: match std::env::var("PARA_SSG_INLINE_TAGS").as_deref() {
+                Ok("strip") => InlineTagMode::Strip,
+                _ => InlineTagMode::Link,
+            },
+            default_language: std::env::var("PARA_SSG_LANGUAGE")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            unicode_matching: match std::env::var("PARA_SSG_UNICODE_MATCHING").as_deref() {
+                Ok("strict") => UnicodeMatching::Strict,
+                Ok("fold-diacritics") => UnicodeMatching::FoldDiacritics,
+                _ => UnicodeMatching::Normalized,
+            },
+            relationship_types: std::env::var("PARA_SSG_RELATIONSHIPS")
+                .map(|v| RelationshipType::parse_list(&v))
+                .unwrap_or_default(),
+            query_index: None,
+            glossary: None,
+            theme_dir: std::env::var("PARA_SSG_THEME_DIR")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            shortcodes: None,
+            recover_frontmatter: env_flag("PARA_SSG_RECOVER_FRONTMATTER", false),
+            dev_build: false,
+            bibliography_path: std::env::var("PARA_SSG_BIBLIOGRAPHY")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            bibliography: None,
+            citation_style: match std::env::var("PARA_SSG_CITATION_STYLE").as_deref() {
+                Ok("numeric") => CitationStyle::Numeric,
+                _ => CitationStyle::AuthorDate,
+            },
+        }
+    }
+}
+
 /// Extended configuration for site generation with blog support
 #[derive(Debug, Clone)]
 pub struct Config {
     pub input_dir: String,
     pub output_dir: String,
     pub base_url: String,
+    /// Absolute site URL (`https://example.com`) for feeds and other
+    /// output that needs absolute links; `base_url` is appended to it
+    pub site_url: Option<String>,
     pub site_title: String,
     pub verbose: bool,
     pub watch: bool,
     pub blog: BlogConfig,
+    pub feeds: FeedConfig,
+    pub sitemap: SitemapConfig,
+    pub pagination: PaginationConfig,
+    pub parser: ParserConfig,
 }
 
 impl Config {
@@ -70,13 +493,37 @@ impl Config {
             input_dir,
             output_dir,
             base_url,
+            site_url: std::env::var("PARA_SSG_SITE_URL")
+                .ok()
+                .filter(|url| !url.is_empty()),
             site_title: "forge".to_string(),
             verbose: false,
             watch: false,
             blog: BlogConfig::from_env(),
+            feeds: FeedConfig::from_env(),
+            sitemap: SitemapConfig::from_env(),
+            pagination: PaginationConfig::from_env(),
+            parser: ParserConfig::from_env(),
         }
     }
 
+    /// Absolute URL of the site root, ending in `/`
+    ///
+    /// `base_url` when it is already absolute, otherwise `site_url` with
+    /// `base_url` appended; `None` when neither gives an absolute URL.
+    pub fn absolute_base_url(&self) -> Option<String> {
+        let url = if self.base_url.contains("://") {
+            self.base_url.clone()
+        } else {
+            format!(
+                "{}/{}",
+                self.site_url.as_deref()?.trim_end_matches('/'),
+                self.base_url.trim_start_matches('/')
+            )
+        };
+        Some(if url.ends_with('/') { url } else { url + "/" })
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<()> {
         if !Path::new(&self.input_dir).exists() {
@@ -146,6 +593,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_env_flag() {
+        let name = "PARA_SSG_TEST_ENV_FLAG";
+        env::remove_var(name);
+        assert!(env_flag(name, true));
+        assert!(!env_flag(name, false));
+
+        for value in ["1", "true", "TRUE", "yes", "on"] {
+            env::set_var(name, value);
+            assert!(env_flag(name, false), "{} should enable", value);
+        }
+        for value in ["0", "false", "No", "off"] {
+            env::set_var(name, value);
+            assert!(!env_flag(name, true), "{} should disable", value);
+        }
+        env::set_var(name, "maybe");
+        assert!(env_flag(name, true));
+        env::remove_var(name);
+    }
+
     #[test]
     fn test_blog_config_is_valid_for_comments() {
         let mut config = BlogConfig::new();
@@ -171,4 +638,50 @@ mod tests {
         assert_eq!(config.output_dir, "output");
         assert!(config.blog.comments_enabled);
     }
+
+    #[test]
+    fn test_custom_callout_parse_list() {
+        let callouts = CustomCallout::parse_list("Decision:⚖:#8B5CF6, idea:💡,,");
+
+        assert_eq!(callouts.len(), 2);
+        assert_eq!(callouts[0].name, "decision");
+        assert_eq!(callouts[0].icon, "⚖");
+        assert_eq!(callouts[0].color.as_deref(), Some("#8B5CF6"));
+        assert_eq!(callouts[1].name, "idea");
+        assert_eq!(callouts[1].color, None);
+    }
+
+    #[test]
+    fn test_relationship_type_parse_list() {
+        let types =
+            RelationshipType::parse_list("supersedes:Supersedes:Superseded by, see_also:See also");
+
+        assert_eq!(types.len(), 2);
+        assert_eq!(
+            types[0],
+            RelationshipType::new("supersedes", "Supersedes", "Superseded by")
+        );
+        assert_eq!(types[1].label, "See also");
+        assert_eq!(types[1].inverse_label, "See also");
+    }
+
+    #[test]
+    fn test_absolute_base_url() {
+        let mut config = Config::new("in".to_string(), "out".to_string());
+        config.base_url = "/notes/".to_string();
+        config.site_url = None;
+        assert_eq!(config.absolute_base_url(), None);
+
+        config.site_url = Some("https://example.com/".to_string());
+        assert_eq!(
+            config.absolute_base_url().as_deref(),
+            Some("https://example.com/notes/")
+        );
+
+        config.base_url = "https://example.com/wiki".to_string();
+        assert_eq!(
+            config.absolute_base_url().as_deref(),
+            Some("https://example.com/wiki/")
+        );
+    }
 }
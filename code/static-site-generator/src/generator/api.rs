@@ -0,0 +1,343 @@
+//! ABOUTME: Headless content API with a JSON file per document
+//! ABOUTME: Writes api/<path>.json with metadata, HTML, links, backlinks and TOC, plus api/index.json
+
+use crate::parser::{ContentStats, Document, DocumentMetadata, Task, TocEntry};
+use crate::Result;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Output directory of the content API
+pub const API_DIR: &str = "api";
+
+/// A link from a document to another, through a wiki link or a typed
+/// frontmatter relationship
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiLink {
+    /// Target as written
+    pub target: String,
+    /// Link text, when it differs from the target
+    pub text: Option<String>,
+    /// Relationship label for typed links, `None` for wiki links
+    pub relationship: Option<String>,
+    /// Page URL of the target, when resolved
+    pub url: Option<String>,
+    /// API URL of the target, when resolved
+    pub api_url: Option<String>,
+    /// Whether the target could not be found
+    pub broken: bool,
+}
+
+/// A document linking to this one
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiBacklink {
+    pub title: String,
+    pub url: String,
+    pub api_url: String,
+    /// Text around the link
+    pub context: Option<String>,
+    /// Relationship label for typed links, `None` for wiki links
+    pub relationship: Option<String>,
+}
+
+/// Another language version of a document
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiTranslation {
+    pub lang: String,
+    pub title: String,
+    pub url: String,
+    pub api_url: String,
+}
+
+/// Everything the API publishes about a document
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiDocument<'a> {
+    /// Source path relative to the input directory
+    pub path: &'a Path,
+    pub title: &'a str,
+    pub url: String,
+    pub api_url: String,
+    pub category: &'a str,
+    pub lang: &'a str,
+    pub metadata: &'a DocumentMetadata,
+    /// Rendered HTML content
+    pub html: &'a str,
+    pub toc: &'a [TocEntry],
+    pub stats: &'a ContentStats,
+    pub tasks: &'a [Task],
+    pub links: Vec<ApiLink>,
+    pub backlinks: Vec<ApiBacklink>,
+    pub translations: Vec<ApiTranslation>,
+}
+
+/// A document in `api/index.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiIndexEntry<'a> {
+    pub path: &'a Path,
+    pub title: &'a str,
+    pub url: String,
+    pub api_url: String,
+    pub category: &'a str,
+    pub lang: &'a str,
+    pub description: Option<&'a str>,
+    pub tags: &'a [String],
+    pub date: Option<String>,
+}
+
+/// Writes the JSON content API
+pub struct ApiGenerator {
+    output_dir: PathBuf,
+    base_url: String,
+}
+
+impl ApiGenerator {
+    /// Create an API generator writing under `output_dir/api`
+    pub fn new(output_dir: PathBuf, base_url: String) -> Self {
+        Self {
+            output_dir,
+            base_url,
+        }
+    }
+
+    /// Describe a document for the API
+    pub fn api_document<'a>(&self, doc: &'a Document) -> ApiDocument<'a> {
+        let mut links: Vec<ApiLink> = doc
+            .wiki_links
+            .iter()
+            .map(|link| ApiLink {
+                target: link.wiki_link.target.clone(),
+                text: link.wiki_link.display.clone(),
+                relationship: None,
+                url: link
+                    .resolved_path
+                    .as_deref()
+                    .map(|path| self.page_url(path)),
+                api_url: link.resolved_path.as_deref().map(|path| self.api_url(path)),
+                broken: link.is_broken,
+            })
+            .collect();
+        links.extend(doc.relationships.iter().map(|relationship| {
+            ApiLink {
+                target: relationship.target.clone(),
+                text: relationship.target_title.clone(),
+                relationship: Some(relationship.label.clone()),
+                url: relationship
+                    .resolved_path
+                    .as_deref()
+                    .map(|path| self.page_url(path)),
+                // Project folders resolve without a title and have no API file
+                api_url: relationship
+                    .resolved_path
+                    .as_deref()
+                    .filter(|_| relationship.target_title.is_some())
+                    .map(|path| self.api_url(path)),
+                broken: relationship.is_broken(),
+            }
+        }));
+
+        let backlinks = doc
+            .backlinks
+            .iter()
+            .map(|backlink| {
+                let output_path = backlink.source_path.with_extension("html");
+                ApiBacklink {
+                    title: backlink.source_title.clone(),
+                    url: self.page_url(&output_path),
+                    api_url: self.api_url(&output_path),
+                    context: backlink.link_context.clone(),
+                    relationship: backlink.relationship.clone(),
+                }
+            })
+            .collect();
+
+        let translations = doc
+            .translations
+            .iter()
+            .map(|translation| ApiTranslation {
+                lang: translation.lang.clone(),
+                title: translation.title.clone(),
+                url: self.page_url(&translation.output_path),
+                api_url: self.api_url(&translation.output_path),
+            })
+            .collect();
+
+        ApiDocument {
+            path: &doc.relative_path,
+            title: doc.title(),
+            url: self.page_url(&doc.output_path),
+            api_url: self.api_url(&doc.output_path),
+            category: doc.effective_category(),
+            lang: &doc.lang,
+            metadata: &doc.metadata,
+            html: &doc.html_content,
+            toc: &doc.toc,
+            stats: &doc.stats,
+            tasks: &doc.tasks,
+            links,
+            backlinks,
+            translations,
+        }
+    }
+
+    /// Write `api/<path>.json` for every published document and
+    /// `api/index.json` listing them, returning the number of documents
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a file cannot be serialized or written
+    pub fn write_api(&self, documents: &[Document]) -> Result<usize> {
+        let mut published: Vec<&Document> =
+            documents.iter().filter(|doc| !doc.is_draft()).collect();
+        published.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+        for doc in &published {
+            let path = self.output_dir.join(api_path(&doc.output_path));
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(
+                &path,
+                serde_json::to_string_pretty(&self.api_document(doc))?,
+            )?;
+        }
+
+        let index: Vec<ApiIndexEntry> = published
+            .iter()
+            .map(|doc| ApiIndexEntry {
+                path: &doc.relative_path,
+                title: doc.title(),
+                url: self.page_url(&doc.output_path),
+                api_url: self.api_url(&doc.output_path),
+                category: doc.effective_category(),
+                lang: &doc.lang,
+                description: doc.metadata.description.as_deref(),
+                tags: &doc.metadata.tags,
+                date: doc.date().map(|date| date.to_rfc3339()),
+            })
+            .collect();
+        let index_path = self.output_dir.join(API_DIR).join("index.json");
+        fs::create_dir_all(self.output_dir.join(API_DIR))?;
+        fs::write(
+            index_path,
+            serde_json::to_string_pretty(&serde_json::json!({ "documents": index }))?,
+        )?;
+
+        Ok(published.len())
+    }
+
+    fn page_url(&self, output_path: &Path) -> String {
+        format!("{}{}", self.base_url, url_path(output_path))
+    }
+
+    fn api_url(&self, output_path: &Path) -> String {
+        format!("{}{}", self.base_url, url_path(&api_path(output_path)))
+    }
+}
+
+/// Path of a document's API file, relative to the output directory
+pub fn api_path(output_path: &Path) -> PathBuf {
+    Path::new(API_DIR).join(output_path.with_extension("json"))
+}
+
+fn url_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_wiki_links, BacklinkReference, ResolvedLink};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_api() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut doc = Document::new(
+            PathBuf::from("/input/areas/note.md"),
+            PathBuf::from("areas/note.md"),
+            "areas".to_string(),
+        );
+        doc.metadata.title = Some("Note".to_string());
+        doc.metadata.tags = vec!["rust".to_string()];
+        doc.html_content = r#"<h1 id="note">Note</h1>"#.to_string();
+        doc.toc = vec![TocEntry {
+            level: 1,
+            title: "Note".to_string(),
+            id: "note".to_string(),
+        }];
+        doc.wiki_links = vec![ResolvedLink {
+            wiki_link: parse_wiki_links("[[Other|the other]]").remove(0),
+            resolved_path: Some(PathBuf::from("resources/other.html")),
+            is_broken: false,
+        }];
+        doc.backlinks = vec![BacklinkReference {
+            source_path: PathBuf::from("projects/p.md"),
+            source_title: "P".to_string(),
+            link_context: None,
+            relationship: Some("Implemented by".to_string()),
+        }];
+        let mut draft = Document::new(
+            PathBuf::from("/input/areas/draft.md"),
+            PathBuf::from("areas/draft.md"),
+            "areas".to_string(),
+        );
+        draft.metadata.status = Some("draft".to_string());
+
+        let api = ApiGenerator::new(temp_dir.path().to_path_buf(), "/".to_string());
+        assert_eq!(api.write_api(&[doc, draft]).unwrap(), 1);
+        assert!(!temp_dir.path().join("api/areas/draft.json").exists());
+
+        let json: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("api/areas/note.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["url"], "/areas/note.html");
+        assert_eq!(json["metadata"]["tags"][0], "rust");
+        assert_eq!(json["html"], r#"<h1 id="note">Note</h1>"#);
+        assert_eq!(json["toc"][0]["id"], "note");
+        assert_eq!(json["links"][0]["api_url"], "/api/resources/other.json");
+        assert_eq!(json["links"][0]["text"], "the other");
+        assert_eq!(json["backlinks"][0]["url"], "/projects/p.html");
+        assert_eq!(json["backlinks"][0]["relationship"], "Implemented by");
+
+        let index: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("api/index.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(index["documents"].as_array().unwrap().len(), 1);
+        assert_eq!(index["documents"][0]["api_url"], "/api/areas/note.json");
+    }
+
+    #[test]
+    fn test_toc_ids_are_rendered() {
+        let temp_dir = TempDir::new().unwrap();
+        let markdown = "# Café Notes\n\n## Setup\n\ntext\n\n## Setup\n";
+        let mut doc = Document::new(
+            PathBuf::from("/input/areas/cafe.md"),
+            PathBuf::from("areas/cafe.md"),
+            "areas".to_string(),
+        );
+        doc.html_content = crate::parser::markdown_to_html(markdown).unwrap();
+        doc.toc = crate::parser::table_of_contents(markdown, Default::default());
+
+        let api = ApiGenerator::new(temp_dir.path().to_path_buf(), "/".to_string());
+        api.write_api(&[doc]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("api/areas/cafe.json")).unwrap(),
+        )
+        .unwrap();
+
+        let html = json["html"].as_str().unwrap();
+        let toc = json["toc"].as_array().unwrap();
+        assert_eq!(toc.len(), 3);
+        for entry in toc {
+            let id = entry["id"].as_str().unwrap();
+            assert!(
+                html.contains(&format!(r#"id="{}""#, id)),
+                "{} not in {}",
+                id,
+                html
+            );
+        }
+    }
+}
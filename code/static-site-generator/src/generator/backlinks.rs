@@ -5,7 +5,8 @@ use crate::parser::document::{BacklinkReference, Document};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-/// Build a reverse index of document relationships from wiki links
+/// Build a reverse index of document relationships from wiki links and
+/// typed frontmatter relationships
 pub fn build_backlink_index(documents: &[Document]) -> HashMap<PathBuf, Vec<BacklinkReference>> {
     let mut backlink_index: HashMap<PathBuf, Vec<BacklinkReference>> = HashMap::new();
 
@@ -21,6 +22,7 @@ pub fn build_backlink_index(documents: &[Document]) -> HashMap<PathBuf, Vec<Back
                         &doc.raw_content,
                         &link.wiki_link.full_match,
                     ),
+                    relationship: None,
                 };
 
                 // Add to the target document's backlinks
@@ -30,6 +32,21 @@ pub fn build_backlink_index(documents: &[Document]) -> HashMap<PathBuf, Vec<Back
                     .push(backlink);
             }
         }
+
+        // Typed frontmatter relationships appear under their inverse label
+        for relationship in &doc.relationships {
+            if let Some(target) = &relationship.resolved_path {
+                backlink_index
+                    .entry(target.clone())
+                    .or_default()
+                    .push(BacklinkReference {
+                        source_path: doc.relative_path.clone(),
+                        source_title: doc.title().to_string(),
+                        link_context: None,
+                        relationship: Some(relationship.inverse_label.clone()),
+                    });
+            }
+        }
     }
 
     backlink_index
@@ -163,6 +180,8 @@ mod tests {
                 display: None,
                 start: 0,
                 end: original.len(),
+                location: crate::parser::SourceLocation::default(),
+                embed: false,
             },
             resolved_path: if is_valid {
                 Some(PathBuf::from(target_path))
@@ -199,6 +218,29 @@ mod tests {
         assert!(index.get(&PathBuf::from("doc1.md")).is_none());
     }
 
+    #[test]
+    fn test_relationships_in_backlink_index() {
+        use crate::parser::Relationship;
+
+        let mut implementation = create_test_document("projects/parser.md", "Parser");
+        implementation.relationships = vec![Relationship {
+            key: "implements".to_string(),
+            label: "Implements".to_string(),
+            inverse_label: "Implemented by".to_string(),
+            target: "Spec".to_string(),
+            line: Some(2),
+            resolved_path: Some(PathBuf::from("resources/spec.html")),
+            target_title: Some("Spec".to_string()),
+        }];
+
+        let index = build_backlink_index(&[implementation]);
+
+        let backlinks = index.get(&PathBuf::from("resources/spec.html")).unwrap();
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].source_title, "Parser");
+        assert_eq!(backlinks[0].relationship.as_deref(), Some("Implemented by"));
+    }
+
     #[test]
     fn test_apply_backlinks_to_documents() {
         let mut documents = vec![
@@ -213,6 +255,7 @@ mod tests {
                 source_path: PathBuf::from("doc1.md"),
                 source_title: "Document 1".to_string(),
                 link_context: Some("Test context".to_string()),
+                relationship: None,
             }],
         );
 
@@ -248,6 +291,7 @@ mod tests {
             source_path: PathBuf::from("doc1.md"),
             source_title: "Document 1".to_string(),
             link_context: None,
+            relationship: None,
         }];
 
         let doc3 = create_test_document("doc3.md", "Document 3");
@@ -0,0 +1,638 @@
+//! ABOUTME: Atom, RSS and JSON Feed generation for the blog, categories and tags
+//! ABOUTME: Writes atom.xml and feed.xml (plus feed.json for the blog) with absolute URLs
+
+use crate::config::{FeedConfig, FeedContent};
+use crate::parser::{extract_summary, listed_in_language, tag_slug, Document, FrontmatterDate};
+use crate::theme::templates::FeedLink;
+use crate::utils::blog::is_blog_post;
+use crate::utils::{html_escape, url_encode_path};
+use crate::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of Atom feeds in their section's directory
+pub const ATOM_FILE_NAME: &str = "atom.xml";
+
+/// File name of RSS feeds in their section's directory
+pub const RSS_FILE_NAME: &str = "feed.xml";
+
+/// File name of the blog's JSON Feed
+pub const JSON_FEED_FILE_NAME: &str = "feed.json";
+
+lazy_static! {
+    /// `href` and `src` attributes in rendered HTML
+    static ref LINK_ATTRIBUTE_RE: Regex = Regex::new(r#"(\s(?:href|src)=")([^"]*)""#).unwrap();
+}
+
+/// A dated document in a feed
+#[derive(Debug, Clone, Copy)]
+pub struct FeedEntry<'a> {
+    pub doc: &'a Document,
+    pub published: &'a FrontmatterDate,
+    pub updated: &'a FrontmatterDate,
+}
+
+impl<'a> FeedEntry<'a> {
+    /// Entry for a document, or `None` if it has no date
+    ///
+    /// Published is the `date` (or `created`) value; updated is `modified`
+    /// when it is later.
+    fn new(doc: &'a Document) -> Option<Self> {
+        let published = doc
+            .metadata
+            .date
+            .as_ref()
+            .or(doc.metadata.created.as_ref())
+            .or(doc.metadata.modified.as_ref())?;
+        let updated = doc
+            .metadata
+            .modified
+            .as_ref()
+            .filter(|modified| *modified > published)
+            .unwrap_or(published);
+        Some(Self {
+            doc,
+            published,
+            updated,
+        })
+    }
+}
+
+/// Documents published together as Atom, RSS and JSON feeds
+#[derive(Debug)]
+pub struct Feed<'a> {
+    /// Feed title
+    pub title: String,
+    /// Directory of the feed files and the listing they mirror, such as `blog`
+    pub dir: PathBuf,
+    /// Section whose pages advertise the feed; `None` for site-wide feeds
+    pub section: Option<String>,
+    /// Entries, newest first
+    pub entries: Vec<FeedEntry<'a>>,
+}
+
+impl Feed<'_> {
+    /// Latest update of any entry
+    fn updated(&self) -> Option<&FrontmatterDate> {
+        self.entries.iter().map(|entry| entry.updated).max()
+    }
+}
+
+/// Generates Atom 1.0, RSS 2.0 and JSON Feed 1.1 feeds
+pub struct FeedGenerator {
+    /// Absolute URL of the site root, ending in `/`
+    site_url: String,
+    site_title: String,
+    language: String,
+    config: FeedConfig,
+}
+
+impl FeedGenerator {
+    /// Create a feed generator for a site rooted at the absolute `site_url`
+    pub fn new(site_url: String, site_title: String, language: String, config: FeedConfig) -> Self {
+        Self {
+            site_url,
+            site_title,
+            language,
+            config,
+        }
+    }
+
+    /// Feeds for the blog and, when enabled, for each category and tag
+    ///
+    /// Drafts, undated documents and translations out of the site language
+    /// are left out. Feeds without entries are not returned.
+    pub fn feeds<'a>(&self, documents: &'a [Document]) -> Vec<Feed<'a>> {
+        let mut entries: Vec<FeedEntry> = documents
+            .iter()
+            .filter(|doc| {
+                !doc.is_draft() && listed_in_language(doc, &self.language, &self.language)
+            })
+            .filter_map(FeedEntry::new)
+            .collect();
+        entries.sort_by(|a, b| {
+            b.published
+                .cmp(a.published)
+                .then_with(|| a.doc.title().cmp(b.doc.title()))
+        });
+
+        let mut feeds = vec![self.feed(
+            "Blog",
+            PathBuf::from("blog"),
+            None,
+            entries
+                .iter()
+                .filter(|entry| is_blog_post(&entry.doc.relative_path)),
+        )];
+
+        if self.config.categories {
+            for (category, title) in [
+                ("projects", "Projects"),
+                ("areas", "Areas"),
+                ("resources", "Resources"),
+                ("archives", "Archives"),
+            ] {
+                feeds.push(
+                    self.feed(
+                        title,
+                        PathBuf::from(category),
+                        Some(category.to_string()),
+                        entries
+                            .iter()
+                            .filter(|entry| entry.doc.effective_category() == category),
+                    ),
+                );
+            }
+        }
+
+        if self.config.tags {
+            let mut tags: BTreeMap<String, (&str, Vec<&FeedEntry>)> = BTreeMap::new();
+            for entry in &entries {
+                for tag in &entry.doc.metadata.tags {
+                    let slug = tag_slug(tag);
+                    if slug.is_empty() {
+                        continue;
+                    }
+                    let (_, tagged) = tags.entry(slug).or_insert_with(|| (tag, Vec::new()));
+                    if !tagged.iter().any(|e| std::ptr::eq(e.doc, entry.doc)) {
+                        tagged.push(entry);
+                    }
+                }
+            }
+            for (slug, (tag, tagged)) in tags {
+                let dir = Path::new("tags").join(&slug);
+                let section = format!("tags/{}", slug);
+                feeds.push(self.feed(&format!("#{}", tag), dir, Some(section), tagged));
+            }
+        }
+
+        feeds.retain(|feed| !feed.entries.is_empty());
+        feeds
+    }
+
+    fn feed<'a, 'e>(
+        &self,
+        title: &str,
+        dir: PathBuf,
+        section: Option<String>,
+        entries: impl IntoIterator<Item = &'e FeedEntry<'a>>,
+    ) -> Feed<'a>
+    where
+        'a: 'e,
+    {
+        let mut entries: Vec<FeedEntry<'a>> = entries.into_iter().copied().collect();
+        if self.config.limit > 0 {
+            entries.truncate(self.config.limit);
+        }
+        Feed {
+            title: format!("{} | {}", title, self.site_title),
+            dir,
+            section,
+            entries,
+        }
+    }
+
+    /// Write `atom.xml` and `feed.xml` for every feed, and `feed.json` for
+    /// the blog, and return their autodiscovery links (one per file written)
+    pub fn write_feeds(&self, output_dir: &Path, documents: &[Document]) -> Result<Vec<FeedLink>> {
+        let mut links = Vec::new();
+        for feed in self.feeds(documents) {
+            let dir = output_dir.join(&feed.dir);
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join(ATOM_FILE_NAME), self.render_atom(&feed))?;
+            fs::write(dir.join(RSS_FILE_NAME), self.render_rss(&feed))?;
+
+            let mut files = vec![
+                (ATOM_FILE_NAME, "application/atom+xml"),
+                (RSS_FILE_NAME, "application/rss+xml"),
+            ];
+            // Category and tag feeds are only offered as Atom and RSS
+            if feed.section.is_none() {
+                fs::write(dir.join(JSON_FEED_FILE_NAME), self.render_json_feed(&feed)?)?;
+                files.push((JSON_FEED_FILE_NAME, "application/feed+json"));
+            }
+
+            for (file_name, media_type) in files {
+                links.push(FeedLink {
+                    title: feed.title.clone(),
+                    url: self.feed_file_url(&feed, file_name),
+                    media_type: media_type.to_string(),
+                    section: feed.section.clone(),
+                });
+            }
+        }
+        Ok(links)
+    }
+
+    /// Render a feed as Atom 1.0
+    pub fn render_atom(&self, feed: &Feed) -> String {
+        let page_url = self.page_url(feed);
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}">
+  <title>{}</title>
+  <id>{}</id>
+  <link rel="self" type="application/atom+xml" href="{}"/>
+  <link rel="alternate" type="text/html" href="{}"/>
+  <updated>{}</updated>
+  <author><name>{}</name></author>
+  <generator>para-ssg</generator>
+"#,
+            html_escape(&self.language),
+            html_escape(&feed.title),
+            html_escape(&page_url),
+            html_escape(&self.feed_file_url(feed, ATOM_FILE_NAME)),
+            html_escape(&page_url),
+            feed.updated()
+                .map(|date| date.to_rfc3339())
+                .unwrap_or_default(),
+            html_escape(&self.site_title)
+        );
+
+        for entry in &feed.entries {
+            let url = self.document_url(entry.doc);
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!(
+                "    <title>{}</title>\n    <id>{}</id>\n    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n    <published>{}</published>\n    <updated>{}</updated>\n",
+                html_escape(entry.doc.title()),
+                html_escape(&self.entry_id(entry.doc)),
+                html_escape(&url),
+                entry.published.to_rfc3339(),
+                entry.updated.to_rfc3339()
+            ));
+            if let Some(author) = &entry.doc.metadata.author {
+                xml.push_str(&format!(
+                    "    <author><name>{}</name></author>\n",
+                    html_escape(author)
+                ));
+            }
+            for tag in &entry.doc.metadata.tags {
+                xml.push_str(&format!("    <category term=\"{}\"/>\n", html_escape(tag)));
+            }
+            xml.push_str(&format!(
+                "    <summary type=\"text\">{}</summary>\n",
+                html_escape(&summary(entry.doc))
+            ));
+            if self.config.content == FeedContent::Full {
+                xml.push_str(&format!(
+                    "    <content type=\"html\" xml:base=\"{}\">{}</content>\n",
+                    html_escape(&url),
+                    html_escape(&absolute_links(&entry.doc.html_content, &url))
+                ));
+            }
+            xml.push_str("  </entry>\n");
+        }
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    /// Render a feed as RSS 2.0
+    pub fn render_rss(&self, feed: &Feed) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>{}</title>
+    <link>{}</link>
+    <description>{}</description>
+    <language>{}</language>
+    <lastBuildDate>{}</lastBuildDate>
+    <atom:link rel="self" type="application/rss+xml" href="{}"/>
+    <generator>para-ssg</generator>
+"#,
+            html_escape(&feed.title),
+            html_escape(&self.page_url(feed)),
+            html_escape(&feed.title),
+            html_escape(&self.language),
+            feed.updated()
+                .map(|date| date.to_rfc2822())
+                .unwrap_or_default(),
+            html_escape(&self.feed_file_url(feed, RSS_FILE_NAME))
+        );
+
+        for entry in &feed.entries {
+            let url = self.document_url(entry.doc);
+            let id = self.entry_id(entry.doc);
+            xml.push_str("    <item>\n");
+            xml.push_str(&format!(
+                "      <title>{}</title>\n      <link>{}</link>\n      <guid isPermaLink=\"{}\">{}</guid>\n      <pubDate>{}</pubDate>\n",
+                html_escape(entry.doc.title()),
+                html_escape(&url),
+                id == url,
+                html_escape(&id),
+                entry.published.to_rfc2822()
+            ));
+            if let Some(author) = &entry.doc.metadata.author {
+                xml.push_str(&format!(
+                    "      <dc:creator>{}</dc:creator>\n",
+                    html_escape(author)
+                ));
+            }
+            for tag in &entry.doc.metadata.tags {
+                xml.push_str(&format!(
+                    "      <category>{}</category>\n",
+                    html_escape(tag)
+                ));
+            }
+            xml.push_str(&format!(
+                "      <description>{}</description>\n",
+                html_escape(&summary(entry.doc))
+            ));
+            if self.config.content == FeedContent::Full {
+                xml.push_str(&format!(
+                    "      <content:encoded>{}</content:encoded>\n",
+                    html_escape(&absolute_links(&entry.doc.html_content, &url))
+                ));
+            }
+            xml.push_str("    </item>\n");
+        }
+        xml.push_str("  </channel>\n</rss>\n");
+        xml
+    }
+
+    /// Render a feed as JSON Feed 1.1
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the feed cannot be serialized
+    pub fn render_json_feed(&self, feed: &Feed) -> Result<String> {
+        let items: Vec<serde_json::Value> = feed
+            .entries
+            .iter()
+            .map(|entry| {
+                let url = self.document_url(entry.doc);
+                let mut item = serde_json::json!({
+                    "id": self.entry_id(entry.doc),
+                    "url": url,
+                    "title": entry.doc.title(),
+                    "summary": summary(entry.doc),
+                    "date_published": entry.published.to_rfc3339(),
+                    "date_modified": entry.updated.to_rfc3339(),
+                    "tags": entry.doc.metadata.tags,
+                    "language": entry.doc.lang,
+                });
+                match self.config.content {
+                    FeedContent::Full => {
+                        item["content_html"] = absolute_links(&entry.doc.html_content, &url).into();
+                    }
+                    FeedContent::Summary => item["content_text"] = summary(entry.doc).into(),
+                }
+                if let Some(author) = &entry.doc.metadata.author {
+                    item["authors"] = serde_json::json!([{ "name": author }]);
+                }
+                item
+            })
+            .collect();
+
+        let json = serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": feed.title,
+            "home_page_url": self.page_url(feed),
+            "feed_url": self.feed_file_url(feed, JSON_FEED_FILE_NAME),
+            "language": self.language,
+            "authors": [{ "name": self.site_title }],
+            "items": items,
+        });
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+
+    /// Absolute URL of a document
+    fn document_url(&self, doc: &Document) -> String {
+        format!(
+            "{}{}",
+            self.site_url,
+            url_encode_path(&doc.output_path.to_string_lossy())
+        )
+    }
+
+    /// Stable entry ID: the `feed_id` frontmatter value, so moved posts are
+    /// not shown as new, or else the document URL
+    fn entry_id(&self, doc: &Document) -> String {
+        doc.metadata
+            .custom
+            .get("feed_id")
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| self.document_url(doc))
+    }
+
+    /// Absolute URL of the listing page a feed mirrors
+    fn page_url(&self, feed: &Feed) -> String {
+        format!(
+            "{}{}/",
+            self.site_url,
+            url_encode_path(&feed.dir.to_string_lossy())
+        )
+    }
+
+    fn feed_file_url(&self, feed: &Feed, file_name: &str) -> String {
+        format!("{}{}", self.page_url(feed), file_name)
+    }
+}
+
+/// Make links in a document's rendered HTML absolute, since feed readers
+/// show content away from the site
+fn absolute_links(html: &str, doc_url: &str) -> String {
+    LINK_ATTRIBUTE_RE
+        .replace_all(html, |captures: &regex::Captures| {
+            format!("{}{}\"", &captures[1], resolve_url(doc_url, &captures[2]))
+        })
+        .into_owned()
+}
+
+/// Resolve `href` against the absolute URL `base`
+///
+/// Absolute and protocol-relative URLs, fragments and other schemes such as
+/// `mailto:` are returned unchanged.
+fn resolve_url(base: &str, href: &str) -> String {
+    let has_scheme = href
+        .split_once(':')
+        .is_some_and(|(scheme, _)| !scheme.contains('/'));
+    let Some(host_start) = base.find("://").map(|i| i + 3) else {
+        return href.to_string();
+    };
+    if href.is_empty() || href.starts_with('#') || href.starts_with("//") || has_scheme {
+        return href.to_string();
+    }
+    let path_start = base[host_start..]
+        .find('/')
+        .map_or(base.len(), |i| host_start + i);
+    let origin = &base[..path_start];
+    if href.starts_with('/') {
+        return format!("{}{}", origin, href);
+    }
+
+    let mut segments: Vec<&str> = base[path_start..].split('/').skip(1).collect();
+    segments.pop();
+    for segment in href.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    format!("{}/{}", origin, segments.join("/"))
+}
+
+/// Plain-text summary: the `description` frontmatter value or the start of
+/// the content
+fn summary(doc: &Document) -> String {
+    doc.metadata
+        .description
+        .clone()
+        .unwrap_or_else(|| extract_summary(&doc.raw_content, 200))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(path: &str, date: &str, tags: &[&str]) -> Document {
+        let mut doc = Document::new(
+            PathBuf::from(path),
+            PathBuf::from(path),
+            "areas".to_string(),
+        );
+        doc.metadata.title = Some(format!("Post {}", date));
+        doc.metadata.date = FrontmatterDate::parse(date);
+        doc.metadata.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        doc.raw_content = "Some **text** & more.".to_string();
+        doc.html_content =
+            r#"<p>See <a href="/areas/blog/other.html">this</a>, <a href="../run.html">that</a> and <img src="//cdn.example/x.png"></p>"#
+                .to_string();
+        doc
+    }
+
+    fn generator(config: FeedConfig) -> FeedGenerator {
+        FeedGenerator::new(
+            "https://example.com/notes/".to_string(),
+            "Forge".to_string(),
+            "en".to_string(),
+            config,
+        )
+    }
+
+    #[test]
+    fn test_blog_feeds() {
+        let mut draft = post("areas/blog/draft.md", "2024-03-01", &[]);
+        draft.metadata.status = Some("draft".to_string());
+        let mut undated = post("areas/blog/undated.md", "2024-01-01", &[]);
+        undated.metadata.date = None;
+        let mut moved = post("areas/blog/new.md", "2024-02-01", &["rust"]);
+        moved.metadata.custom.insert(
+            "feed_id".to_string(),
+            serde_yaml::Value::String("urn:post:1".to_string()),
+        );
+        let documents = vec![
+            post("areas/blog/old.md", "2024-01-01", &["rust"]),
+            moved,
+            draft,
+            undated,
+            post("areas/health/run.md", "2024-02-15", &[]),
+        ];
+
+        let generator = generator(FeedConfig::default());
+        let feeds = generator.feeds(&documents);
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].title, "Blog | Forge");
+        assert_eq!(feeds[0].entries.len(), 2);
+        assert_eq!(
+            feeds[0].entries[0].doc.relative_path,
+            Path::new("areas/blog/new.md")
+        );
+
+        let atom = generator.render_atom(&feeds[0]);
+        assert!(atom.contains("<id>https://example.com/notes/blog/</id>"));
+        assert!(atom.contains(r#"href="https://example.com/notes/blog/atom.xml""#));
+        assert!(atom.contains("<updated>2024-02-01T00:00:00+00:00</updated>"));
+        assert!(atom.contains("<id>urn:post:1</id>"));
+        assert!(atom.contains("<id>https://example.com/notes/areas/blog/old.html</id>"));
+        assert!(atom.contains("href=&quot;https://example.com/areas/blog/other.html&quot;"));
+        assert!(atom.contains("href=&quot;https://example.com/notes/areas/run.html&quot;"));
+        assert!(atom.contains("src=&quot;//cdn.example/x.png&quot;"));
+        assert!(atom.contains(r#"<category term="rust"/>"#));
+
+        let rss = generator.render_rss(&feeds[0]);
+        assert!(rss.contains("<pubDate>Thu, 1 Feb 2024 00:00:00 +0000</pubDate>"));
+        assert!(rss.contains(r#"<guid isPermaLink="false">urn:post:1</guid>"#));
+        assert!(rss.contains("<content:encoded>"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.render_json_feed(&feeds[0]).unwrap()).unwrap();
+        assert_eq!(json["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(json["feed_url"], "https://example.com/notes/blog/feed.json");
+        assert_eq!(json["items"][0]["id"], "urn:post:1");
+        assert_eq!(
+            json["items"][0]["date_published"],
+            "2024-02-01T00:00:00+00:00"
+        );
+        assert!(json["items"][1]["content_html"]
+            .as_str()
+            .unwrap()
+            .contains("https://example.com/notes/areas/run.html"));
+    }
+
+    #[test]
+    fn test_entry_urls_are_percent_encoded() {
+        let documents = vec![post("areas/blog/Café notes.md", "2024-01-01", &[])];
+        let generator = generator(FeedConfig::default());
+        let feeds = generator.feeds(&documents);
+        let url = "https://example.com/notes/areas/blog/Caf%C3%A9%20notes.html";
+
+        let atom = generator.render_atom(&feeds[0]);
+        assert!(atom.contains(&format!("<id>{}</id>", url)));
+        assert!(atom.contains(&format!(r#"href="{}""#, url)));
+        let rss = generator.render_rss(&feeds[0]);
+        assert!(rss.contains(&format!("<link>{}</link>", url)));
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.render_json_feed(&feeds[0]).unwrap()).unwrap();
+        assert_eq!(json["items"][0]["url"], url);
+    }
+
+    #[test]
+    fn test_category_and_tag_feeds() {
+        let documents = vec![
+            post("areas/blog/a.md", "2024-01-01", &["Rust", "rust"]),
+            post("areas/health/b.md", "2024-01-02", &["health/sleep"]),
+        ];
+        let generator = generator(FeedConfig {
+            content: FeedContent::Summary,
+            categories: true,
+            tags: true,
+            limit: 1,
+        });
+        let feeds = generator.feeds(&documents);
+        let dirs: Vec<_> = feeds.iter().map(|feed| feed.dir.clone()).collect();
+        assert_eq!(
+            dirs,
+            vec![
+                PathBuf::from("blog"),
+                PathBuf::from("areas"),
+                PathBuf::from("tags/health/sleep"),
+                PathBuf::from("tags/rust"),
+            ]
+        );
+        assert_eq!(feeds[1].entries.len(), 1);
+        assert_eq!(feeds[3].entries.len(), 1);
+        assert_eq!(feeds[3].section.as_deref(), Some("tags/rust"));
+
+        let atom = generator.render_atom(&feeds[0]);
+        assert!(atom.contains("<summary type=\"text\">Some"));
+        assert!(atom.contains("&amp; more.</summary>"));
+        assert!(!atom.contains("<content"));
+
+        // Only the blog gets a JSON Feed
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let links = generator.write_feeds(temp_dir.path(), &documents).unwrap();
+        assert_eq!(links.len(), 3 + 2 * 3);
+        assert!(temp_dir.path().join("blog/feed.json").exists());
+        assert!(temp_dir.path().join("areas/atom.xml").exists());
+        assert!(!temp_dir.path().join("areas/feed.json").exists());
+        assert!(!temp_dir.path().join("tags/rust/feed.json").exists());
+    }
+}
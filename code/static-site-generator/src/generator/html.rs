@@ -1,15 +1,25 @@
 //! ABOUTME: HTML generation and templating functionality
 //! ABOUTME: Creates static HTML pages from parsed documents
 
-use crate::config::BlogConfig;
-use crate::parser::Document;
-use crate::theme::comments::render_comments_widget;
+use crate::config::{BlogConfig, CitationStyle, PaginationConfig};
+use crate::parser::{
+    format_reference, listed_in_language, sort_entries, tag_tree, BacklinkReference, BibEntry,
+    Document, Glossary, TagNode, TAGS_DIR,
+};
+use crate::theme::comments::render_localized_comments_widget;
+use crate::theme::i18n::Messages;
 use crate::theme::styles::get_default_styles;
 use crate::theme::templates::{
-    BreadcrumbItem, DocumentMetadata as TemplateMetadata, DocumentSummary, TemplateEngine,
+    ArchivePeriod, BreadcrumbItem, DocumentMetadata as TemplateMetadata, DocumentSummary, FeedLink,
+    GlossaryEntry, PageLanguage, Pagination, ReferenceEntry, TagCloudEntry, TaskEntry, TaskGroup,
+    TaskSection, TemplateEngine,
+};
+use crate::utils::blog::{
+    blog_archive, is_blog_post, ArchiveMonth, ArchiveYear, BLOG_ARCHIVE_DIR, BLOG_DIR,
 };
-use crate::utils::blog::is_blog_post;
+use crate::utils::html_escape;
 use crate::{ParaSsgError, Result};
+use chrono::NaiveDate;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -21,6 +31,13 @@ pub struct HtmlGenerator {
     site_title: String,
     base_url: String,
     blog_config: BlogConfig,
+    /// Site languages, default first; listings link across them when
+    /// there is more than one
+    languages: Vec<String>,
+    /// Listing page sizes
+    pagination: PaginationConfig,
+    /// Day the site is built, for marking overdue tasks
+    build_date: NaiveDate,
 }
 
 impl HtmlGenerator {
@@ -32,15 +49,101 @@ impl HtmlGenerator {
         blog_config: BlogConfig,
     ) -> Self {
         Self {
-            template_engine: TemplateEngine::new(),
+            template_engine: TemplateEngine::new()
+                .with_tag_pages(format!("{}{}/", base_url, TAGS_DIR)),
             output_dir,
             styles: get_default_styles(),
             site_title,
             base_url,
             blog_config,
+            languages: Vec::new(),
+            pagination: PaginationConfig::default(),
+            build_date: chrono::Local::now().date_naive(),
         }
     }
 
+    /// Mark tasks due before `build_date` as overdue (defaults to today)
+    pub fn with_build_date(mut self, build_date: NaiveDate) -> Self {
+        self.build_date = build_date;
+        self
+    }
+
+    /// Split category, subdirectory and blog listings into pages
+    pub fn with_pagination(mut self, pagination: PaginationConfig) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Localize UI strings with `messages` and generate per-language
+    /// listings and search indexes for `languages` (the site language first)
+    pub fn with_languages(mut self, messages: Messages, languages: Vec<String>) -> Self {
+        self.template_engine =
+            std::mem::take(&mut self.template_engine).with_messages(messages, languages.len() > 1);
+        self.languages = languages;
+        self
+    }
+
+    /// Add autodiscovery links for generated feeds
+    pub fn with_feeds(mut self, feeds: Vec<FeedLink>) -> Self {
+        self.template_engine = std::mem::take(&mut self.template_engine).with_feeds(feeds);
+        self
+    }
+
+    /// Whether a document is shown in listings for `lang` (the site
+    /// language when `None`)
+    fn is_listed(&self, doc: &Document, lang: Option<&str>) -> bool {
+        let default_language = self.template_engine.default_language();
+        listed_in_language(doc, lang.unwrap_or(default_language), default_language)
+    }
+
+    /// URL of the listing root for a language: the site root for the site
+    /// language, `<lang>/` otherwise
+    pub fn language_root(&self, lang: &str) -> String {
+        if lang == self.template_engine.default_language() {
+            self.base_url.clone()
+        } else {
+            format!("{}{}/", self.base_url, lang)
+        }
+    }
+
+    /// Language and URL of a listing page in every site language, or
+    /// nothing for single-language sites
+    fn listing_alternates(&self, page: &str) -> Vec<(String, String)> {
+        if self.languages.len() < 2 {
+            return Vec::new();
+        }
+        self.languages
+            .iter()
+            .map(|lang| {
+                (
+                    lang.clone(),
+                    format!("{}{}", self.language_root(lang), page),
+                )
+            })
+            .collect()
+    }
+
+    /// Render a language switcher naming the current language and linking
+    /// to each alternate
+    fn render_language_switcher(&self, lang: &str, alternates: &[(String, String)]) -> String {
+        let mut html = format!(
+            r#"<nav class="language-switcher" aria-label="{}"><span class="current-language" lang="{}">{}</span>"#,
+            html_escape(&self.template_engine.message(Some(lang), "translations")),
+            html_escape(lang),
+            html_escape(&self.template_engine.language_name(lang))
+        );
+        for (alternate, url) in alternates.iter().filter(|(alternate, _)| alternate != lang) {
+            html.push_str(&format!(
+                r#" <a href="{}" hreflang="{1}" lang="{1}">{2}</a>"#,
+                url,
+                html_escape(alternate),
+                html_escape(&self.template_engine.language_name(alternate))
+            ));
+        }
+        html.push_str("</nav>");
+        html
+    }
+
     /// Generate a single document page
     pub fn generate_document_page(&self, doc: &Document) -> Result<String> {
         // Convert document metadata to template metadata
@@ -52,38 +155,81 @@ impl HtmlGenerator {
                 .as_ref()
                 .or(doc.metadata.modified.as_ref())
                 .or(doc.metadata.created.as_ref())
-                .map(|d| d.format("%Y-%m-%d").to_string()),
+                .map(|d| d.display()),
             status: doc.metadata.status.clone(),
             tags: doc.metadata.tags.clone(),
+            stats: Some(doc.stats),
+            tasks: doc.task_progress(),
         };
 
-        // Convert backlinks to HTML format
+        let relationships_html = self.render_relationships(doc);
+
+        // Convert backlinks to HTML format, typed relationships grouped under
+        // their label after the plain wiki link backlinks
         let backlinks_html = if !doc.backlinks.is_empty() {
-            let mut backlinks_list = String::from("<ul class=\"backlinks-list\">");
+            let mut groups: Vec<(Option<&str>, Vec<&BacklinkReference>)> = Vec::new();
             for bl in &doc.backlinks {
-                let url = format!(
-                    "{}{}",
-                    self.base_url,
-                    bl.source_path.with_extension("html").display()
-                );
-                backlinks_list.push_str(&format!(
-                    r#"<li><a href="{}">{}</a></li>"#,
-                    url,
-                    html_escape(&bl.source_title)
-                ));
+                let label = bl.relationship.as_deref();
+                match groups.iter_mut().find(|(l, _)| *l == label) {
+                    Some((_, group)) => group.push(bl),
+                    None => groups.push((label, vec![bl])),
+                }
+            }
+            groups.sort_by_key(|(label, _)| label.is_some());
+
+            let mut backlinks_list = String::new();
+            for (label, group) in groups {
+                if let Some(label) = label {
+                    backlinks_list.push_str(&format!(
+                        r#"<h3 class="backlinks-relationship">{}</h3>"#,
+                        html_escape(label)
+                    ));
+                }
+                backlinks_list.push_str("<ul class=\"backlinks-list\">");
+                for bl in group {
+                    let url = format!(
+                        "{}{}",
+                        self.base_url,
+                        bl.source_path.with_extension("html").display()
+                    );
+                    backlinks_list.push_str(&format!(
+                        r#"<li><a href="{}">{}</a></li>"#,
+                        url,
+                        html_escape(&bl.source_title)
+                    ));
+                }
+                backlinks_list.push_str("</ul>");
             }
-            backlinks_list.push_str("</ul>");
             Some(backlinks_list)
         } else {
             None
         };
 
+        // Link to translations, this page first for hreflang alternates
+        let alternates: Vec<(String, String)> = if doc.translations.is_empty() {
+            Vec::new()
+        } else {
+            std::iter::once((doc.lang.clone(), doc.output_path.as_path()))
+                .chain(
+                    doc.translations
+                        .iter()
+                        .map(|t| (t.lang.clone(), t.output_path.as_path())),
+                )
+                .map(|(lang, path)| (lang, format!("{}{}", self.base_url, path.display())))
+                .collect()
+        };
+        let switcher_html =
+            (!alternates.is_empty()).then(|| self.render_language_switcher(&doc.lang, &alternates));
+
         // Generate document HTML
         let mut doc_content = self.template_engine.render_document(
             doc.title(),
             &doc.html_content,
             &template_meta,
+            relationships_html.as_deref(),
             backlinks_html.as_deref(),
+            switcher_html.as_deref(),
+            Some(&doc.lang),
         )?;
 
         // If this is a blog post and comments are configured, inject comments widget
@@ -95,10 +241,12 @@ impl HtmlGenerator {
                 .get("github_issue")
                 .and_then(|v| v.as_str());
 
-            let comments_html = render_comments_widget(
+            let comments_html = render_localized_comments_widget(
                 &self.blog_config.github_owner,
                 &self.blog_config.github_repo,
                 github_issue,
+                self.template_engine.messages(),
+                &doc.lang,
             );
 
             // Inject comments before closing </article> tag
@@ -125,7 +273,12 @@ impl HtmlGenerator {
         };
 
         // Generate full page
-        self.template_engine.render_base(
+        let language = PageLanguage {
+            lang: Some(doc.lang.clone()),
+            alternates,
+        };
+        let html = self.template_engine.render_localized_base(
+            &language,
             doc.title(),
             &doc_content,
             active_category,
@@ -133,14 +286,71 @@ impl HtmlGenerator {
             &self.styles,
             &self.site_title,
             &self.base_url,
-        )
+        )?;
+
+        // Keep noindex documents out of search engines as well as the sitemap
+        if doc.is_noindex() {
+            return Ok(html.replacen(
+                "</head>",
+                "    <meta name=\"robots\" content=\"noindex\">\n</head>",
+                1,
+            ));
+        }
+        Ok(html)
+    }
+
+    /// Render a document's typed relationships as a definition list
+    /// grouped by label, or `None` if it has none
+    fn render_relationships(&self, doc: &Document) -> Option<String> {
+        if doc.relationships.is_empty() {
+            return None;
+        }
+
+        let mut html = String::from("<dl class=\"relationships-list\">");
+        let mut current_label = None;
+        for relationship in &doc.relationships {
+            if current_label != Some(&relationship.label) {
+                html.push_str(&format!("<dt>{}</dt>", html_escape(&relationship.label)));
+                current_label = Some(&relationship.label);
+            }
+            match &relationship.resolved_path {
+                Some(path) => html.push_str(&format!(
+                    r#"<dd><a href="{}{}">{}</a></dd>"#,
+                    self.base_url,
+                    path.display(),
+                    html_escape(
+                        relationship
+                            .target_title
+                            .as_deref()
+                            .unwrap_or(&relationship.target)
+                    )
+                )),
+                None => html.push_str(&format!(
+                    r#"<dd><span class="wiki-link broken" title="Link target not found: {0}">{0}</span></dd>"#,
+                    html_escape(&relationship.target)
+                )),
+            }
+        }
+        html.push_str("</dl>");
+        Some(html)
     }
 
     /// Generate a category index page
     pub fn generate_category_page(&self, category: &str, documents: &[Document]) -> Result<String> {
+        Ok(self
+            .generate_category_pages(category, documents)?
+            .swap_remove(0))
+    }
+
+    /// Generate every page of a category index, the first page first
+    pub fn generate_category_pages(
+        &self,
+        category: &str,
+        documents: &[Document],
+    ) -> Result<Vec<String>> {
         // Convert documents to summaries
         let mut summaries: Vec<DocumentSummary> = documents.iter()
-            .filter(|doc| !doc.is_draft()) // Exclude drafts from listings
+            .filter(|doc| !doc.is_draft() && self.is_listed(doc, None)) // Exclude drafts and translations
             .map(|doc| {
                 let url = format!("{}{}", self.base_url, doc.output_path.display());
                 let summary = crate::parser::extract_summary(&doc.raw_content, 200);
@@ -148,8 +358,10 @@ impl HtmlGenerator {
                 DocumentSummary {
                     url,
                     title: doc.title().to_string(),
-                    date: doc.date().map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    date: doc.date().map(|d| d.display()),
                     tags: doc.metadata.tags.clone(),
+                    stats: Some(doc.stats),
+                    tasks: doc.task_progress(),
                     summary: Some(summary),
                 }
             })
@@ -163,15 +375,10 @@ impl HtmlGenerator {
             (None, None) => a.title.cmp(&b.title),
         });
 
-        // Generate category page content
-        let category_content = self
-            .template_engine
-            .render_category_index(category, &summaries)?;
-
         // Generate breadcrumbs
         let breadcrumbs = vec![
             BreadcrumbItem {
-                title: "Home".to_string(),
+                title: self.template_engine.message(None, "home"),
                 url: Some(self.base_url.clone()),
             },
             BreadcrumbItem {
@@ -181,16 +388,29 @@ impl HtmlGenerator {
         ];
         let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
 
-        // Generate full page
-        self.template_engine.render_base(
-            category_title(category),
-            &category_content,
-            Some(category),
-            Some(&breadcrumb_html),
-            &self.styles,
-            &self.site_title,
-            &self.base_url,
-        )
+        // Generate a full page for each page of entries
+        let dir_url = format!("{}{}/", self.base_url, category);
+        self.paginate(summaries, self.pagination.page_size, &dir_url)
+            .into_iter()
+            .map(|(entries, pagination)| {
+                let category_content = self.template_engine.render_paginated_category_index(
+                    category,
+                    &entries,
+                    pagination.as_ref(),
+                    None,
+                )?;
+                let html = self.template_engine.render_base(
+                    category_title(category),
+                    &category_content,
+                    Some(category),
+                    Some(&breadcrumb_html),
+                    &self.styles,
+                    &self.site_title,
+                    &self.base_url,
+                )?;
+                Ok(add_pagination_links(html, pagination.as_ref()))
+            })
+            .collect()
     }
 
     /// Generate a subdirectory index page
@@ -201,7 +421,7 @@ impl HtmlGenerator {
     ) -> Result<String> {
         // Convert documents to summaries
         let mut summaries: Vec<DocumentSummary> = documents.iter()
-            .filter(|doc| !doc.is_draft()) // Exclude drafts from listings
+            .filter(|doc| !doc.is_draft() && self.is_listed(doc, None)) // Exclude drafts and translations
             .map(|doc| {
                 let url = format!("{}{}", self.base_url, doc.output_path.display());
                 let summary = crate::parser::extract_summary(&doc.raw_content, 200);
@@ -209,8 +429,10 @@ impl HtmlGenerator {
                 DocumentSummary {
                     url,
                     title: doc.title().to_string(),
-                    date: doc.date().map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    date: doc.date().map(|d| d.display()),
                     tags: doc.metadata.tags.clone(),
+                    stats: Some(doc.stats),
+                    tasks: doc.task_progress(),
                     summary: Some(summary),
                 }
             })
@@ -226,7 +448,7 @@ impl HtmlGenerator {
 
         // Generate breadcrumbs
         let mut breadcrumbs = vec![BreadcrumbItem {
-            title: "Home".to_string(),
+            title: self.template_engine.message(None, "home"),
             url: Some(self.base_url.clone()),
         }];
 
@@ -302,9 +524,22 @@ impl HtmlGenerator {
         documents: &[Document],
         subdirectories: &[&crate::utils::DirectoryInfo],
     ) -> Result<String> {
+        Ok(self
+            .generate_subdirectory_pages_with_dirs(subdir_path, documents, subdirectories)?
+            .swap_remove(0))
+    }
+
+    /// Generate every page of a subdirectory index, the first page first;
+    /// directory cards are on the first page
+    pub fn generate_subdirectory_pages_with_dirs(
+        &self,
+        subdir_path: &Path,
+        documents: &[Document],
+        subdirectories: &[&crate::utils::DirectoryInfo],
+    ) -> Result<Vec<String>> {
         // Convert documents to summaries
         let mut summaries: Vec<DocumentSummary> = documents.iter()
-            .filter(|doc| !doc.is_draft()) // Exclude drafts from listings
+            .filter(|doc| !doc.is_draft() && self.is_listed(doc, None)) // Exclude drafts and translations
             .map(|doc| {
                 let url = format!("{}{}", self.base_url, doc.output_path.display());
                 let summary = crate::parser::extract_summary(&doc.raw_content, 200);
@@ -312,8 +547,10 @@ impl HtmlGenerator {
                 DocumentSummary {
                     url,
                     title: doc.title().to_string(),
-                    date: doc.date().map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    date: doc.date().map(|d| d.display()),
                     tags: doc.metadata.tags.clone(),
+                    stats: Some(doc.stats),
+                    tasks: doc.task_progress(),
                     summary: Some(summary),
                 }
             })
@@ -329,7 +566,7 @@ impl HtmlGenerator {
 
         // Generate breadcrumbs
         let mut breadcrumbs = vec![BreadcrumbItem {
-            title: "Home".to_string(),
+            title: self.template_engine.message(None, "home"),
             url: Some(self.base_url.clone()),
         }];
 
@@ -381,39 +618,57 @@ impl HtmlGenerator {
             .map(humanize_filename)
             .unwrap_or_else(|| "Subdirectory".to_string());
 
-        // Generate subdirectory page content with directory cards
-        let content = self.template_engine.render_subdirectory_index_with_dirs(
-            &subdir_name,
-            &summaries,
-            subdirectories,
-            &self.base_url,
-        )?;
-
-        // Generate full page
-        self.template_engine.render_base(
-            &subdir_name,
-            &content,
-            category.as_deref(),
-            Some(&breadcrumb_html),
-            &self.styles,
-            &self.site_title,
-            &self.base_url,
-        )
+        // Generate a full page for each page of entries
+        let dir_url = format!(
+            "{}{}/",
+            self.base_url,
+            subdir_path.to_string_lossy().replace('\\', "/")
+        );
+        self.paginate(summaries, self.pagination.page_size, &dir_url)
+            .into_iter()
+            .map(|(entries, pagination)| {
+                let content = self.template_engine.render_paginated_subdirectory_index(
+                    &subdir_name,
+                    &entries,
+                    subdirectories,
+                    &self.base_url,
+                    pagination.as_ref(),
+                )?;
+                let html = self.template_engine.render_base(
+                    &subdir_name,
+                    &content,
+                    category.as_deref(),
+                    Some(&breadcrumb_html),
+                    &self.styles,
+                    &self.site_title,
+                    &self.base_url,
+                )?;
+                Ok(add_pagination_links(html, pagination.as_ref()))
+            })
+            .collect()
     }
 
     /// Generate the home page with recently modified files
     pub fn generate_home_page(&self, documents: &[Document]) -> Result<String> {
+        let lang = self.template_engine.default_language().to_string();
+        self.generate_home_page_in(documents, &lang)
+    }
+
+    /// Generate the home page listing documents in `lang`
+    pub fn generate_home_page_in(&self, documents: &[Document], lang: &str) -> Result<String> {
         // Convert documents to summaries and sort by modification date
         let mut summaries: Vec<DocumentSummary> = documents.iter()
-            .filter(|doc| !doc.is_draft()) // Exclude drafts
+            .filter(|doc| !doc.is_draft() && self.is_listed(doc, Some(lang))) // Exclude drafts and translations
             .map(|doc| {
                 let url = format!("{}{}", self.base_url, doc.output_path.display());
 
                 DocumentSummary {
                     url,
                     title: doc.title().to_string(),
-                    date: doc.date().map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    date: doc.date().map(|d| d.display()),
                     tags: doc.metadata.tags.clone(),
+                    stats: Some(doc.stats),
+                    tasks: doc.task_progress(),
                     summary: None, // Don't need summary for home page
                 }
             })
@@ -431,9 +686,20 @@ impl HtmlGenerator {
         let content = self
             .template_engine
             .render_home_page(&summaries, &self.base_url)?;
+        let alternates = self.listing_alternates("");
+        let content = if alternates.is_empty() {
+            content
+        } else {
+            self.render_language_switcher(lang, &alternates) + &content
+        };
 
         // Generate full page
-        self.template_engine.render_base(
+        let language = PageLanguage {
+            lang: Some(lang.to_string()),
+            alternates,
+        };
+        self.template_engine.render_localized_base(
+            &language,
             "", // Empty title for home page
             &content,
             None,
@@ -446,9 +712,31 @@ impl HtmlGenerator {
 
     /// Generate a blog listing page
     pub fn generate_blog_listing_page(&self, blog_posts: &[Document]) -> Result<String> {
+        let lang = self.template_engine.default_language().to_string();
+        self.generate_blog_listing_page_in(blog_posts, &lang)
+    }
+
+    /// Generate a blog listing page for posts in `lang`
+    pub fn generate_blog_listing_page_in(
+        &self,
+        blog_posts: &[Document],
+        lang: &str,
+    ) -> Result<String> {
+        Ok(self
+            .generate_blog_listing_pages_in(blog_posts, lang)?
+            .swap_remove(0))
+    }
+
+    /// Generate every page of the blog listing for posts in `lang`, the
+    /// first page first
+    pub fn generate_blog_listing_pages_in(
+        &self,
+        blog_posts: &[Document],
+        lang: &str,
+    ) -> Result<Vec<String>> {
         // Convert blog posts to summaries
         let mut summaries: Vec<DocumentSummary> = blog_posts.iter()
-            .filter(|doc| !doc.is_draft()) // Exclude drafts from listings
+            .filter(|doc| !doc.is_draft() && self.is_listed(doc, Some(lang))) // Exclude drafts and translations
             .map(|doc| {
                 let url = format!("{}{}", self.base_url, doc.output_path.display());
                 let summary = crate::parser::extract_summary(&doc.raw_content, 200);
@@ -458,6 +746,8 @@ impl HtmlGenerator {
                     title: doc.title().to_string(),
                     date: doc.date().map(|d| d.format("%Y-%m-%d").to_string()),
                     tags: doc.metadata.tags.clone(),
+                    stats: Some(doc.stats),
+                    tasks: doc.task_progress(),
                     summary: Some(summary),
                 }
             })
@@ -471,29 +761,368 @@ impl HtmlGenerator {
             (None, None) => a.title.cmp(&b.title),
         });
 
-        // Generate blog listing content using category template
-        let blog_content = self
-            .template_engine
-            .render_category_index("blog", &summaries)?;
+        let alternates = self.listing_alternates("blog/");
+        let language_switcher = if alternates.is_empty() {
+            String::new()
+        } else {
+            self.render_language_switcher(lang, &alternates)
+        };
+
+        // Sidebar linking the archive of each year and month
+        let archive = self.blog_archive(blog_posts, lang);
+        let sidebar = if archive.is_empty() {
+            String::new()
+        } else {
+            self.template_engine.render_archive_sidebar(
+                &self.archive_periods(&archive, lang),
+                &self.archive_url(lang, Path::new(BLOG_DIR).join(BLOG_ARCHIVE_DIR)),
+                Some(lang),
+            )
+        };
 
         // Generate breadcrumbs
+        let title = self.template_engine.message(Some(lang), "blog");
         let breadcrumbs = vec![
             BreadcrumbItem {
-                title: "Home".to_string(),
+                title: self.template_engine.message(Some(lang), "home"),
+                url: Some(self.language_root(lang)),
+            },
+            BreadcrumbItem {
+                title: title.clone(),
+                url: None,
+            },
+        ];
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        // Generate a full page for each page of posts, using the category
+        // template; only first pages are translations of each other
+        let dir_url = format!("{}blog/", self.language_root(lang));
+        self.paginate(summaries, self.pagination.blog_page_size, &dir_url)
+            .into_iter()
+            .map(|(entries, pagination)| {
+                let blog_content = self.template_engine.render_paginated_category_index(
+                    "blog",
+                    &entries,
+                    pagination.as_ref(),
+                    Some(lang),
+                )?;
+                let first_page = pagination.as_ref().map_or(true, |p| p.current == 1);
+                let language = PageLanguage {
+                    lang: Some(lang.to_string()),
+                    alternates: if first_page {
+                        alternates.clone()
+                    } else {
+                        Vec::new()
+                    },
+                };
+                let html = self.template_engine.render_localized_base(
+                    &language,
+                    &title,
+                    &format!(
+                        r#"{}<div class="blog-layout">{}{}</div>"#,
+                        language_switcher, blog_content, sidebar
+                    ),
+                    Some("blog"),
+                    Some(&breadcrumb_html),
+                    &self.styles,
+                    &self.site_title,
+                    &self.base_url,
+                )?;
+                Ok(add_pagination_links(html, pagination.as_ref()))
+            })
+            .collect()
+    }
+
+    /// Published blog posts in `lang` grouped by year and month
+    pub fn blog_archive<'a>(&self, blog_posts: &'a [Document], lang: &str) -> Vec<ArchiveYear<'a>> {
+        blog_archive(
+            blog_posts
+                .iter()
+                .filter(|doc| !doc.is_draft() && self.is_listed(doc, Some(lang))),
+        )
+    }
+
+    /// Generate the blog archive overview in `lang`, with post counts per
+    /// year and month
+    pub fn generate_blog_archive_page_in(
+        &self,
+        archive: &[ArchiveYear],
+        lang: &str,
+    ) -> Result<String> {
+        let content = self
+            .template_engine
+            .render_archive_overview(&self.archive_periods(archive, lang), Some(lang))?;
+        let title = self.template_engine.message(Some(lang), "blog_archive");
+        self.render_archive_base(&title, &content, lang, &[])
+    }
+
+    /// Generate the archive page of a year in `lang`, linking its months
+    pub fn generate_blog_year_page_in(&self, year: &ArchiveYear, lang: &str) -> Result<String> {
+        let summaries: Vec<DocumentSummary> = year
+            .posts()
+            .into_iter()
+            .map(|doc| self.blog_post_summary(doc))
+            .collect();
+        let months: Vec<ArchivePeriod> = year
+            .months
+            .iter()
+            .map(|month| self.month_period(month, lang))
+            .collect();
+
+        let title = year.year.to_string();
+        let content = self
+            .template_engine
+            .render_archive_page(&title, &summaries, &months)?;
+        self.render_archive_base(&title, &content, lang, &[])
+    }
+
+    /// Generate the archive page of a month in `lang`
+    pub fn generate_blog_month_page_in(&self, month: &ArchiveMonth, lang: &str) -> Result<String> {
+        let summaries: Vec<DocumentSummary> = month
+            .posts
+            .iter()
+            .map(|doc| self.blog_post_summary(doc))
+            .collect();
+
+        let title = month.title();
+        let content = self
+            .template_engine
+            .render_archive_page(&title, &summaries, &[])?;
+        let year = BreadcrumbItem {
+            title: month.year.to_string(),
+            url: Some(self.archive_url(lang, Path::new(BLOG_DIR).join(month.year.to_string()))),
+        };
+        self.render_archive_base(&title, &content, lang, &[year])
+    }
+
+    /// Wrap a blog archive page in the base template, with breadcrumbs from
+    /// the blog listing through `parents` to the page
+    fn render_archive_base(
+        &self,
+        title: &str,
+        content: &str,
+        lang: &str,
+        parents: &[BreadcrumbItem],
+    ) -> Result<String> {
+        let mut breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(Some(lang), "home"),
+                url: Some(self.language_root(lang)),
+            },
+            BreadcrumbItem {
+                title: self.template_engine.message(Some(lang), "blog"),
+                url: Some(format!("{}{}/", self.language_root(lang), BLOG_DIR)),
+            },
+        ];
+        breadcrumbs.extend(parents.iter().map(|parent| BreadcrumbItem {
+            title: parent.title.clone(),
+            url: parent.url.clone(),
+        }));
+        breadcrumbs.push(BreadcrumbItem {
+            title: title.to_string(),
+            url: None,
+        });
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        let language = PageLanguage {
+            lang: Some(lang.to_string()),
+            alternates: Vec::new(),
+        };
+        self.template_engine.render_localized_base(
+            &language,
+            title,
+            content,
+            Some("blog"),
+            Some(&breadcrumb_html),
+            &self.styles,
+            &self.site_title,
+            &self.base_url,
+        )
+    }
+
+    /// Archive years with their months, for the sidebar and overview
+    fn archive_periods(&self, archive: &[ArchiveYear], lang: &str) -> Vec<ArchivePeriod> {
+        archive
+            .iter()
+            .map(|year| ArchivePeriod {
+                title: year.year.to_string(),
+                url: self.archive_url(lang, year.dir()),
+                count: year.count(),
+                children: year
+                    .months
+                    .iter()
+                    .map(|month| self.month_period(month, lang))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn month_period(&self, month: &ArchiveMonth, lang: &str) -> ArchivePeriod {
+        ArchivePeriod {
+            title: month.title(),
+            url: self.archive_url(lang, month.dir()),
+            count: month.posts.len(),
+            children: Vec::new(),
+        }
+    }
+
+    /// URL of a blog archive directory in `lang`
+    fn archive_url(&self, lang: &str, dir: PathBuf) -> String {
+        format!(
+            "{}{}/",
+            self.language_root(lang),
+            dir.to_string_lossy().replace('\\', "/")
+        )
+    }
+
+    /// Listing entry for a blog post, dated by day like the blog listing
+    fn blog_post_summary(&self, doc: &Document) -> DocumentSummary {
+        DocumentSummary {
+            date: doc.date().map(|d| d.format("%Y-%m-%d").to_string()),
+            ..self.document_summary(doc)
+        }
+    }
+
+    /// Split listing entries into pages of `page_size`, with the position
+    /// of each page when there is more than one
+    ///
+    /// The first page is at `dir_url` and page N at `dir_url/page/N/`. An
+    /// empty listing still has a first page, and a `page_size` of 0 keeps
+    /// every entry on it.
+    fn paginate(
+        &self,
+        summaries: Vec<DocumentSummary>,
+        page_size: usize,
+        dir_url: &str,
+    ) -> Vec<(Vec<DocumentSummary>, Option<Pagination>)> {
+        if page_size == 0 || summaries.len() <= page_size {
+            return vec![(summaries, None)];
+        }
+
+        let total_items = summaries.len();
+        let page_count = total_items.div_ceil(page_size);
+        let page_urls: Vec<String> = (1..=page_count)
+            .map(|page| match page {
+                1 => dir_url.to_string(),
+                _ => format!("{}page/{}/", dir_url, page),
+            })
+            .collect();
+
+        let mut pages = Vec::with_capacity(page_count);
+        let mut summaries = summaries.into_iter();
+        for current in 1..=page_count {
+            let entries: Vec<DocumentSummary> = summaries.by_ref().take(page_size).collect();
+            pages.push((
+                entries,
+                Some(Pagination {
+                    current,
+                    page_urls: page_urls.clone(),
+                    total_items,
+                }),
+            ));
+        }
+        pages
+    }
+
+    /// Generate the open tasks page
+    ///
+    /// Unchecked tasks from non-draft documents are grouped by category, then
+    /// by project (the document's `project` relationship or its folder under
+    /// `projects/`) or, outside projects, by document. Within a group tasks
+    /// are ordered by due date, undated last, and marked overdue when due
+    /// before the build date.
+    pub fn generate_tasks_page(&self, documents: &[Document]) -> Result<String> {
+        let mut sections: Vec<(String, Vec<TaskGroup>)> = Vec::new();
+
+        let mut documents: Vec<&Document> = documents
+            .iter()
+            .filter(|doc| !doc.is_draft() && doc.tasks.iter().any(|t| !t.checked))
+            .collect();
+        documents.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+        for doc in documents {
+            let source_url = format!("{}{}", self.base_url, doc.output_path.display());
+            let (group_title, group_url) = self.task_group_for(doc, &source_url);
+
+            let category = doc.effective_category().to_string();
+            let groups = match sections.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, groups)) => groups,
+                None => {
+                    sections.push((category, Vec::new()));
+                    &mut sections.last_mut().unwrap().1
+                }
+            };
+            let group = match groups.iter_mut().position(|g| g.title == group_title) {
+                Some(index) => &mut groups[index],
+                None => {
+                    groups.push(TaskGroup {
+                        title: group_title,
+                        url: group_url,
+                        tasks: Vec::new(),
+                    });
+                    groups.last_mut().unwrap()
+                }
+            };
+
+            for task in doc.tasks.iter().filter(|t| !t.checked) {
+                group.tasks.push(TaskEntry {
+                    text: task.text.clone(),
+                    source_title: doc.title().to_string(),
+                    source_url: source_url.clone(),
+                    heading: task.heading.clone(),
+                    due: task.due.map(|d| d.format("%Y-%m-%d").to_string()),
+                    overdue: task.due.is_some_and(|d| d < self.build_date),
+                });
+            }
+        }
+
+        // PARA categories first, then any others alphabetically
+        let order = |category: &str| {
+            ["projects", "areas", "resources", "archives"]
+                .iter()
+                .position(|c| *c == category)
+                .unwrap_or(usize::MAX)
+        };
+        sections.sort_by(|(a, _), (b, _)| order(a).cmp(&order(b)).then(a.cmp(b)));
+
+        let sections: Vec<TaskSection> = sections
+            .into_iter()
+            .map(|(category, mut groups)| {
+                groups.sort_by(|a, b| a.title.cmp(&b.title));
+                for group in &mut groups {
+                    // Stable sort keeps document order for equal due dates
+                    group.tasks.sort_by(|a, b| match (&a.due, &b.due) {
+                        (Some(d1), Some(d2)) => d1.cmp(d2),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    });
+                }
+                TaskSection {
+                    title: humanize_filename(category_title(&category)),
+                    groups,
+                }
+            })
+            .collect();
+
+        let content = self.template_engine.render_tasks_page(&sections)?;
+
+        let breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(None, "home"),
                 url: Some(self.base_url.clone()),
             },
             BreadcrumbItem {
-                title: "Blog".to_string(),
+                title: "Tasks".to_string(),
                 url: None,
             },
         ];
         let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
 
-        // Generate full page
         self.template_engine.render_base(
-            "Blog",
-            &blog_content,
-            Some("blog"),
+            "Open tasks",
+            &content,
+            Some("tasks"),
             Some(&breadcrumb_html),
             &self.styles,
             &self.site_title,
@@ -501,6 +1130,297 @@ impl HtmlGenerator {
         )
     }
 
+    /// Generate the site-wide references page
+    ///
+    /// Lists every cited work once, sorted by author and year, with the
+    /// documents that cite it. Drafts are left out.
+    pub fn generate_references_page(&self, documents: &[Document]) -> Result<String> {
+        let mut documents: Vec<&Document> = documents
+            .iter()
+            .filter(|doc| !doc.is_draft() && !doc.citations.is_empty())
+            .collect();
+        documents.sort_by(|a, b| a.title().cmp(b.title()));
+
+        let mut works: Vec<BibEntry> = Vec::new();
+        for entry in documents.iter().flat_map(|doc| &doc.citations) {
+            if !works.iter().any(|work| work.key == entry.key) {
+                works.push(entry.clone());
+            }
+        }
+        sort_entries(&mut works);
+
+        let references: Vec<ReferenceEntry> = works
+            .iter()
+            .map(|work| ReferenceEntry {
+                anchor: work.anchor(),
+                html: format_reference(work, CitationStyle::AuthorDate),
+                cited_by: documents
+                    .iter()
+                    .filter(|doc| doc.citations.iter().any(|e| e.key == work.key))
+                    .map(|doc| {
+                        (
+                            doc.title().to_string(),
+                            format!("{}{}", self.base_url, doc.output_path.display()),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let content = self.template_engine.render_references_page(&references)?;
+
+        let breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(None, "home"),
+                url: Some(self.base_url.clone()),
+            },
+            BreadcrumbItem {
+                title: "References".to_string(),
+                url: None,
+            },
+        ];
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        self.template_engine.render_base(
+            "References",
+            &content,
+            None,
+            Some(&breadcrumb_html),
+            &self.styles,
+            &self.site_title,
+            &self.base_url,
+        )
+    }
+
+    /// Generate the glossary page
+    ///
+    /// Lists every term alphabetically with its definition, the document
+    /// defining it, and the documents that link it. Drafts are left out of
+    /// the back-references.
+    pub fn generate_glossary_page(
+        &self,
+        glossary: &Glossary,
+        documents: &[Document],
+    ) -> Result<String> {
+        let mut documents: Vec<&Document> = documents
+            .iter()
+            .filter(|doc| !doc.is_draft() && !doc.glossary_terms.is_empty())
+            .collect();
+        documents.sort_by(|a, b| a.title().cmp(b.title()));
+
+        let entries: Vec<GlossaryEntry> = glossary
+            .terms()
+            .iter()
+            .map(|term| GlossaryEntry {
+                anchor: term.anchor(),
+                term: term.term.clone(),
+                definition_html: term.definition_html.clone(),
+                source_title: term.source_title.clone(),
+                source_url: format!("{}{}", self.base_url, term.output_path.display()),
+                used_in: documents
+                    .iter()
+                    .filter(|doc| doc.glossary_terms.contains(&term.term))
+                    .map(|doc| {
+                        (
+                            doc.title().to_string(),
+                            format!("{}{}", self.base_url, doc.output_path.display()),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let content = self.template_engine.render_glossary_page(&entries)?;
+
+        let breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(None, "home"),
+                url: Some(self.base_url.clone()),
+            },
+            BreadcrumbItem {
+                title: "Glossary".to_string(),
+                url: None,
+            },
+        ];
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        self.template_engine.render_base(
+            "Glossary",
+            &content,
+            None,
+            Some(&breadcrumb_html),
+            &self.styles,
+            &self.site_title,
+            &self.base_url,
+        )
+    }
+
+    /// Tag hierarchy of the published documents in the site language
+    pub fn tag_tree<'a>(&self, documents: &'a [Document]) -> Vec<TagNode<'a>> {
+        tag_tree(
+            documents
+                .iter()
+                .filter(|doc| !doc.is_draft() && self.is_listed(doc, None)),
+        )
+    }
+
+    /// Generate the tag index, a cloud of every tag sized by its number of
+    /// documents, with nested tags under their parents
+    pub fn generate_tag_index_page(&self, tags: &[TagNode]) -> Result<String> {
+        let max_count = tags
+            .iter()
+            .flat_map(|tag| tag.walk())
+            .map(|tag| tag.count())
+            .max()
+            .unwrap_or(1);
+        let content = self
+            .template_engine
+            .render_tag_index(&self.tag_cloud(tags, max_count))?;
+
+        let breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(None, "home"),
+                url: Some(self.base_url.clone()),
+            },
+            BreadcrumbItem {
+                title: "Tags".to_string(),
+                url: None,
+            },
+        ];
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        self.template_engine.render_base(
+            "Tags",
+            &content,
+            None,
+            Some(&breadcrumb_html),
+            &self.styles,
+            &self.site_title,
+            &self.base_url,
+        )
+    }
+
+    /// Generate the page of a tag, listing the documents with the tag or
+    /// one nested below it, newest first
+    pub fn generate_tag_page(&self, tag: &TagNode) -> Result<String> {
+        let mut summaries: Vec<DocumentSummary> = tag
+            .all_documents()
+            .into_iter()
+            .map(|doc| self.document_summary(doc))
+            .collect();
+        sort_by_date(&mut summaries);
+
+        let max_count = tag.children.iter().map(|child| child.count()).max();
+        let subtags = self.tag_cloud(&tag.children, max_count.unwrap_or(1));
+        let content = self
+            .template_engine
+            .render_tag_page(&tag.tag, &summaries, &subtags)?;
+
+        // Home / Tags / area / health, linking every level above this one
+        let tags_url = format!("{}{}/", self.base_url, TAGS_DIR);
+        let mut breadcrumbs = vec![
+            BreadcrumbItem {
+                title: self.template_engine.message(None, "home"),
+                url: Some(self.base_url.clone()),
+            },
+            BreadcrumbItem {
+                title: "Tags".to_string(),
+                url: Some(tags_url.clone()),
+            },
+        ];
+        let names: Vec<&str> = tag.tag.split('/').collect();
+        let slugs: Vec<&str> = tag.slug.split('/').collect();
+        for (depth, name) in names.iter().enumerate() {
+            let url = (depth + 1 < slugs.len())
+                .then(|| format!("{}{}/", tags_url, slugs[..=depth].join("/")));
+            breadcrumbs.push(BreadcrumbItem {
+                title: name.to_string(),
+                url,
+            });
+        }
+        let breadcrumb_html = self.template_engine.render_breadcrumbs(&breadcrumbs)?;
+
+        // Tag feeds are advertised on their tag page
+        let section = format!("{}/{}", TAGS_DIR, tag.slug);
+        self.template_engine.render_base(
+            &format!("#{}", tag.tag),
+            &content,
+            Some(&section),
+            Some(&breadcrumb_html),
+            &self.styles,
+            &self.site_title,
+            &self.base_url,
+        )
+    }
+
+    /// Tag cloud entries for tags and their nested tags, weighted on a log
+    /// scale against the most used tag
+    fn tag_cloud(&self, tags: &[TagNode], max_count: usize) -> Vec<TagCloudEntry> {
+        tags.iter()
+            .map(|tag| {
+                let count = tag.count();
+                let weight = if max_count > 1 {
+                    1 + ((count as f64).ln() / (max_count as f64).ln() * 4.0).round() as u8
+                } else {
+                    1
+                };
+                TagCloudEntry {
+                    name: tag.name.clone(),
+                    url: format!("{}{}/{}/", self.base_url, TAGS_DIR, tag.slug),
+                    count,
+                    weight,
+                    children: self.tag_cloud(&tag.children, max_count),
+                }
+            })
+            .collect()
+    }
+
+    /// Listing entry for a document
+    fn document_summary(&self, doc: &Document) -> DocumentSummary {
+        DocumentSummary {
+            url: format!("{}{}", self.base_url, doc.output_path.display()),
+            title: doc.title().to_string(),
+            date: doc.date().map(|d| d.display()),
+            tags: doc.metadata.tags.clone(),
+            stats: Some(doc.stats),
+            tasks: doc.task_progress(),
+            summary: Some(crate::parser::extract_summary(&doc.raw_content, 200)),
+        }
+    }
+
+    /// Title and URL of the group a document's tasks are listed under
+    fn task_group_for(&self, doc: &Document, source_url: &str) -> (String, Option<String>) {
+        if let Some(project) = doc
+            .relationships
+            .iter()
+            .find(|r| r.key == "project" && !r.is_broken())
+        {
+            let url = project
+                .resolved_path
+                .as_ref()
+                .map(|path| format!("{}{}", self.base_url, path.display()));
+            let title = project
+                .target_title
+                .clone()
+                .unwrap_or(project.target.clone());
+            return (title, url);
+        }
+
+        let mut components = doc.relative_path.components();
+        if doc.effective_category() == "projects" && doc.relative_path.starts_with("projects") {
+            components.next();
+            let project_dir = components.next().map(|c| c.as_os_str().to_string_lossy());
+            if let (Some(dir), Some(_)) = (project_dir, components.next()) {
+                return (
+                    humanize_filename(&dir),
+                    Some(format!("{}projects/{}/", self.base_url, dir)),
+                );
+            }
+        }
+
+        (doc.title().to_string(), Some(source_url.to_string()))
+    }
+
     /// Write an HTML page to disk
     pub fn write_page(&self, relative_path: &Path, content: &str) -> Result<()> {
         let output_path = self.output_dir.join(relative_path);
@@ -528,7 +1448,7 @@ impl HtmlGenerator {
     /// Generate breadcrumbs for a document
     pub fn generate_breadcrumbs_for_document(&self, doc: &Document) -> Vec<BreadcrumbItem> {
         let mut breadcrumbs = vec![BreadcrumbItem {
-            title: "Home".to_string(),
+            title: self.template_engine.message(Some(&doc.lang), "home"),
             url: Some(self.base_url.clone()),
         }];
 
@@ -557,7 +1477,7 @@ impl HtmlGenerator {
                         name_str.as_ref(),
                         "projects" | "areas" | "resources" | "archives"
                     ) {
-                        category_title(&name_str).to_string()
+                        self.template_engine.message(Some(&doc.lang), &name_str)
                     } else {
                         humanize_filename(&name_str)
                     };
@@ -573,6 +1493,42 @@ impl HtmlGenerator {
     }
 }
 
+/// Output path of page `page` of the listing in `dir`: `dir/index.html`
+/// for the first page, `dir/page/N/index.html` after that
+pub fn listing_page_path(dir: &Path, page: usize) -> PathBuf {
+    if page <= 1 {
+        dir.join("index.html")
+    } else {
+        dir.join("page").join(page.to_string()).join("index.html")
+    }
+}
+
+/// Add `rel="prev"` and `rel="next"` links between listing pages to the
+/// page head
+fn add_pagination_links(html: String, pagination: Option<&Pagination>) -> String {
+    let Some(pagination) = pagination else {
+        return html;
+    };
+    let mut links = String::new();
+    if let Some(url) = pagination.prev_url() {
+        links.push_str(&format!("    <link rel=\"prev\" href=\"{}\">\n", url));
+    }
+    if let Some(url) = pagination.next_url() {
+        links.push_str(&format!("    <link rel=\"next\" href=\"{}\">\n", url));
+    }
+    html.replacen("</head>", &format!("{}</head>", links), 1)
+}
+
+/// Sort listing entries by date, newest first, then undated ones by title
+fn sort_by_date(summaries: &mut [DocumentSummary]) {
+    summaries.sort_by(|a, b| match (&b.date, &a.date) {
+        (Some(d1), Some(d2)) => d1.cmp(d2),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.title.cmp(&b.title),
+    });
+}
+
 /// Get human-readable category title
 fn category_title(category: &str) -> &str {
     match category {
@@ -613,20 +1569,11 @@ pub fn humanize_filename(filename: &str) -> String {
         .join(" ")
 }
 
-/// HTML escape special characters
-fn html_escape(text: &str) -> String {
-    text.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#39;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::BlogConfig;
-    use crate::parser::{Document, DocumentMetadata};
+    use crate::parser::{ContentStats, Document, DocumentMetadata};
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -667,6 +1614,56 @@ mod tests {
         assert!(html.contains("Test Site"));
     }
 
+    #[test]
+    fn test_generate_translated_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        )
+        .with_languages(
+            Messages::new("en"),
+            vec!["en".to_string(), "es".to_string()],
+        );
+
+        let mut documents: Vec<Document> = ["resources/idea.md", "resources/idea.es.md"]
+            .iter()
+            .map(|path| {
+                Document::new(
+                    PathBuf::from(path),
+                    PathBuf::from(path),
+                    "resources".to_string(),
+                )
+            })
+            .collect();
+        documents[1].lang = "es".to_string();
+        documents[1].translation_key = documents[0].translation_key.clone();
+        documents[1].metadata.title = Some("La idea".to_string());
+        crate::parser::link_translations(&mut documents);
+
+        let html = generator.generate_document_page(&documents[1]).unwrap();
+        assert!(html.contains(r#"<html lang="es""#));
+        assert!(html.contains(r#"data-search-index="/search-index.es.json""#));
+        assert!(
+            html.contains(r#"<link rel="alternate" hreflang="en" href="/resources/idea.html">"#)
+        );
+        assert!(
+            html.contains(r#"<a href="/resources/idea.html" hreflang="en" lang="en">English</a>"#)
+        );
+        assert!(html.contains("Inicio"));
+
+        let home = generator.generate_home_page(&documents).unwrap();
+        assert!(home.contains("/resources/idea.html"));
+        assert!(!home.contains("/resources/idea.es.html"));
+        assert!(home.contains(r#"<link rel="alternate" hreflang="es" href="/es/">"#));
+
+        let home_es = generator.generate_home_page_in(&documents, "es").unwrap();
+        assert!(home_es.contains("/resources/idea.es.html"));
+        assert!(!home_es.contains("/resources/idea.html"));
+    }
+
     #[test]
     fn test_generate_category_page() {
         let temp_dir = TempDir::new().unwrap();
@@ -695,6 +1692,299 @@ mod tests {
         assert!(html.contains("This is a test project"));
     }
 
+    #[test]
+    fn test_generate_paginated_category_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        )
+        .with_pagination(PaginationConfig {
+            page_size: 2,
+            blog_page_size: 0,
+        });
+
+        let documents: Vec<Document> = (1..=5)
+            .map(|i| {
+                let mut doc = Document::new(
+                    PathBuf::from(format!("/input/projects/p{}.md", i)),
+                    PathBuf::from(format!("projects/p{}.md", i)),
+                    "projects".to_string(),
+                );
+                doc.metadata.title = Some(format!("Project {}", i));
+                doc
+            })
+            .collect();
+
+        let pages = generator
+            .generate_category_pages("projects", &documents)
+            .unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(pages[0].contains("5 documents in this category"));
+        assert!(pages[0].contains("Project 1") && pages[0].contains("Project 2"));
+        assert!(!pages[0].contains(r#"rel="prev""#));
+        assert!(pages[0].contains(r#"<link rel="next" href="/projects/page/2/">"#));
+
+        assert!(pages[1].contains("Project 3") && !pages[1].contains("Project 1"));
+        assert!(pages[1].contains(r#"<link rel="prev" href="/projects/">"#));
+        assert!(pages[1].contains(r#"<li><span aria-current="page">2</span></li>"#));
+        assert!(pages[2].contains(r#"<a href="/projects/page/2/" rel="prev""#));
+        assert!(!pages[2].contains(r#"rel="next""#));
+
+        assert_eq!(
+            listing_page_path(Path::new("projects"), 1),
+            PathBuf::from("projects/index.html")
+        );
+        assert_eq!(
+            listing_page_path(Path::new("projects"), 3),
+            PathBuf::from("projects/page/3/index.html")
+        );
+    }
+
+    fn numbered_documents(dir: &str, count: usize) -> Vec<Document> {
+        (1..=count)
+            .map(|i| {
+                let mut doc = Document::new(
+                    PathBuf::from(format!("/input/{}/p{}.md", dir, i)),
+                    PathBuf::from(format!("{}/p{}.md", dir, i)),
+                    "areas".to_string(),
+                );
+                doc.metadata.title = Some(format!("Post {}", i));
+                doc.metadata.date =
+                    crate::parser::FrontmatterDate::parse(&format!("2024-01-0{}", i));
+                doc
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_paginated_blog_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/site/".to_string(),
+            BlogConfig::new(),
+        )
+        .with_pagination(PaginationConfig {
+            page_size: 0,
+            blog_page_size: 2,
+        });
+
+        let pages = generator
+            .generate_blog_listing_pages_in(&numbered_documents("areas/blog", 3), "en")
+            .unwrap();
+        assert_eq!(pages.len(), 2);
+        // Newest first
+        assert!(pages[0].contains("Post 3") && pages[0].contains("Post 2"));
+        assert!(pages[0].contains(r#"<link rel="next" href="/site/blog/page/2/">"#));
+        assert!(pages[1].contains("Post 1") && !pages[1].contains("Post 3"));
+        assert!(pages[1].contains(r#"<link rel="prev" href="/site/blog/">"#));
+        assert!(!pages[1].contains(r#"<link rel="next""#));
+        // Archives stay linked from every page
+        assert!(pages[1].contains(r#"<aside class="blog-sidebar">"#));
+    }
+
+    #[test]
+    fn test_generate_paginated_subdirectory_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        )
+        .with_pagination(PaginationConfig {
+            page_size: 2,
+            blog_page_size: 0,
+        });
+        let child = crate::utils::DirectoryInfo {
+            relative_path: PathBuf::from("areas/health/sleep"),
+            category: "areas".to_string(),
+            subdirectories: Vec::new(),
+            document_count: 1,
+        };
+
+        let pages = generator
+            .generate_subdirectory_pages_with_dirs(
+                Path::new("areas/health"),
+                &numbered_documents("areas/health", 3),
+                &[&child],
+            )
+            .unwrap();
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].contains("4 items in this directory"));
+        assert!(pages[0].contains(r#"class="directory-card""#));
+        assert!(!pages[1].contains(r#"class="directory-card""#));
+        assert!(pages[1].contains(r#"<link rel="prev" href="/areas/health/">"#));
+        assert!(pages[0].contains(r#"<a href="/areas/health/page/2/">2</a>"#));
+    }
+
+    #[test]
+    fn test_add_pagination_links() {
+        let html = "<html><head>\n</head><body></body></html>".to_string();
+        let middle = Pagination {
+            current: 2,
+            page_urls: vec![
+                "/blog/".to_string(),
+                "/blog/page/2/".to_string(),
+                "/blog/page/3/".to_string(),
+            ],
+            total_items: 7,
+        };
+
+        let linked = add_pagination_links(html.clone(), Some(&middle));
+        assert_eq!(
+            linked,
+            "<html><head>\n    <link rel=\"prev\" href=\"/blog/\">\n    <link rel=\"next\" href=\"/blog/page/3/\">\n</head><body></body></html>"
+        );
+        assert_eq!(add_pagination_links(html.clone(), None), html);
+    }
+
+    #[test]
+    fn test_generate_blog_archives() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        );
+
+        let posts: Vec<Document> = [("spring", "2024-05-01"), ("winter", "2023-12-24")]
+            .iter()
+            .map(|(name, date)| {
+                let mut doc = Document::new(
+                    PathBuf::from(format!("/input/areas/blog/{}.md", name)),
+                    PathBuf::from(format!("areas/blog/{}.md", name)),
+                    "areas".to_string(),
+                );
+                doc.metadata.title = Some(name.to_string());
+                doc.metadata.date = crate::parser::FrontmatterDate::parse(date);
+                doc
+            })
+            .collect();
+
+        let listing = generator.generate_blog_listing_page(&posts).unwrap();
+        assert!(listing.contains(
+            r#"<aside class="blog-sidebar"><h2><a href="/blog/archive/">Archive</a></h2>"#
+        ));
+        assert!(listing.contains(
+            r#"<a href="/blog/2024/05/">May 2024</a> <span class="archive-count">(1)</span>"#
+        ));
+
+        let archive = generator.blog_archive(&posts, "en");
+        let overview = generator
+            .generate_blog_archive_page_in(&archive, "en")
+            .unwrap();
+        assert!(overview.contains("2 posts"));
+        assert!(overview.contains(r#"<a href="/blog/2023/">2023</a>"#));
+
+        let year = generator
+            .generate_blog_year_page_in(&archive[0], "en")
+            .unwrap();
+        assert!(year.contains("<h1>2024</h1>"));
+        assert!(year.contains("spring") && !year.contains("winter"));
+
+        let month = generator
+            .generate_blog_month_page_in(&archive[1].months[0], "en")
+            .unwrap();
+        assert!(month.contains("<h1>December 2023</h1>"));
+        assert!(month.contains(r#"<li><a href="/blog/2023/">2023</a></li>"#));
+    }
+
+    #[test]
+    fn test_generate_tag_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        );
+
+        let mut sleep = Document::new(
+            PathBuf::from("/input/areas/sleep.md"),
+            PathBuf::from("areas/sleep.md"),
+            "areas".to_string(),
+        );
+        sleep.metadata.title = Some("Sleep".to_string());
+        sleep.metadata.tags = vec!["area/health".to_string()];
+        let mut draft = sleep.clone();
+        draft.metadata.title = Some("Draft".to_string());
+        draft.metadata.status = Some("draft".to_string());
+
+        let documents = vec![sleep, draft];
+        let tags = generator.tag_tree(&documents);
+        assert_eq!(tags.len(), 1);
+
+        let index = generator.generate_tag_index_page(&tags).unwrap();
+        assert!(index.contains(r#"<a href="/tags/area/" class="tag-weight-1">area</a>"#));
+        assert!(index.contains(r#"href="/tags/area/health/""#));
+
+        let health = generator.generate_tag_page(&tags[0].children[0]).unwrap();
+        assert!(health.contains("<h1>#area/health</h1>"));
+        assert!(health.contains("1 documents tagged area/health"));
+        assert!(health.contains(r#"<li><a href="/tags/area/">area</a></li>"#));
+        assert!(health.contains(r#"<a href="/tags/area/health/" class="tag-link">"#));
+        assert!(!health.contains("Draft"));
+
+        let area = generator.generate_tag_page(&tags[0]).unwrap();
+        assert!(area.contains("Sleep"));
+        assert!(area.contains(r#"<ul class="tag-cloud subtags">"#));
+    }
+
+    #[test]
+    fn test_generate_tasks_page() {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = HtmlGenerator::new(
+            temp_dir.path().to_path_buf(),
+            "Test Site".to_string(),
+            "/".to_string(),
+            BlogConfig::new(),
+        )
+        .with_build_date(NaiveDate::from_ymd_opt(2025, 7, 2).unwrap());
+
+        let mut todo = Document::new(
+            PathBuf::from("/input/projects/launch/todo-launch.md"),
+            PathBuf::from("projects/launch/todo-launch.md"),
+            "projects".to_string(),
+        );
+        todo.tasks = crate::parser::extract_tasks(
+            "## Release\n\n- [x] Tag release\n- [ ] Write notes\n- [ ] Publish 📅 2025-07-01\n",
+        );
+        let mut area = Document::new(
+            PathBuf::from("/input/areas/health.md"),
+            PathBuf::from("areas/health.md"),
+            "areas".to_string(),
+        );
+        area.metadata.title = Some("Health".to_string());
+        area.tasks = crate::parser::extract_tasks("- [ ] Book checkup\n");
+
+        let documents = [area, todo];
+        let html = generator.generate_tasks_page(&documents).unwrap();
+
+        assert!(html.contains("3 open tasks across 2 documents"));
+        assert!(!html.contains("Tag release"));
+        // Projects come first, grouped by project folder
+        let projects = html.find("<h2>Projects</h2>").unwrap();
+        assert!(projects < html.find("<h2>Areas</h2>").unwrap());
+        assert!(html.contains(r#"<h3><a href="/projects/launch/">Launch</a></h3>"#));
+        // Dated tasks sort before undated ones
+        assert!(html.find("Publish").unwrap() < html.find("Write notes").unwrap());
+        assert!(html.contains(r#"<time class="task-due overdue">2025-07-01</time>"#));
+        assert!(html.contains("todo-launch</a> › Release"));
+
+        // Tasks due on the build date are not overdue yet
+        let html = generator
+            .with_build_date(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap())
+            .generate_tasks_page(&documents)
+            .unwrap();
+        assert!(html.contains(r#"<time class="task-due">2025-07-01</time>"#));
+    }
+
     #[test]
     fn test_generate_home_page() {
         let temp_dir = TempDir::new().unwrap();
@@ -786,11 +2076,25 @@ mod tests {
             relative_path: PathBuf::from("areas/blog/test-post.md"),
             output_path: PathBuf::from("areas/blog/test-post.html"),
             category: "areas".to_string(),
+            lang: "en".to_string(),
+            translation_key: "areas/blog/test-post".to_string(),
+            translations: Vec::new(),
             metadata: DocumentMetadata::default(),
             raw_content: "Test blog post".to_string(),
             html_content: "<article><p>Test blog post</p></article>".to_string(),
+            stats: ContentStats::default(),
+            tasks: Vec::new(),
             wiki_links: vec![],
+            relationships: Vec::new(),
             backlinks: vec![],
+            dependencies: vec![],
+            diagnostics: vec![],
+            frontmatter_error: None,
+            line_map: Default::default(),
+            bibliography: None,
+            citations: Vec::new(),
+            glossary_terms: Vec::new(),
+            toc: Vec::new(),
         };
 
         // Add github_issue to metadata
@@ -826,11 +2130,25 @@ mod tests {
             relative_path: PathBuf::from("areas/blog/test-post.md"),
             output_path: PathBuf::from("areas/blog/test-post.html"),
             category: "areas".to_string(),
+            lang: "en".to_string(),
+            translation_key: "areas/blog/test-post".to_string(),
+            translations: Vec::new(),
             metadata: DocumentMetadata::default(),
             raw_content: "Test blog post".to_string(),
             html_content: "<article><p>Test blog post</p></article>".to_string(),
+            stats: ContentStats::default(),
+            tasks: Vec::new(),
             wiki_links: vec![],
+            relationships: Vec::new(),
             backlinks: vec![],
+            dependencies: vec![],
+            diagnostics: vec![],
+            frontmatter_error: None,
+            line_map: Default::default(),
+            bibliography: None,
+            citations: Vec::new(),
+            glossary_terms: Vec::new(),
+            toc: Vec::new(),
         };
 
         let html = generator.generate_document_page(&doc).unwrap();
@@ -855,11 +2173,25 @@ mod tests {
             relative_path: PathBuf::from("areas/blog/test-post.md"),
             output_path: PathBuf::from("areas/blog/test-post.html"),
             category: "areas".to_string(),
+            lang: "en".to_string(),
+            translation_key: "areas/blog/test-post".to_string(),
+            translations: Vec::new(),
             metadata: DocumentMetadata::default(),
             raw_content: "Test blog post".to_string(),
             html_content: "<article><p>Test blog post</p></article>".to_string(),
+            stats: ContentStats::default(),
+            tasks: Vec::new(),
             wiki_links: vec![],
+            relationships: Vec::new(),
             backlinks: vec![],
+            dependencies: vec![],
+            diagnostics: vec![],
+            frontmatter_error: None,
+            line_map: Default::default(),
+            bibliography: None,
+            citations: Vec::new(),
+            glossary_terms: Vec::new(),
+            toc: Vec::new(),
         };
 
         let html = generator.generate_document_page(&doc).unwrap();
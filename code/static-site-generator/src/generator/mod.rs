@@ -1,12 +1,18 @@
 //! ABOUTME: Site generation module for HTML output and asset creation
 //! ABOUTME: Orchestrates the creation of static website files
 
+pub mod api;
 pub mod assets;
 pub mod backlinks;
+pub mod feeds;
 pub mod html;
 pub mod search;
+pub mod sitemap;
 
+pub use api::*;
 pub use assets::*;
 pub use backlinks::*;
+pub use feeds::*;
 pub use html::*;
 pub use search::*;
+pub use sitemap::*;
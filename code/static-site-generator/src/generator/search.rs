@@ -1,7 +1,8 @@
 //! ABOUTME: Search index generation functionality
 //! ABOUTME: Creates JSON search indexes for client-side search
 
-use crate::parser::Document;
+use crate::config::UnicodeMatching;
+use crate::parser::{fold_case_insensitive, listed_in_language, Document};
 use crate::Result;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -24,8 +25,15 @@ pub struct SearchEntry {
     /// Content excerpt for search preview
     pub excerpt: String,
 
-    /// Full text content for searching (lowercased for case-insensitive search)
+    /// Full text content for searching, normalized for case-insensitive
+    /// search (see [`SearchIndex::normalization`])
     pub content: String,
+
+    /// Number of words in the document
+    pub word_count: usize,
+
+    /// Estimated reading time in minutes
+    pub reading_time: usize,
 }
 
 /// Complete search index for the site
@@ -34,6 +42,10 @@ pub struct SearchIndex {
     /// Version of the search index format
     pub version: String,
 
+    /// Unicode matching mode applied to `content`; the search script applies
+    /// the same folding to queries, titles and tags
+    pub normalization: String,
+
     /// All searchable documents
     pub documents: Vec<SearchEntry>,
 
@@ -65,7 +77,7 @@ const MAX_CONTENT_LENGTH: usize = 5000;
 
 impl SearchEntry {
     /// Create a search entry from a document
-    pub fn from_document(doc: &Document) -> Self {
+    pub fn from_document(doc: &Document, matching: UnicodeMatching) -> Self {
         let title = doc.title().to_string();
         let path = doc.output_path.to_string_lossy().to_string();
         let category = doc.effective_category().to_string();
@@ -78,7 +90,7 @@ impl SearchEntry {
         let excerpt = generate_excerpt(&plain_text, MAX_EXCERPT_LENGTH);
 
         // Process content for searching
-        let content = process_content_for_search(&plain_text, MAX_CONTENT_LENGTH);
+        let content = process_content_for_search(&plain_text, MAX_CONTENT_LENGTH, matching);
 
         Self {
             title,
@@ -87,24 +99,34 @@ impl SearchEntry {
             tags,
             excerpt,
             content,
+            word_count: doc.stats.word_count,
+            reading_time: doc.stats.reading_time,
         }
     }
 }
 
 impl SearchIndex {
     /// Create a new search index from documents
-    pub fn from_documents(documents: &[Document]) -> Self {
+    pub fn from_documents(documents: &[Document], matching: UnicodeMatching) -> Self {
+        Self::from_document_refs(&documents.iter().collect::<Vec<_>>(), matching)
+    }
+
+    /// Create a search index from a selection of documents
+    pub fn from_document_refs(documents: &[&Document], matching: UnicodeMatching) -> Self {
         let mut entries = Vec::new();
         let mut documents_by_category = std::collections::HashMap::new();
         let mut total_content_size = 0;
         let mut total_excerpt_length = 0;
 
         // Filter out draft documents
-        let published_docs: Vec<&Document> =
-            documents.iter().filter(|doc| !doc.is_draft()).collect();
+        let published_docs: Vec<&Document> = documents
+            .iter()
+            .copied()
+            .filter(|doc| !doc.is_draft())
+            .collect();
 
         for doc in &published_docs {
-            let entry = SearchEntry::from_document(doc);
+            let entry = SearchEntry::from_document(doc, matching);
 
             // Update statistics
             *documents_by_category
@@ -131,6 +153,7 @@ impl SearchIndex {
 
         Self {
             version: "1.0".to_string(),
+            normalization: matching.name().to_string(),
             documents: entries,
             stats,
         }
@@ -195,9 +218,13 @@ fn generate_excerpt(content: &str, max_length: usize) -> String {
     format!("{}...", excerpt.trim())
 }
 
-/// Process content for searching (lowercase, remove stop words)
-fn process_content_for_search(content: &str, max_length: usize) -> String {
-    let content_lower = content.to_lowercase();
+/// Process content for searching (Unicode normalization and case folding)
+fn process_content_for_search(
+    content: &str,
+    max_length: usize,
+    matching: UnicodeMatching,
+) -> String {
+    let content_lower = fold_case_insensitive(content, matching);
 
     // Truncate if too long, ensuring we don't cut in the middle of a UTF-8 character
     let truncated = if content_lower.len() > max_length {
@@ -217,8 +244,12 @@ fn process_content_for_search(content: &str, max_length: usize) -> String {
 }
 
 /// Generate search index from documents and save to output directory
-pub fn generate_search_index(documents: &[Document], output_dir: &Path) -> Result<()> {
-    let index = SearchIndex::from_documents(documents);
+pub fn generate_search_index(
+    documents: &[Document],
+    output_dir: &Path,
+    matching: UnicodeMatching,
+) -> Result<()> {
+    let index = SearchIndex::from_documents(documents, matching);
 
     // Print statistics
     println!("Search index statistics:");
@@ -248,9 +279,40 @@ pub fn generate_search_index(documents: &[Document], output_dir: &Path) -> Resul
     Ok(())
 }
 
+/// Save a search index per language as `search-index.<lang>.json`
+///
+/// `languages` lists the site language first; its index also covers
+/// documents that have no translation in it.
+pub fn generate_language_search_indexes(
+    documents: &[Document],
+    output_dir: &Path,
+    matching: UnicodeMatching,
+    languages: &[String],
+) -> Result<()> {
+    let Some(default_language) = languages.first() else {
+        return Ok(());
+    };
+    for lang in languages {
+        let listed: Vec<&Document> = documents
+            .iter()
+            .filter(|doc| listed_in_language(doc, lang, default_language))
+            .collect();
+        let index = SearchIndex::from_document_refs(&listed, matching);
+        let index_path = output_dir.join(format!("search-index.{}.json", lang));
+        std::fs::write(&index_path, serde_json::to_string_pretty(&index)?)?;
+        println!(
+            "Generated search index: {} ({} documents)",
+            index_path.display(),
+            index.stats.total_documents
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::ContentStats;
     use std::path::PathBuf;
 
     fn create_test_document(title: &str, content: &str, tags: Vec<String>) -> Document {
@@ -262,6 +324,7 @@ mod tests {
         doc.metadata.title = Some(title.to_string());
         doc.metadata.tags = tags;
         doc.html_content = format!("<p>{}</p>", content);
+        doc.stats = ContentStats::from_markdown(content);
         doc
     }
 
@@ -302,7 +365,7 @@ mod tests {
             vec!["test".to_string(), "search".to_string()],
         );
 
-        let entry = SearchEntry::from_document(&doc);
+        let entry = SearchEntry::from_document(&doc, UnicodeMatching::default());
 
         assert_eq!(entry.title, "Test Document");
         assert_eq!(entry.path, "Test Document.html");
@@ -311,6 +374,8 @@ mod tests {
         assert!(entry.excerpt.contains("content of the test document"));
         assert!(entry.content.contains("content of the test document"));
         assert_eq!(entry.content, entry.content.to_lowercase());
+        assert_eq!(entry.word_count, 12);
+        assert_eq!(entry.reading_time, 1);
     }
 
     #[test]
@@ -320,7 +385,7 @@ mod tests {
             create_test_document("Doc2", "Content for document two", vec!["tag2".to_string()]),
         ];
 
-        let index = SearchIndex::from_documents(&docs);
+        let index = SearchIndex::from_documents(&docs, UnicodeMatching::default());
 
         assert_eq!(index.version, "1.0");
         assert_eq!(index.documents.len(), 2);
@@ -336,18 +401,33 @@ mod tests {
         let published_doc = create_test_document("Published", "Published content", vec![]);
 
         let docs = vec![draft_doc, published_doc];
-        let index = SearchIndex::from_documents(&docs);
+        let index = SearchIndex::from_documents(&docs, UnicodeMatching::default());
 
         assert_eq!(index.documents.len(), 1);
         assert_eq!(index.documents[0].title, "Published");
     }
 
+    #[test]
+    fn test_search_content_normalization() {
+        let doc = create_test_document("Café", "Cafe\u{301} in der Straße", vec![]);
+
+        let entry = SearchEntry::from_document(&doc, UnicodeMatching::Normalized);
+        assert_eq!(entry.content, "café in der strasse");
+
+        let entry = SearchEntry::from_document(&doc, UnicodeMatching::FoldDiacritics);
+        assert_eq!(entry.content, "cafe in der strasse");
+
+        let index = SearchIndex::from_documents(&[doc], UnicodeMatching::Strict);
+        assert_eq!(index.normalization, "strict");
+        assert_eq!(index.documents[0].content, "café in der straße");
+    }
+
     #[test]
     fn test_content_truncation() {
         let long_content = "x".repeat(10000);
         let doc = create_test_document("Long", &long_content, vec![]);
 
-        let entry = SearchEntry::from_document(&doc);
+        let entry = SearchEntry::from_document(&doc, UnicodeMatching::default());
         assert!(entry.content.len() <= MAX_CONTENT_LENGTH);
     }
 }
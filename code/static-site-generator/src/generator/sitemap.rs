@@ -0,0 +1,331 @@
+//! ABOUTME: sitemap.xml and robots.txt generation
+//! ABOUTME: Lists published pages with lastmod dates, splitting into a sitemap index when large
+
+use super::html::listing_page_path;
+use crate::parser::{DatePrecision, Document, FrontmatterDate};
+use crate::utils::{html_escape, url_encode_path};
+use crate::Result;
+use std::fs;
+use std::path::Path;
+
+/// Most URLs a single sitemap file may hold
+pub const SITEMAP_URL_LIMIT: usize = 50_000;
+
+/// File name of the sitemap, or of the sitemap index when split
+pub const SITEMAP_FILE_NAME: &str = "sitemap.xml";
+
+/// A page in the sitemap
+#[derive(Debug, Clone)]
+pub struct SitemapEntry {
+    /// Absolute URL
+    pub loc: String,
+    /// Last modification, in W3C datetime format
+    pub lastmod: Option<String>,
+}
+
+/// Pages of the site collected for sitemap.xml
+pub struct Sitemap {
+    /// Absolute URL of the site root, ending in `/`
+    site_url: String,
+    /// Disallowed paths, relative to the site root without a leading `/`
+    disallow: Vec<String>,
+    entries: Vec<SitemapEntry>,
+}
+
+impl Sitemap {
+    /// Create an empty sitemap for a site rooted at the absolute `site_url`,
+    /// leaving out paths under `disallow`
+    pub fn new(site_url: String, disallow: &[String]) -> Self {
+        Self {
+            site_url,
+            disallow: disallow
+                .iter()
+                .map(|path| path.trim_start_matches('/').to_string())
+                .collect(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a document's page, unless it is a draft or `noindex`
+    pub fn add_document(&mut self, doc: &Document) {
+        if doc.is_draft() || doc.is_noindex() {
+            return;
+        }
+        let path = doc.output_path.to_string_lossy().replace('\\', "/");
+        self.add(path, doc.date());
+    }
+
+    /// Add the listing page of a directory (`""` for the home page), last
+    /// modified when the newest of its published documents was
+    pub fn add_listing<'a>(
+        &mut self,
+        dir: &Path,
+        documents: impl IntoIterator<Item = &'a Document>,
+    ) {
+        self.add_listing_pages(dir, 1, documents);
+    }
+
+    /// Add every page of a listing split into `page_count` pages (`dir/`,
+    /// `dir/page/2/`, ...), each last modified when the newest of the
+    /// listing's published documents was
+    pub fn add_listing_pages<'a>(
+        &mut self,
+        dir: &Path,
+        page_count: usize,
+        documents: impl IntoIterator<Item = &'a Document>,
+    ) {
+        let lastmod = documents
+            .into_iter()
+            .filter(|doc| !doc.is_draft() && !doc.is_noindex())
+            .filter_map(Document::date)
+            .max();
+        for page in 1..=page_count.max(1) {
+            let page_path = listing_page_path(dir, page);
+            let page_dir = page_path.parent().unwrap_or(Path::new(""));
+            let page_dir = page_dir.to_string_lossy().replace('\\', "/");
+            let path = if page_dir.is_empty() {
+                String::new()
+            } else {
+                format!("{}/", page_dir.trim_end_matches('/'))
+            };
+            self.add(path, lastmod);
+        }
+    }
+
+    fn add(&mut self, path: String, lastmod: Option<&FrontmatterDate>) {
+        if self
+            .disallow
+            .iter()
+            .any(|rule| path.starts_with(rule.as_str()))
+        {
+            return;
+        }
+        self.entries.push(SitemapEntry {
+            loc: format!("{}{}", self.site_url, url_encode_path(&path)),
+            lastmod: lastmod.map(w3c_datetime),
+        });
+    }
+
+    /// Number of pages in the sitemap
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the sitemap has no pages
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sitemap files as (file name, XML) pairs
+    ///
+    /// Up to `limit` URLs give a single `sitemap.xml`. Beyond that, URLs are
+    /// split across `sitemap-1.xml`, `sitemap-2.xml`, ... and `sitemap.xml`
+    /// is a sitemap index pointing to them.
+    pub fn render(&self, limit: usize) -> Vec<(String, String)> {
+        let mut entries = self.entries.clone();
+        entries.sort_by(|a, b| a.loc.cmp(&b.loc));
+        entries.dedup_by(|a, b| a.loc == b.loc);
+
+        if entries.len() <= limit {
+            return vec![(SITEMAP_FILE_NAME.to_string(), render_urlset(&entries))];
+        }
+
+        let mut files = Vec::new();
+        let mut index = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for (i, chunk) in entries.chunks(limit.max(1)).enumerate() {
+            let file_name = format!("sitemap-{}.xml", i + 1);
+            index.push_str(&format!(
+                "  <sitemap>\n    <loc>{}{}</loc>\n",
+                html_escape(&self.site_url),
+                file_name
+            ));
+            if let Some(lastmod) = chunk.iter().filter_map(|e| e.lastmod.as_ref()).max() {
+                index.push_str(&format!("    <lastmod>{}</lastmod>\n", lastmod));
+            }
+            index.push_str("  </sitemap>\n");
+            files.push((file_name, render_urlset(chunk)));
+        }
+        index.push_str("</sitemapindex>\n");
+        files.insert(0, (SITEMAP_FILE_NAME.to_string(), index));
+        files
+    }
+
+    /// Write the sitemap files to the output directory
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a file cannot be written
+    pub fn write(&self, output_dir: &Path) -> Result<()> {
+        for (file_name, xml) in self.render(SITEMAP_URL_LIMIT) {
+            fs::write(output_dir.join(file_name), xml)?;
+        }
+        Ok(())
+    }
+}
+
+/// Contents of robots.txt: `disallow` rules for all crawlers under
+/// `base_path`, and the sitemap location when there is one
+pub fn robots_txt(base_path: &str, disallow: &[String], sitemap_url: Option<&str>) -> String {
+    let mut robots = String::from("User-agent: *\n");
+    if disallow.is_empty() {
+        robots.push_str("Disallow:\n");
+    }
+    for path in disallow {
+        robots.push_str(&format!(
+            "Disallow: {}/{}\n",
+            base_path.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        ));
+    }
+    if let Some(sitemap_url) = sitemap_url {
+        robots.push_str(&format!("\nSitemap: {}\n", sitemap_url));
+    }
+    robots
+}
+
+/// Path part of an absolute URL, ending in `/`: `/notes/` for
+/// `https://example.com/notes/`
+pub fn url_path_of(url: &str) -> String {
+    let after_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    match after_scheme.find('/') {
+        Some(start) => format!("{}/", after_scheme[start..].trim_end_matches('/')),
+        None => "/".to_string(),
+    }
+}
+
+fn render_urlset(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for entry in entries {
+        xml.push_str(&format!(
+            "  <url>\n    <loc>{}</loc>\n",
+            html_escape(&entry.loc)
+        ));
+        if let Some(lastmod) = &entry.lastmod {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", lastmod));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// A date as a W3C datetime, date-only when it was written without a time
+fn w3c_datetime(date: &FrontmatterDate) -> String {
+    match date.precision() {
+        DatePrecision::Day => date.format("%Y-%m-%d").to_string(),
+        _ => date.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn document(path: &str, date: Option<&str>) -> Document {
+        let mut doc = Document::new(
+            PathBuf::from(path),
+            PathBuf::from(path),
+            "projects".to_string(),
+        );
+        doc.metadata.date = date.and_then(FrontmatterDate::parse);
+        doc
+    }
+
+    #[test]
+    fn test_sitemap_entries() {
+        let mut draft = document("projects/draft.md", None);
+        draft.metadata.status = Some("draft".to_string());
+        let mut hidden = document("projects/hidden.md", Some("2024-09-01"));
+        hidden.metadata.custom.insert(
+            "robots".to_string(),
+            serde_yaml::Value::String("noindex, nofollow".to_string()),
+        );
+        let documents = vec![
+            document("projects/a.md", Some("2024-05-01")),
+            document("projects/b.md", Some("2024-06-01T10:00:00+02:00")),
+            document("private/c.md", None),
+            draft,
+            hidden,
+        ];
+
+        let mut sitemap = Sitemap::new(
+            "https://example.com/notes/".to_string(),
+            &["/private/".to_string()],
+        );
+        for doc in &documents {
+            sitemap.add_document(doc);
+        }
+        sitemap.add_listing_pages(Path::new("projects"), 2, &documents);
+        sitemap.add_listing(Path::new(""), &documents);
+        assert_eq!(sitemap.len(), 5);
+
+        let files = sitemap.render(SITEMAP_URL_LIMIT);
+        assert_eq!(files.len(), 1);
+        let xml = &files[0].1;
+        assert!(xml.contains(
+            "<loc>https://example.com/notes/projects/a.html</loc>\n    <lastmod>2024-05-01</lastmod>"
+        ));
+        assert!(xml.contains("<lastmod>2024-06-01T10:00:00+02:00</lastmod>"));
+        assert!(xml.contains(
+            "<loc>https://example.com/notes/projects/</loc>\n    <lastmod>2024-06-01T10:00:00+02:00</lastmod>"
+        ));
+        assert!(xml.contains("<loc>https://example.com/notes/</loc>"));
+        assert!(xml.contains(
+            "<loc>https://example.com/notes/projects/page/2/</loc>\n    <lastmod>2024-06-01T10:00:00+02:00</lastmod>"
+        ));
+        assert!(!xml.contains("draft"));
+        assert!(!xml.contains("hidden"));
+        assert!(!xml.contains("private"));
+
+        // Spaces and non-ASCII names are percent-encoded
+        let mut sitemap = Sitemap::new("https://example.com/".to_string(), &[]);
+        sitemap.add_document(&document("areas/Café notes.md", None));
+        let xml = &sitemap.render(SITEMAP_URL_LIMIT)[0].1;
+        assert!(xml.contains("<loc>https://example.com/areas/Caf%C3%A9%20notes.html</loc>"));
+    }
+
+    #[test]
+    fn test_sitemap_index_and_robots() {
+        let mut sitemap = Sitemap::new("https://example.com/".to_string(), &[]);
+        for i in 0..5 {
+            sitemap.add_document(&document(
+                &format!("projects/{}.md", i),
+                Some(&format!("2024-01-0{}", i + 1)),
+            ));
+        }
+
+        let files = sitemap.render(2);
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "sitemap.xml",
+                "sitemap-1.xml",
+                "sitemap-2.xml",
+                "sitemap-3.xml"
+            ]
+        );
+        assert!(files[0].1.contains("<sitemapindex"));
+        assert!(files[0]
+            .1
+            .contains("<loc>https://example.com/sitemap-3.xml</loc>"));
+        assert_eq!(files[3].1.matches("<url>").count(), 1);
+
+        assert_eq!(
+            robots_txt(
+                "/notes/",
+                &["private/".to_string()],
+                Some("https://example.com/notes/sitemap.xml")
+            ),
+            "User-agent: *\nDisallow: /notes/private/\n\nSitemap: https://example.com/notes/sitemap.xml\n"
+        );
+        assert_eq!(robots_txt("/", &[], None), "User-agent: *\nDisallow:\n");
+        assert_eq!(url_path_of("https://example.com/notes/"), "/notes/");
+        assert_eq!(url_path_of("https://example.com"), "/");
+    }
+}
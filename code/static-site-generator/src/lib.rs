@@ -48,10 +48,27 @@ pub enum ParaSsgError {
 pub type Result<T> = std::result::Result<T, ParaSsgError>;
 
 // Re-export Config from config module
-pub use config::{BlogConfig, Config};
+pub use config::{
+    BlogConfig, CitationStyle, Config, CustomCallout, FeedConfig, FeedContent, InlineTagMode,
+    PaginationConfig, ParserConfig, RelationshipType, SitemapConfig, UnicodeMatching,
+};
+
+/// Outcome of a successful site build
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    /// Files outside the input directory that documents depend on (e.g. code includes).
+    /// Watch mode monitors these in addition to the markdown sources.
+    pub dependencies: Vec<PathBuf>,
+
+    /// Number of error-level document diagnostics (e.g. schema violations)
+    pub errors: usize,
+
+    /// Content statistics summed per PARA category
+    pub category_stats: std::collections::BTreeMap<String, parser::CategoryStats>,
+}
 
 /// Generate static site from configuration
-pub fn generate_site(config: &Config) -> Result<()> {
+pub fn generate_site(config: &Config) -> Result<BuildReport> {
     let start_time = std::time::Instant::now();
     config.validate()?;
 
@@ -68,7 +85,7 @@ pub fn generate_site(config: &Config) -> Result<()> {
             config.input_dir
         );
         eprintln!("   Ensure your input directory contains .md files");
-        return Ok(());
+        return Ok(BuildReport::default());
     }
 
     println!("📊 Found {} documents:", stats.total_count);
@@ -99,6 +116,73 @@ pub fn generate_site(config: &Config) -> Result<()> {
     utils::create_output_directory(output_path)?;
     println!("✅ Created output directory: {}", config.output_dir);
 
+    // Load the frontmatter schema, if any
+    let mut parser_config = config.parser.clone();
+    parser_config.dev_build |= config.watch;
+    if parser_config.schema.is_none() {
+        let schema_path = parser_config
+            .schema_path
+            .clone()
+            .unwrap_or_else(|| input_path.join(parser::SCHEMA_FILE_NAME));
+        if parser_config.schema_path.is_some() || schema_path.exists() {
+            parser_config.schema = Some(parser::FrontmatterSchema::load(&schema_path)?);
+            println!("📐 Loaded frontmatter schema: {}", schema_path.display());
+        }
+    }
+
+    // Load shortcode templates from the theme directory
+    if parser_config.shortcodes.is_none() {
+        let shortcode_dir = parser_config
+            .theme_dir
+            .clone()
+            .unwrap_or_else(|| input_path.join(parser::DEFAULT_THEME_DIR))
+            .join(parser::SHORTCODE_DIR);
+        if shortcode_dir.is_dir() {
+            let templates = parser::ShortcodeTemplates::load(&shortcode_dir)?;
+            println!(
+                "🧩 Loaded {} shortcode template(s) from {}",
+                templates.len(),
+                shortcode_dir.display()
+            );
+            parser_config.shortcodes = Some(Arc::new(templates));
+        }
+    }
+
+    // Load the site bibliography for citations
+    let mut site_dependencies = Vec::new();
+    if parser_config.bibliography.is_none() {
+        let bibliography_path = parser_config.bibliography_path.clone().or_else(|| {
+            parser::BIBLIOGRAPHY_FILE_NAMES
+                .iter()
+                .map(|name| input_path.join(name))
+                .find(|path| path.exists())
+        });
+        if let Some(bibliography_path) = bibliography_path {
+            let bibliography = parser::Bibliography::load(&bibliography_path)?;
+            println!(
+                "📚 Loaded {} bibliography entries from {}",
+                bibliography.len(),
+                bibliography_path.display()
+            );
+            parser_config.bibliography = Some(Arc::new(bibliography));
+            site_dependencies.push(bibliography_path);
+        }
+    }
+
+    // Read git history once so the parallel parse only does lookups
+    if parser_config.git_dates && parser_config.git_history.is_none() {
+        match utils::GitHistory::load(input_path) {
+            Some(history) => {
+                println!("🕓 Read git history for {} files", history.len());
+                parser_config.git_history = Some(Arc::new(history));
+            }
+            None => println!(
+                "⚠️  Git dates enabled but '{}' is not in a git repository; using file times",
+                config.input_dir
+            ),
+        }
+    }
+
     // Parse all documents in parallel (first pass - basic parsing)
     println!("📝 Parsing documents...");
     let total_docs = document_infos.len();
@@ -108,10 +192,11 @@ pub fn generate_site(config: &Config) -> Result<()> {
     let parse_results: Vec<_> = document_infos
         .par_iter()
         .map(|doc_info| {
-            let result = parser::parse_document(
+            let result = parser::parse_document_with_config(
                 &doc_info.path,
                 &doc_info.relative_path,
                 doc_info.category.clone(),
+                &parser_config,
             );
 
             if config.verbose {
@@ -145,10 +230,32 @@ pub fn generate_site(config: &Config) -> Result<()> {
     let mut documents = Vec::new();
     let mut parse_errors = 0;
     let mut parse_warnings = Vec::new();
+    let mut diagnostics_count = 0;
+    let mut error_count = 0;
+    let mut dependencies: std::collections::BTreeSet<_> = site_dependencies.into_iter().collect();
 
     for (i, result) in parse_results.into_iter().enumerate() {
         match result {
             Ok(doc) => {
+                for diagnostic in &doc.diagnostics {
+                    eprintln!(
+                        "{} {}",
+                        if diagnostic.is_error() {
+                            "❌"
+                        } else {
+                            "⚠️ "
+                        },
+                        diagnostic.located(&document_infos[i].path)
+                    );
+                    if let Some(excerpt) = diagnostic.excerpt() {
+                        eprint!("{}", excerpt);
+                    }
+                }
+                let errors = doc.diagnostics.iter().filter(|d| d.is_error()).count();
+                error_count += errors;
+                diagnostics_count += doc.diagnostics.len() - errors;
+                dependencies.extend(doc.dependencies.iter().cloned());
+
                 // Check for potential issues
                 if doc.metadata.title.is_none() && config.verbose {
                     parse_warnings.push(format!(
@@ -165,11 +272,8 @@ pub fn generate_site(config: &Config) -> Result<()> {
                 documents.push(doc);
             }
             Err(e) => {
-                eprintln!(
-                    "⚠️  Failed to parse '{}': {}",
-                    document_infos[i].path.display(),
-                    e
-                );
+                // Read and frontmatter errors already name the file
+                eprintln!("⚠️  {}", e);
                 parse_errors += 1;
             }
         }
@@ -179,6 +283,13 @@ pub fn generate_site(config: &Config) -> Result<()> {
         println!("⚠️  {} document(s) failed to parse", parse_errors);
     }
 
+    if error_count > 0 {
+        println!("❌ {} document error(s) reported", error_count);
+    }
+    if diagnostics_count > 0 {
+        println!("⚠️  {} document warning(s) reported", diagnostics_count);
+    }
+
     if config.verbose && !parse_warnings.is_empty() {
         println!("\n📋 Parse warnings:");
         for warning in parse_warnings {
@@ -188,13 +299,40 @@ pub fn generate_site(config: &Config) -> Result<()> {
 
     println!("✅ Successfully parsed {} documents", documents.len());
 
+    // Group translations and collect the site languages, default first
+    parser::link_translations(&mut documents);
+    let languages = parser::site_languages(&documents, parser_config.language());
+    if languages.len() > 1 {
+        println!("🌐 Languages: {}", languages.join(", "));
+    }
+
     // Build document lookup for wiki link resolution
     println!("🔗 Processing wiki links...");
     let document_lookup: Vec<(PathBuf, String)> = documents
         .iter()
         .map(|doc| (doc.output_path.clone(), doc.title().to_string()))
         .collect();
-    let lookup_map = parser::build_document_lookup(&document_lookup);
+    let lookup_map =
+        parser::build_document_lookup(&document_lookup, parser_config.unicode_matching);
+    let project_folders = parser::build_project_folder_lookup(
+        document_lookup.iter().map(|(path, _)| path.as_path()),
+        parser_config.unicode_matching,
+    );
+    let titles: std::collections::HashMap<PathBuf, String> = document_lookup.into_iter().collect();
+
+    // Snapshot the document set for `query` blocks
+    let query_index: Vec<parser::QueryRecord> = documents
+        .iter()
+        .map(parser::QueryRecord::from_document)
+        .collect();
+    parser_config.query_index = Some(Arc::new(query_index));
+
+    // Collect glossary terms so their first occurrences can be linked
+    let glossary = parser::Glossary::from_documents(&documents, parser_config.unicode_matching);
+    if !glossary.is_empty() {
+        println!("📖 Found {} glossary terms", glossary.terms().len());
+        parser_config.glossary = Some(Arc::new(glossary));
+    }
 
     // Process wiki links in parallel (second pass)
     if config.verbose {
@@ -210,27 +348,60 @@ pub fn generate_site(config: &Config) -> Result<()> {
     documents = documents
         .into_par_iter()
         .map(|mut doc| {
+            // Documents with their own bibliography render with it instead
+            let document_config;
+            let doc_parser_config = match &doc.bibliography {
+                Some(bibliography) => {
+                    document_config = config::ParserConfig {
+                        bibliography: Some(bibliography.clone()),
+                        ..parser_config.clone()
+                    };
+                    &document_config
+                }
+                None => &parser_config,
+            };
             let (html_with_links, resolved_links) = parser::markdown_to_html_with_wiki_links(
                 &doc.raw_content,
                 &doc.output_path,
                 &lookup_map,
+                doc_parser_config,
             )
             .expect("Wiki link processing failed");
 
-            doc.html_content = html_with_links;
-            doc.wiki_links = resolved_links.clone();
+            // Record the glossary terms each document links
+            if let Some(glossary) = &parser_config.glossary {
+                for term in
+                    parser::glossary_terms_used(&doc.raw_content, glossary, &doc.output_path)
+                {
+                    doc.glossary_terms.push(term.term.clone());
+                }
+            }
+
+            doc.html_content = match (&doc.frontmatter_error, parser_config.dev_build) {
+                (Some(diagnostic), true) => {
+                    parser::render_frontmatter_banner(diagnostic) + &html_with_links
+                }
+                _ => html_with_links,
+            };
+            doc.wiki_links = resolved_links;
+            for link in &mut doc.wiki_links {
+                link.wiki_link.location = doc.line_map.locate(link.wiki_link.location);
+            }
+            parser::resolve_relationships(
+                &mut doc.relationships,
+                &lookup_map,
+                &project_folders,
+                &titles,
+                parser_config.unicode_matching,
+            );
 
             // Count and collect broken links
-            let broken_links = parser::get_broken_links(&doc.wiki_links);
+            let broken_links = parser::broken_link_diagnostics(&doc.wiki_links);
             if !broken_links.is_empty() {
                 broken_links_clone.fetch_add(broken_links.len(), Ordering::SeqCst);
                 if config.verbose {
-                    for link in &broken_links {
-                        println!(
-                            "   ⚠️  Broken link in '{}': [[{}]]",
-                            doc.relative_path.display(),
-                            link.target
-                        );
+                    for diagnostic in &broken_links {
+                        println!("   ⚠️  {}", diagnostic.located(&doc.source_path));
                     }
                 }
             }
@@ -252,6 +423,21 @@ pub fn generate_site(config: &Config) -> Result<()> {
 
     let broken_links_total = broken_links_total.load(Ordering::SeqCst);
 
+    // Report relationship targets that do not exist
+    let mut unresolved_relationships = 0;
+    for doc in &documents {
+        for diagnostic in parser::unresolved_relationship_diagnostics(&doc.relationships) {
+            eprintln!("⚠️  {}", diagnostic.located(&doc.source_path));
+            unresolved_relationships += 1;
+        }
+    }
+    if unresolved_relationships > 0 {
+        println!(
+            "⚠️  Total unresolved relationships: {}",
+            unresolved_relationships
+        );
+    }
+
     if broken_links_total > 0 {
         println!("⚠️  Total broken wiki links: {}", broken_links_total);
         if !config.verbose {
@@ -285,7 +471,21 @@ pub fn generate_site(config: &Config) -> Result<()> {
 
     // Generate search index
     println!("🔍 Generating search index...");
-    generator::generate_search_index(&documents, output_path)?;
+    generator::generate_search_index(&documents, output_path, parser_config.unicode_matching)?;
+    if languages.len() > 1 {
+        generator::generate_language_search_indexes(
+            &documents,
+            output_path,
+            parser_config.unicode_matching,
+            &languages,
+        )?;
+    }
+
+    // Generate the JSON content API
+    let api_count =
+        generator::ApiGenerator::new(output_path.to_path_buf(), config.base_url.clone())
+            .write_api(&documents)?;
+    println!("🧾 Generated content API for {} documents", api_count);
 
     // Additional validation warnings
     if config.verbose {
@@ -322,14 +522,57 @@ pub fn generate_site(config: &Config) -> Result<()> {
         }
     }
 
+    // Generate Atom and RSS feeds, which need absolute URLs
+    let feed_links = match config.absolute_base_url() {
+        Some(site_url) => {
+            let feeds = generator::FeedGenerator::new(
+                site_url,
+                config.site_title.clone(),
+                parser_config.language().to_string(),
+                config.feeds.clone(),
+            );
+            let links = feeds.write_feeds(output_path, &documents)?;
+            if !links.is_empty() {
+                println!("📡 Generated {} feed file(s)", links.len());
+            }
+            links
+        }
+        None => {
+            if config.verbose {
+                println!("📡 Skipping feeds: set PARA_SSG_SITE_URL for absolute feed URLs");
+            }
+            Vec::new()
+        }
+    };
+
     // Generate HTML pages
     println!("🔨 Generating HTML pages...");
-    let generator = Arc::new(generator::HtmlGenerator::new(
-        output_path.to_path_buf(),
-        config.site_title.clone(),
-        config.base_url.clone(),
-        config.blog.clone(),
-    ));
+    let mut messages = theme::i18n::Messages::new(parser_config.language());
+    let messages_dir = parser_config
+        .theme_dir
+        .clone()
+        .unwrap_or_else(|| input_path.join(parser::DEFAULT_THEME_DIR))
+        .join(theme::i18n::MESSAGES_DIR);
+    if messages_dir.is_dir() {
+        messages.load_dir(&messages_dir)?;
+    }
+    // Overdue tasks are judged against today in the site timezone
+    let build_date = match parser_config.default_timezone {
+        Some(tz) => chrono::Utc::now().with_timezone(&tz).date_naive(),
+        None => chrono::Local::now().date_naive(),
+    };
+    let generator = Arc::new(
+        generator::HtmlGenerator::new(
+            output_path.to_path_buf(),
+            config.site_title.clone(),
+            config.base_url.clone(),
+            config.blog.clone(),
+        )
+        .with_languages(messages, languages.clone())
+        .with_feeds(feed_links)
+        .with_pagination(config.pagination.clone())
+        .with_build_date(build_date),
+    );
 
     // Save document count before moving documents
     let total_document_count = documents.len();
@@ -344,10 +587,30 @@ pub fn generate_site(config: &Config) -> Result<()> {
             .push(doc);
     }
 
+    // Aggregate content statistics per category
+    let category_stats: std::collections::BTreeMap<String, parser::CategoryStats> = categories
+        .iter()
+        .map(|(category, docs)| {
+            let mut stats = parser::CategoryStats::default();
+            docs.iter().for_each(|doc| stats.add(doc.stats));
+            (category.clone(), stats)
+        })
+        .collect();
+
     // Generate individual document pages in parallel
     let processed = Arc::new(AtomicUsize::new(0));
     let processed_clone = processed.clone();
     let all_docs: Vec<_> = categories.values().flatten().collect();
+
+    // Collect published pages for sitemap.xml as they are generated
+    let site_url = config.absolute_base_url();
+    let mut sitemap = generator::Sitemap::new(
+        site_url.clone().unwrap_or_default(),
+        &config.sitemap.disallow,
+    );
+    for doc in &all_docs {
+        sitemap.add_document(doc);
+    }
     let total_pages = all_docs.len();
 
     all_docs.par_iter().try_for_each(|doc| -> Result<()> {
@@ -370,9 +633,19 @@ pub fn generate_site(config: &Config) -> Result<()> {
     // Generate category index pages
     for category in &["projects", "areas", "resources", "archives"] {
         if let Some(docs) = categories.get(*category) {
-            let html = generator.generate_category_page(category, docs)?;
-            let index_path = Path::new(category).join("index.html");
-            generator.write_page(&index_path, &html)?;
+            // A category with its own directory gets its index from the
+            // subdirectory pages below, so its pages are not written twice
+            let has_directory = directory_infos
+                .iter()
+                .any(|dir| dir.relative_path == Path::new(category));
+            if !has_directory {
+                let pages = generator.generate_category_pages(category, docs)?;
+                for (i, html) in pages.iter().enumerate() {
+                    let page_path = generator::listing_page_path(Path::new(category), i + 1);
+                    generator.write_page(&page_path, html)?;
+                }
+                sitemap.add_listing_pages(Path::new(category), pages.len(), docs);
+            }
 
             // Generate subdirectory index pages
             // First, collect all directories under this category
@@ -416,13 +689,16 @@ pub fn generate_site(config: &Config) -> Result<()> {
                     })
                     .collect();
 
-                let html = generator.generate_subdirectory_page_with_dirs(
+                let pages = generator.generate_subdirectory_pages_with_dirs(
                     subdir_path,
                     &subdir_docs,
                     &child_dirs,
                 )?;
-                let index_path = subdir_path.join("index.html");
-                generator.write_page(&index_path, &html)?;
+                for (i, html) in pages.iter().enumerate() {
+                    let page_path = generator::listing_page_path(subdir_path, i + 1);
+                    generator.write_page(&page_path, html)?;
+                }
+                sitemap.add_listing_pages(subdir_path, pages.len(), &subdir_docs);
             }
         }
     }
@@ -432,18 +708,137 @@ pub fn generate_site(config: &Config) -> Result<()> {
     let blog_posts = utils::get_blog_posts(&all_docs_vec);
     if !blog_posts.is_empty() {
         let blog_posts_owned: Vec<Document> = blog_posts.iter().map(|&doc| doc.clone()).collect();
-        let blog_html = generator.generate_blog_listing_page(&blog_posts_owned)?;
-        let blog_path = Path::new("blog").join("index.html");
-        generator.write_page(&blog_path, &blog_html)?;
+        let pages = generator.generate_blog_listing_pages_in(&blog_posts_owned, &languages[0])?;
+        for (i, blog_html) in pages.iter().enumerate() {
+            let blog_path = generator::listing_page_path(Path::new("blog"), i + 1);
+            generator.write_page(&blog_path, blog_html)?;
+        }
+        sitemap.add_listing_pages(Path::new("blog"), pages.len(), blog_posts.iter().copied());
+    }
+
+    // Generate the open tasks page
+    let tasks_html = generator.generate_tasks_page(&all_docs_vec)?;
+    generator.write_page(&Path::new("tasks").join("index.html"), &tasks_html)?;
+    let open_tasks = all_docs_vec
+        .iter()
+        .filter(|doc| !doc.is_draft())
+        .flat_map(|doc| &doc.tasks)
+        .filter(|task| !task.checked)
+        .count();
+
+    // Generate the references page when any document cites a work
+    if all_docs_vec.iter().any(|doc| !doc.citations.is_empty()) {
+        let references_html = generator.generate_references_page(&all_docs_vec)?;
+        generator.write_page(
+            &Path::new("references").join("index.html"),
+            &references_html,
+        )?;
+    }
+
+    // Generate the glossary page
+    if let Some(glossary) = &parser_config.glossary {
+        let glossary_html = generator.generate_glossary_page(glossary, &all_docs_vec)?;
+        generator.write_page(Path::new(parser::GLOSSARY_PAGE_PATH), &glossary_html)?;
+    }
+
+    // Generate the tag index and a page for every tag, nested ones included
+    let tags = generator.tag_tree(&all_docs_vec);
+    if !tags.is_empty() {
+        let tags_html = generator.generate_tag_index_page(&tags)?;
+        generator.write_page(&Path::new(parser::TAGS_DIR).join("index.html"), &tags_html)?;
+        let tagged: Vec<_> = tags.iter().flat_map(|tag| tag.all_documents()).collect();
+        sitemap.add_listing(Path::new(parser::TAGS_DIR), tagged);
+
+        let mut tag_count = 0;
+        for tag in tags.iter().flat_map(|tag| tag.walk()) {
+            let tag_html = generator.generate_tag_page(tag)?;
+            generator.write_page(&tag.dir().join("index.html"), &tag_html)?;
+            sitemap.add_listing(&tag.dir(), tag.all_documents());
+            tag_count += 1;
+        }
+        println!("🏷️  Generated {} tag pages", tag_count);
     }
 
     // Generate home page with all documents for the file list
     let all_documents: Vec<_> = categories.values().flatten().cloned().collect();
     let home_html = generator.generate_home_page(&all_documents)?;
     generator.write_page(Path::new("index.html"), &home_html)?;
+    sitemap.add_listing(Path::new(""), &all_documents);
+
+    // Generate home and blog listings for the other languages under `<lang>/`
+    for lang in languages.iter().skip(1) {
+        let home_html = generator.generate_home_page_in(&all_documents, lang)?;
+        generator.write_page(&Path::new(lang).join("index.html"), &home_html)?;
+        let lang_documents = all_documents.iter().filter(|doc| doc.lang == *lang);
+        sitemap.add_listing(Path::new(lang), lang_documents);
+
+        if blog_posts.iter().any(|doc| doc.lang == *lang) {
+            let blog_posts_owned: Vec<Document> =
+                blog_posts.iter().map(|&doc| doc.clone()).collect();
+            let pages = generator.generate_blog_listing_pages_in(&blog_posts_owned, lang)?;
+            for (i, blog_html) in pages.iter().enumerate() {
+                let blog_dir = Path::new(lang).join("blog");
+                generator.write_page(&generator::listing_page_path(&blog_dir, i + 1), blog_html)?;
+            }
+            let lang_posts = blog_posts.iter().copied().filter(|doc| doc.lang == *lang);
+            sitemap.add_listing_pages(&Path::new(lang).join("blog"), pages.len(), lang_posts);
+        }
+    }
+
+    // Generate blog archives by year and month, for each language
+    if !blog_posts.is_empty() {
+        let blog_posts_owned: Vec<Document> = blog_posts.iter().map(|&doc| doc.clone()).collect();
+        for (i, lang) in languages.iter().enumerate() {
+            let archive = generator.blog_archive(&blog_posts_owned, lang);
+            if archive.is_empty() {
+                continue;
+            }
+            // The site language's archives are at the root, others under `<lang>/`
+            let root = if i == 0 {
+                PathBuf::new()
+            } else {
+                PathBuf::from(lang)
+            };
+
+            let overview_html = generator.generate_blog_archive_page_in(&archive, lang)?;
+            let overview_dir = root.join(utils::BLOG_DIR).join(utils::BLOG_ARCHIVE_DIR);
+            generator.write_page(&overview_dir.join("index.html"), &overview_html)?;
+            sitemap.add_listing(&overview_dir, archive.iter().flat_map(|year| year.posts()));
+
+            for year in &archive {
+                let year_html = generator.generate_blog_year_page_in(year, lang)?;
+                generator.write_page(&root.join(year.dir()).join("index.html"), &year_html)?;
+                sitemap.add_listing(&root.join(year.dir()), year.posts());
+                for month in &year.months {
+                    let month_html = generator.generate_blog_month_page_in(month, lang)?;
+                    generator
+                        .write_page(&root.join(month.dir()).join("index.html"), &month_html)?;
+                    sitemap.add_listing(&root.join(month.dir()), month.posts.iter().copied());
+                }
+            }
+        }
+    }
 
     println!("✅ Generated {} HTML pages", generated_count);
 
+    // Generate sitemap.xml, which needs absolute URLs, and robots.txt
+    let sitemap_url = match &site_url {
+        Some(site_url) => {
+            sitemap.write(output_path)?;
+            println!("🗺️  Generated sitemap with {} URLs", sitemap.len());
+            Some(format!("{}{}", site_url, generator::SITEMAP_FILE_NAME))
+        }
+        None => None,
+    };
+    let base_path = match &site_url {
+        Some(site_url) => generator::url_path_of(site_url),
+        None => config.base_url.clone(),
+    };
+    std::fs::write(
+        output_path.join("robots.txt"),
+        generator::robots_txt(&base_path, &config.sitemap.disallow, sitemap_url.as_deref()),
+    )?;
+
     let elapsed = start_time.elapsed();
 
     // Build summary
@@ -468,6 +863,32 @@ pub fn generate_site(config: &Config) -> Result<()> {
     if parse_errors > 0 {
         println!("   - ⚠️  Parse errors: {}", parse_errors);
     }
+    if error_count > 0 {
+        println!("   - ❌ Document errors: {}", error_count);
+    }
+    if diagnostics_count > 0 {
+        println!("   - ⚠️  Document warnings: {}", diagnostics_count);
+    }
+    if !category_stats.is_empty() {
+        println!("   - Content by category:");
+        for (category, stats) in &category_stats {
+            println!(
+                "      - {}: {} documents, {} words, {} min read, {} code blocks, {} links",
+                category,
+                stats.documents,
+                stats.totals.word_count,
+                stats.totals.reading_time,
+                stats.totals.code_blocks,
+                stats.totals.links
+            );
+        }
+    }
+    if open_tasks > 0 {
+        println!("   - Open tasks: {}", open_tasks);
+    }
+    if !dependencies.is_empty() {
+        println!("   - Dependency files: {}", dependencies.len());
+    }
     println!("   - Build time: {:.2}s", elapsed.as_secs_f32());
     println!("   - Output directory: {}", config.output_dir);
 
@@ -478,7 +899,11 @@ pub fn generate_site(config: &Config) -> Result<()> {
 
     println!("\n🎉 Site generation complete!");
 
-    Ok(())
+    Ok(BuildReport {
+        dependencies: dependencies.into_iter().collect(),
+        errors: error_count,
+        category_stats,
+    })
 }
 
 #[cfg(test)]
@@ -2,9 +2,11 @@
 //! ABOUTME: Handles command-line arguments and orchestrates the build process
 
 use para_ssg::{generate_site, Config, ParaSsgError};
+use std::collections::HashSet;
 use std::env;
+use std::path::PathBuf;
 use std::process;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 
 fn main() {
@@ -80,14 +82,20 @@ fn run() -> Result<(), ParaSsgError> {
         "Building site from '{}' to '{}'",
         config.input_dir, config.output_dir
     );
-    generate_site(&config)?;
+    let report = generate_site(&config)?;
+    if report.errors > 0 && !watch {
+        return Err(ParaSsgError::GenerationError(format!(
+            "{} document error(s) reported",
+            report.errors
+        )));
+    }
     println!("✅ Site generation completed successfully!");
 
     // If watch mode is enabled, start file watcher
     if watch {
         println!("\n👁️  Watch mode enabled. Monitoring for changes...");
         println!("Press Ctrl+C to stop.\n");
-        watch_and_rebuild(&config)?;
+        watch_and_rebuild(&config, report.dependencies)?;
     }
 
     Ok(())
@@ -117,12 +125,16 @@ fn print_usage(program_name: &str) {
     eprintln!("    {} --watch ../context ./dist", program_name);
 }
 
-fn watch_and_rebuild(config: &Config) -> Result<(), ParaSsgError> {
+fn watch_and_rebuild(config: &Config, dependencies: Vec<PathBuf>) -> Result<(), ParaSsgError> {
     use notify::{Config as NotifyConfig, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
     use std::path::Path;
 
     let (tx, rx) = mpsc::channel();
 
+    // Included files may live outside the input directory, so they are tracked separately
+    let watched_dependencies: Arc<Mutex<HashSet<PathBuf>>> = Arc::new(Mutex::new(HashSet::new()));
+    let dependencies_for_events = watched_dependencies.clone();
+
     // Create a watcher
     let mut watcher = RecommendedWatcher::new(
         move |res: Result<notify::Event, notify::Error>| {
@@ -130,9 +142,12 @@ fn watch_and_rebuild(config: &Config) -> Result<(), ParaSsgError> {
                 // Only react to file modifications, creations, and deletions
                 match event.kind {
                     EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                        // Filter for markdown files
+                        // Filter for markdown files and included dependencies
+                        let dependencies = dependencies_for_events.lock().unwrap();
                         for path in &event.paths {
-                            if path.extension().and_then(|s| s.to_str()) == Some("md") {
+                            if path.extension().and_then(|s| s.to_str()) == Some("md")
+                                || dependencies.contains(path)
+                            {
                                 let _ = tx.send(());
                                 break;
                             }
@@ -150,6 +165,7 @@ fn watch_and_rebuild(config: &Config) -> Result<(), ParaSsgError> {
     watcher
         .watch(Path::new(&config.input_dir), RecursiveMode::Recursive)
         .map_err(|e| ParaSsgError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    watch_dependencies(&mut watcher, &watched_dependencies, dependencies);
 
     // Keep track of last rebuild time to debounce rapid changes
     let mut last_rebuild = std::time::Instant::now();
@@ -168,7 +184,14 @@ fn watch_and_rebuild(config: &Config) -> Result<(), ParaSsgError> {
 
                 println!("\n🔄 Change detected, rebuilding...");
                 match generate_site(config) {
-                    Ok(_) => println!("✅ Rebuild completed successfully!"),
+                    Ok(report) => {
+                        watch_dependencies(
+                            &mut watcher,
+                            &watched_dependencies,
+                            report.dependencies,
+                        );
+                        println!("✅ Rebuild completed successfully!");
+                    }
                     Err(e) => eprintln!("❌ Rebuild failed: {}", e),
                 }
                 println!("\n👁️  Watching for changes...");
@@ -185,3 +208,25 @@ fn watch_and_rebuild(config: &Config) -> Result<(), ParaSsgError> {
         }
     }
 }
+
+/// Start watching newly discovered dependency files
+fn watch_dependencies(
+    watcher: &mut notify::RecommendedWatcher,
+    watched: &Mutex<HashSet<PathBuf>>,
+    dependencies: Vec<PathBuf>,
+) {
+    use notify::{RecursiveMode, Watcher};
+
+    let mut watched = watched.lock().unwrap();
+    for path in dependencies {
+        if watched.contains(&path) {
+            continue;
+        }
+        match watcher.watch(&path, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                watched.insert(path);
+            }
+            Err(e) => eprintln!("⚠️  Could not watch '{}': {}", path.display(), e),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! ABOUTME: Obsidian callout rendering for blockquotes
+//! ABOUTME: Turns > [!type] Title blockquotes into styled, optionally foldable asides
+
+use crate::config::CustomCallout;
+use crate::utils::html_escape;
+use lazy_static::lazy_static;
+use pulldown_cmark::{html, CowStr, Event, Tag, TagEnd};
+use regex::Regex;
+
+lazy_static! {
+    /// Regex pattern for the callout marker: [!type] with optional fold state
+    static ref CALLOUT_RE: Regex = Regex::new(r"^\[!([A-Za-z][\w-]*)\]([+-]?)[ \t]*").unwrap();
+}
+
+/// A resolved callout type with its icon and display name
+struct CalloutStyle {
+    /// CSS class suffix (`callout-{kind}`)
+    kind: String,
+    /// Icon shown before the title
+    icon: String,
+    /// Inline color override for custom types
+    color: Option<String>,
+}
+
+/// Built-in callout types: (name, aliases, icon)
+const BUILTIN_CALLOUTS: &[(&str, &[&str], &str)] = &[
+    ("note", &[], "✎"),
+    ("abstract", &["summary", "tldr"], "☰"),
+    ("info", &[], "ℹ"),
+    ("todo", &[], "☑"),
+    ("tip", &["hint", "important"], "💡"),
+    ("success", &["check", "done"], "✔"),
+    ("question", &["help", "faq"], "?"),
+    ("warning", &["caution", "attention"], "⚠"),
+    ("failure", &["fail", "missing"], "✘"),
+    ("danger", &["error"], "⚡"),
+    ("bug", &[], "🐞"),
+    ("example", &[], "❖"),
+    ("quote", &["cite"], "❝"),
+];
+
+/// Resolve a callout type name, checking custom types before built-ins
+///
+/// Unknown types fall back to the `note` style, as in Obsidian.
+fn resolve_style(name: &str, custom: &[CustomCallout]) -> CalloutStyle {
+    let name = name.to_lowercase();
+
+    if let Some(callout) = custom.iter().find(|c| c.name.eq_ignore_ascii_case(&name)) {
+        return CalloutStyle {
+            kind: name,
+            icon: callout.icon.clone(),
+            color: callout.color.clone(),
+        };
+    }
+
+    let (kind, _, icon) = BUILTIN_CALLOUTS
+        .iter()
+        .find(|(kind, aliases, _)| *kind == name || aliases.contains(&name.as_str()))
+        .unwrap_or(&BUILTIN_CALLOUTS[0]);
+
+    CalloutStyle {
+        kind: kind.to_string(),
+        icon: icon.to_string(),
+        color: None,
+    }
+}
+
+/// Rewrite blockquotes that start with a `[!type]` marker into callouts
+///
+/// `> [!warning] Title` renders as an `<aside>` with an icon and title.
+/// A trailing `-` or `+` after the marker (`> [!note]-`) makes the callout
+/// foldable via `<details>`, collapsed or expanded by default respectively.
+pub fn render_callouts<'a>(events: Vec<Event<'a>>, custom: &[CustomCallout]) -> Vec<Event<'a>> {
+    let mut output = Vec::with_capacity(events.len());
+    // For each open blockquote, the closing HTML if it was turned into a callout
+    let mut open_quotes: Vec<Option<&'static str>> = Vec::new();
+    let mut i = 0;
+
+    while i < events.len() {
+        match &events[i] {
+            Event::Start(Tag::BlockQuote(_)) => {
+                if let Some((html, closing, next)) = parse_callout_header(&events, i, custom) {
+                    output.push(Event::Html(CowStr::from(html)));
+                    open_quotes.push(Some(closing));
+                    i = next;
+                    continue;
+                }
+                open_quotes.push(None);
+                output.push(events[i].clone());
+            }
+            Event::End(TagEnd::BlockQuote(_)) => match open_quotes.pop().flatten() {
+                Some(closing) => output.push(Event::Html(CowStr::from(closing))),
+                None => output.push(events[i].clone()),
+            },
+            event => output.push(event.clone()),
+        }
+        i += 1;
+    }
+
+    output
+}
+
+/// Try to parse a callout header at the blockquote starting at `start`
+///
+/// Returns the opening HTML, the closing HTML and the index of the next event
+/// to process.
+fn parse_callout_header(
+    events: &[Event<'_>],
+    start: usize,
+    custom: &[CustomCallout],
+) -> Option<(String, &'static str, usize)> {
+    if !matches!(events.get(start + 1), Some(Event::Start(Tag::Paragraph))) {
+        return None;
+    }
+
+    // The marker may be split across several text events ("[", "!type", "]")
+    let mut lead = String::new();
+    let mut i = start + 2;
+    while let Some(Event::Text(text)) = events.get(i) {
+        lead.push_str(text);
+        i += 1;
+    }
+    let lead_line = lead.split('\n').next().unwrap_or("");
+
+    let captures = CALLOUT_RE.captures(lead_line)?;
+    let type_name = captures[1].to_string();
+    let fold = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+    let rest_of_lead = &lead[captures[0].len()..];
+
+    // The title runs to the end of the first line of the paragraph
+    let mut title_events = vec![Event::Text(CowStr::from(rest_of_lead.to_string()))];
+    let body_continues = loop {
+        match events.get(i) {
+            Some(Event::SoftBreak | Event::HardBreak) => {
+                i += 1;
+                break true;
+            }
+            Some(Event::End(TagEnd::Paragraph)) => {
+                i += 1;
+                break false;
+            }
+            Some(event) => {
+                title_events.push(event.clone());
+                i += 1;
+            }
+            None => break false,
+        }
+    };
+
+    let mut title = String::new();
+    html::push_html(&mut title, title_events.into_iter());
+    let title = if title.trim().is_empty() {
+        html_escape(&capitalize(&type_name))
+    } else {
+        title.trim().to_string()
+    };
+
+    let style = resolve_style(&type_name, custom);
+    let style_attr = style
+        .color
+        .as_ref()
+        .map(|color| format!(r#" style="--callout-color: {}""#, html_escape(color)))
+        .unwrap_or_default();
+    let header = format!(
+        r#"<span class="callout-icon" aria-hidden="true">{}</span><span class="callout-title-text">{}</span>"#,
+        html_escape(&style.icon),
+        title
+    );
+
+    let (mut html, closing) = if fold.is_empty() {
+        (
+            format!(
+                r#"<aside class="callout callout-{}" data-callout="{}"{}><div class="callout-title">{}</div><div class="callout-content">"#,
+                html_escape(&style.kind),
+                html_escape(&type_name.to_lowercase()),
+                style_attr,
+                header
+            ),
+            "</div></aside>\n",
+        )
+    } else {
+        (
+            format!(
+                r#"<aside class="callout callout-{} callout-foldable" data-callout="{}"{}><details{}><summary class="callout-title">{}</summary><div class="callout-content">"#,
+                html_escape(&style.kind),
+                html_escape(&type_name.to_lowercase()),
+                style_attr,
+                if fold == "+" { " open" } else { "" },
+                header
+            ),
+            "</div></details></aside>\n",
+        )
+    };
+
+    // Remaining lines of the first paragraph become the start of the body
+    if body_continues {
+        html.push_str("<p>");
+    }
+
+    Some((html, closing, i))
+}
+
+/// Capitalize the first letter of a callout type for its default title
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{Options, Parser};
+
+    fn render(markdown: &str, custom: &[CustomCallout]) -> String {
+        let events: Vec<Event> = Parser::new_ext(markdown, Options::empty()).collect();
+        let mut html = String::new();
+        html::push_html(&mut html, render_callouts(events, custom).into_iter());
+        html
+    }
+
+    #[test]
+    fn test_callout_with_title() {
+        let html = render("> [!warning] Be *careful*\n> Body text\n", &[]);
+
+        assert!(html.contains(r#"<aside class="callout callout-warning" data-callout="warning">"#));
+        assert!(html.contains(r#"<span class="callout-title-text">Be <em>careful</em></span>"#));
+        assert!(html.contains(r#"<div class="callout-content"><p>Body text</p>"#));
+        assert!(html.contains("</div></aside>"));
+        assert!(!html.contains("blockquote"));
+        assert!(!html.contains("[!warning]"));
+    }
+
+    #[test]
+    fn test_callout_default_title_and_alias() {
+        let html = render("> [!tldr]\n> Short version\n", &[]);
+
+        assert!(html.contains(r#"class="callout callout-abstract" data-callout="tldr""#));
+        assert!(html.contains(r#"<span class="callout-title-text">Tldr</span>"#));
+        assert!(html.contains("<p>Short version</p>"));
+    }
+
+    #[test]
+    fn test_foldable_callouts() {
+        let collapsed = render("> [!note]- Details\n> Hidden\n", &[]);
+        assert!(collapsed.contains("callout-foldable"));
+        assert!(collapsed.contains("<details><summary class=\"callout-title\">"));
+        assert!(collapsed.contains("</div></details></aside>"));
+
+        let expanded = render("> [!note]+ Details\n> Shown\n", &[]);
+        assert!(expanded.contains("<details open>"));
+    }
+
+    #[test]
+    fn test_plain_and_nested_blockquotes() {
+        let html = render("> plain quote\n", &[]);
+        assert!(html.contains("<blockquote>"));
+        assert!(!html.contains("callout"));
+
+        let html = render("> [!info] Outer\n> > [!tip] Inner\n> > text\n", &[]);
+        assert_eq!(html.matches("<aside").count(), 2);
+        assert_eq!(html.matches("</aside>").count(), 2);
+        assert!(html.contains("callout-tip"));
+    }
+
+    #[test]
+    fn test_unknown_and_custom_types() {
+        let html = render("> [!mystery] Who knows\n", &[]);
+        assert!(html.contains(r#"class="callout callout-note" data-callout="mystery""#));
+
+        let custom = vec![CustomCallout {
+            name: "decision".to_string(),
+            icon: "⚖".to_string(),
+            color: Some("#8B5CF6".to_string()),
+        }];
+        let html = render("> [!decision] Use Rust\n", &custom);
+        assert!(html.contains(r#"class="callout callout-decision""#));
+        assert!(html.contains(r#"style="--callout-color: #8B5CF6""#));
+        assert!(html.contains(">⚖</span>"));
+    }
+}
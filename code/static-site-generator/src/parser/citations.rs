@@ -0,0 +1,1005 @@
+//! ABOUTME: Pandoc-style [@key] citations resolved against BibTeX or CSL-JSON files
+//! ABOUTME: Renders in-text citations and per-document bibliographies
+
+use super::diagnostics::Diagnostic;
+use super::spans::SourceLocation;
+use crate::config::CitationStyle;
+use crate::utils::html_escape;
+use crate::{ParaSsgError, Result};
+use lazy_static::lazy_static;
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+/// Bibliography files looked for in the input directory, in order
+pub const BIBLIOGRAPHY_FILE_NAMES: [&str; 2] = ["references.bib", "references.json"];
+
+lazy_static! {
+    /// Bracketed text containing an `@`, with the character after it so
+    /// that links (`[@x](url)`, `[@x][ref]`) can be skipped
+    static ref CITATION_RE: Regex = Regex::new(r"\[([^\[\]]*@[^\[\]]*)\]([(\[])?").unwrap();
+
+    /// One citation item: optional prefix, `@key` and optional locator
+    static ref CITE_ITEM_RE: Regex = Regex::new(
+        r"^\s*(?:(.*?)\s+)?@([\p{L}\p{N}_][\p{L}\p{N}_:.#$%&+?<>~/-]*)\s*(?:,\s*(.*?))?\s*$"
+    )
+    .unwrap();
+}
+
+/// A person or organisation credited on a work
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Name {
+    /// Family name, or the full name of an organisation
+    pub family: String,
+
+    /// Given names, if any
+    pub given: Option<String>,
+}
+
+impl Name {
+    /// Parse a BibTeX name (`Last, First`, `First Last` or `{Organisation}`)
+    fn from_bibtex(raw: &str) -> Self {
+        let raw = raw.trim();
+        if raw.starts_with('{') && matching_brace(raw) == Some(raw.len() - 1) {
+            return Self {
+                family: latex_to_text(raw),
+                given: None,
+            };
+        }
+        let name = latex_to_text(raw);
+        if let Some((family, given)) = name.split_once(',') {
+            return Self {
+                family: family.trim().to_string(),
+                given: Some(given.trim().to_string()).filter(|g| !g.is_empty()),
+            };
+        }
+        match name.rsplit_once(' ') {
+            Some((given, family)) => Self {
+                family: family.to_string(),
+                given: Some(given.to_string()),
+            },
+            None => Self {
+                family: name,
+                given: None,
+            },
+        }
+    }
+
+    /// `Given Family`, or just the family name
+    fn full(&self) -> String {
+        match &self.given {
+            Some(given) => format!("{} {}", given, self.family),
+            None => self.family.clone(),
+        }
+    }
+
+    /// `Family, Given`, or just the family name
+    fn inverted(&self) -> String {
+        match &self.given {
+            Some(given) => format!("{}, {}", self.family, given),
+            None => self.family.clone(),
+        }
+    }
+}
+
+/// A work that can be cited
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BibEntry {
+    /// Citation key (`smith2020`)
+    pub key: String,
+
+    /// Authors, or editors when a work has no authors
+    pub authors: Vec<Name>,
+
+    /// Publication year
+    pub year: Option<String>,
+
+    /// Title of the work
+    pub title: Option<String>,
+
+    /// Journal, proceedings or book the work appears in
+    pub container: Option<String>,
+
+    /// Publisher or institution
+    pub publisher: Option<String>,
+
+    /// Volume of the container
+    pub volume: Option<String>,
+
+    /// Page range
+    pub pages: Option<String>,
+
+    /// Digital Object Identifier
+    pub doi: Option<String>,
+
+    /// Web address
+    pub url: Option<String>,
+}
+
+impl BibEntry {
+    /// Author part of an author-date citation (`Smith`, `Smith and Doe`,
+    /// `Smith et al.`), falling back to the title or key
+    pub fn author_label(&self) -> String {
+        match self.authors.as_slice() {
+            [] => self.title.clone().unwrap_or_else(|| self.key.clone()),
+            [one] => one.family.clone(),
+            [first, second] => format!("{} and {}", first.family, second.family),
+            [first, ..] => format!("{} et al.", first.family),
+        }
+    }
+
+    /// Year for citations, or `n.d.` when undated
+    pub fn year_label(&self) -> &str {
+        self.year.as_deref().unwrap_or("n.d.")
+    }
+
+    /// Anchor of the entry in a bibliography
+    pub fn anchor(&self) -> String {
+        format!("ref-{}", self.key)
+    }
+
+    /// Authors for a reference list: first inverted, the rest in order
+    fn author_list(&self) -> String {
+        let names: Vec<String> = self
+            .authors
+            .iter()
+            .enumerate()
+            .map(|(i, name)| if i == 0 { name.inverted() } else { name.full() })
+            .collect();
+        match names.as_slice() {
+            [] => String::new(),
+            [one] => one.clone(),
+            [first, second] => format!("{} and {}", first, second),
+            [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+        }
+    }
+
+    /// Sort key for author-date reference lists
+    fn sort_key(&self) -> (String, String, String) {
+        (
+            self.author_list().to_lowercase(),
+            self.year_label().to_string(),
+            self.key.clone(),
+        )
+    }
+}
+
+/// Format a bibliography entry as HTML
+///
+/// Author-date references put the year after the authors; numeric
+/// references put it at the end.
+pub fn format_reference(entry: &BibEntry, style: CitationStyle) -> String {
+    let mut parts = Vec::new();
+    let authors = entry.author_list();
+    match style {
+        CitationStyle::AuthorDate if authors.is_empty() => {
+            parts.push(format!("({})", entry.year_label()))
+        }
+        CitationStyle::AuthorDate => parts.push(format!(
+            "{} ({})",
+            html_escape(&authors),
+            entry.year_label()
+        )),
+        CitationStyle::Numeric if !authors.is_empty() => parts.push(html_escape(&authors)),
+        CitationStyle::Numeric => {}
+    }
+    if let Some(title) = &entry.title {
+        parts.push(format!("<cite>{}</cite>", html_escape(title)));
+    }
+
+    let mut source: Vec<String> = [&entry.container, &entry.volume, &entry.pages]
+        .into_iter()
+        .flatten()
+        .map(|part| html_escape(part))
+        .collect();
+    if let Some(publisher) = &entry.publisher {
+        source.push(html_escape(publisher));
+    }
+    if style == CitationStyle::Numeric {
+        source.extend(entry.year.clone());
+    }
+    if !source.is_empty() {
+        parts.push(source.join(", "));
+    }
+
+    let mut html = parts
+        .iter()
+        .map(|part| part.trim_end_matches('.'))
+        .collect::<Vec<_>>()
+        .join(". ")
+        + ".";
+    let link = match (&entry.doi, &entry.url) {
+        (Some(doi), _) => Some(format!(
+            "https://doi.org/{}",
+            doi.trim_start_matches("https://doi.org/")
+        )),
+        (None, Some(url)) => Some(url.clone()),
+        (None, None) => None,
+    };
+    if let Some(link) = link {
+        let link = html_escape(&link);
+        html.push_str(&format!(r#" <a href="{0}">{0}</a>"#, link));
+    }
+    html
+}
+
+/// Works that can be cited, by key
+#[derive(Debug, Clone, Default)]
+pub struct Bibliography {
+    entries: BTreeMap<String, BibEntry>,
+}
+
+impl Bibliography {
+    /// Load a `.bib` (BibTeX/BibLaTeX) or `.json` (CSL-JSON) file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or is malformed
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            ParaSsgError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read bibliography '{}': {}", path.display(), e),
+            ))
+        })?;
+        let result = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_csl_json(&content),
+            _ => Self::from_bibtex(&content),
+        };
+        result.map_err(|e| match e {
+            ParaSsgError::ParseError(message) => ParaSsgError::ParseError(format!(
+                "Invalid bibliography '{}': {}",
+                path.display(),
+                message
+            )),
+            e => e,
+        })
+    }
+
+    /// Parse BibTeX entries; `@comment`, `@string` and `@preamble` are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns error for entries without a key or with unbalanced braces
+    pub fn from_bibtex(content: &str) -> Result<Self> {
+        let mut bibliography = Self::default();
+        let mut rest = content;
+
+        while let Some(at) = rest.find('@') {
+            let line = SourceLocation::from_offset(content, content.len() - rest.len() + at).line;
+            let entry = &rest[at + 1..];
+            let Some(open) = entry.find(['{', '(']) else {
+                break;
+            };
+            let kind = entry[..open].trim().to_lowercase();
+            if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric()) {
+                // A stray `@` outside an entry
+                rest = entry;
+                continue;
+            }
+            let body_start = open + 1;
+            let close = if entry.as_bytes()[open] == b'{' {
+                matching_brace(&entry[open..]).map(|end| open + end)
+            } else {
+                entry.find(')')
+            };
+            let Some(close) = close else {
+                return Err(ParaSsgError::ParseError(format!(
+                    "line {}: unbalanced braces in @{} entry",
+                    line, kind
+                )));
+            };
+            let body = &entry[body_start..close];
+            rest = &entry[close + 1..];
+
+            if matches!(kind.as_str(), "comment" | "string" | "preamble") {
+                continue;
+            }
+
+            let (key, fields) = body.split_once(',').unwrap_or((body, ""));
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(ParaSsgError::ParseError(format!(
+                    "line {}: @{} entry has no citation key",
+                    line, kind
+                )));
+            }
+            let fields = parse_bibtex_fields(fields);
+            let field = |name: &str| fields.get(name).map(|v| latex_to_text(v));
+
+            let authors = fields
+                .get("author")
+                .or_else(|| fields.get("editor"))
+                .map(|names| split_bibtex_names(names))
+                .unwrap_or_default();
+            let year = field("year")
+                .or_else(|| field("date").map(|date| date.chars().take(4).collect::<String>()));
+
+            bibliography.insert(BibEntry {
+                key: key.to_string(),
+                authors,
+                year,
+                title: field("title"),
+                container: field("journal")
+                    .or_else(|| field("journaltitle"))
+                    .or_else(|| field("booktitle")),
+                publisher: field("publisher")
+                    .or_else(|| field("institution"))
+                    .or_else(|| field("school")),
+                volume: field("volume"),
+                pages: field("pages"),
+                doi: field("doi"),
+                url: field("url"),
+            });
+        }
+
+        Ok(bibliography)
+    }
+
+    /// Parse a CSL-JSON array of items
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the text is not a JSON array or an item has no `id`
+    pub fn from_csl_json(content: &str) -> Result<Self> {
+        let items: Vec<serde_json::Value> = serde_json::from_str(content)
+            .map_err(|e| ParaSsgError::ParseError(format!("expected a CSL-JSON array: {}", e)))?;
+        let mut bibliography = Self::default();
+
+        for (index, item) in items.iter().enumerate() {
+            let key = match item.get("id") {
+                Some(serde_json::Value::String(id)) => id.clone(),
+                Some(serde_json::Value::Number(id)) => id.to_string(),
+                _ => {
+                    return Err(ParaSsgError::ParseError(format!(
+                        "item {} has no id",
+                        index + 1
+                    )))
+                }
+            };
+            let text = |name: &str| match item.get(name) {
+                Some(serde_json::Value::String(s)) => Some(s.clone()),
+                Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+                _ => None,
+            };
+            let names = |role: &str| -> Vec<Name> {
+                item.get(role)
+                    .and_then(|names| names.as_array())
+                    .map(|names| {
+                        names
+                            .iter()
+                            .filter_map(|name| {
+                                let field = |f: &str| name.get(f).and_then(|v| v.as_str());
+                                Some(Name {
+                                    family: field("family").or_else(|| field("literal"))?.into(),
+                                    given: field("given").map(String::from),
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let authors = match names("author") {
+                authors if authors.is_empty() => names("editor"),
+                authors => authors,
+            };
+            let year = item.get("issued").and_then(|issued| {
+                issued
+                    .pointer("/date-parts/0/0")
+                    .map(|year| match year {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .or_else(|| issued.get("literal")?.as_str().map(String::from))
+                    .or_else(|| Some(issued.get("raw")?.as_str()?.chars().take(4).collect()))
+            });
+
+            bibliography.insert(BibEntry {
+                key,
+                authors,
+                year,
+                title: text("title"),
+                container: text("container-title"),
+                publisher: text("publisher"),
+                volume: text("volume"),
+                pages: text("page"),
+                doi: text("DOI"),
+                url: text("URL"),
+            });
+        }
+
+        Ok(bibliography)
+    }
+
+    /// Add an entry, replacing any with the same key
+    pub fn insert(&mut self, entry: BibEntry) {
+        self.entries.insert(entry.key.clone(), entry);
+    }
+
+    /// Add all entries of `other`, which take precedence over existing ones
+    pub fn merge(&mut self, other: Bibliography) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Look up an entry by key
+    pub fn get(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.get(key)
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the bibliography has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// One cited work in a citation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiteItem {
+    /// Citation key
+    pub key: String,
+
+    /// Text before the key (`see` in `[see @smith2020]`)
+    pub prefix: Option<String>,
+
+    /// Locator after the key (`p. 12` in `[@smith2020, p. 12]`)
+    pub locator: Option<String>,
+}
+
+/// A bracketed citation of one or more works (`[@a; @b, p. 3]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Citation {
+    /// Cited works in order
+    pub items: Vec<CiteItem>,
+
+    /// Position of the opening `[` in the markdown
+    pub location: SourceLocation,
+}
+
+/// Parse the inside of a bracket as citation items, if it is a citation
+fn parse_cite_items(text: &str) -> Option<Vec<CiteItem>> {
+    text.split(';')
+        .map(|part| {
+            let captures = CITE_ITEM_RE.captures(part)?;
+            let optional = |i: usize| {
+                captures
+                    .get(i)
+                    .map(|m| m.as_str().trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+            Some(CiteItem {
+                key: captures[2].to_string(),
+                prefix: optional(1),
+                locator: optional(3),
+            })
+        })
+        .collect()
+}
+
+/// Find citations in a run of text as (start, end, items) byte ranges
+fn find_citations(text: &str) -> Vec<(usize, usize, Vec<CiteItem>)> {
+    CITATION_RE
+        .captures_iter(text)
+        .filter(|captures| captures.get(2).is_none())
+        .filter_map(|captures| {
+            let whole = captures.get(0)?;
+            Some((whole.start(), whole.end(), parse_cite_items(&captures[1])?))
+        })
+        .collect()
+}
+
+/// Extract citations from markdown, skipping code, math and raw HTML
+pub fn extract_citations(markdown: &str) -> Vec<Citation> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_MATH);
+    let skipped: Vec<Range<usize>> = Parser::new_ext(markdown, options)
+        .into_offset_iter()
+        .filter(|(event, _)| {
+            matches!(
+                event,
+                Event::Start(Tag::CodeBlock(_) | Tag::HtmlBlock | Tag::MetadataBlock(_))
+                    | Event::Code(_)
+                    | Event::InlineMath(_)
+                    | Event::DisplayMath(_)
+                    | Event::InlineHtml(_)
+            )
+        })
+        .map(|(_, range)| range)
+        .collect();
+
+    find_citations(markdown)
+        .into_iter()
+        .filter(|(start, _, _)| !skipped.iter().any(|range| range.contains(start)))
+        .map(|(start, _, items)| Citation {
+            items,
+            location: SourceLocation::from_offset(markdown, start),
+        })
+        .collect()
+}
+
+/// Report citation keys missing from the bibliography
+pub fn unknown_citation_diagnostics(
+    citations: &[Citation],
+    bibliography: &Bibliography,
+) -> Vec<Diagnostic> {
+    citations
+        .iter()
+        .flat_map(|citation| {
+            citation
+                .items
+                .iter()
+                .filter(|item| bibliography.get(&item.key).is_none())
+                .map(|item| {
+                    Diagnostic::at_line(
+                        citation.location.line,
+                        format!("Unknown citation key '@{}'", item.key),
+                    )
+                    .with_column(citation.location.column)
+                })
+        })
+        .collect()
+}
+
+/// Known works cited in `markdown`, in order of first citation
+pub fn cited_entries(markdown: &str, bibliography: &Bibliography) -> Vec<BibEntry> {
+    let mut entries: Vec<BibEntry> = Vec::new();
+    for item in extract_citations(markdown)
+        .into_iter()
+        .flat_map(|citation| citation.items)
+    {
+        if let Some(entry) = bibliography.get(&item.key) {
+            if !entries.iter().any(|e| e.key == entry.key) {
+                entries.push(entry.clone());
+            }
+        }
+    }
+    entries
+}
+
+/// Renders the citations of one document and its bibliography
+pub struct CitationRenderer<'a> {
+    bibliography: &'a Bibliography,
+    style: CitationStyle,
+    /// Cited works in order of first citation (the numeric order)
+    cited: Vec<&'a BibEntry>,
+}
+
+impl<'a> CitationRenderer<'a> {
+    /// Prepare to render `citations` (as found by `extract_citations`)
+    pub fn new(
+        bibliography: &'a Bibliography,
+        citations: &[Citation],
+        style: CitationStyle,
+    ) -> Self {
+        let mut cited: Vec<&BibEntry> = Vec::new();
+        for item in citations.iter().flat_map(|citation| &citation.items) {
+            if let Some(entry) = bibliography.get(&item.key) {
+                if !cited.iter().any(|e| e.key == entry.key) {
+                    cited.push(entry);
+                }
+            }
+        }
+        Self {
+            bibliography,
+            style,
+            cited,
+        }
+    }
+
+    /// Replace citations in text events with links to the bibliography
+    ///
+    /// Citations in code blocks and link text are left alone.
+    pub fn render<'e>(&self, events: Vec<Event<'e>>) -> Vec<Event<'e>> {
+        let mut output = Vec::with_capacity(events.len());
+        let mut skip_depth = 0usize;
+
+        for event in events {
+            match &event {
+                Event::Start(Tag::CodeBlock(_) | Tag::Link { .. } | Tag::MetadataBlock(_)) => {
+                    skip_depth += 1
+                }
+                Event::End(TagEnd::CodeBlock | TagEnd::Link | TagEnd::MetadataBlock(_)) => {
+                    skip_depth -= 1
+                }
+                _ => {}
+            }
+            let Event::Text(text) = &event else {
+                output.push(event);
+                continue;
+            };
+            let citations = find_citations(text);
+            if citations.is_empty() || skip_depth > 0 {
+                output.push(event);
+                continue;
+            }
+
+            let mut last = 0;
+            for (start, end, items) in citations {
+                output.push(Event::Text(CowStr::from(text[last..start].to_string())));
+                output.push(Event::InlineHtml(CowStr::from(
+                    self.render_citation(&items),
+                )));
+                last = end;
+            }
+            output.push(Event::Text(CowStr::from(text[last..].to_string())));
+        }
+
+        output.retain(|event| !matches!(event, Event::Text(text) if text.is_empty()));
+        output
+    }
+
+    /// Render one bracketed citation
+    fn render_citation(&self, items: &[CiteItem]) -> String {
+        let parts: Vec<String> = items
+            .iter()
+            .map(|item| {
+                let mut part = String::new();
+                if let Some(prefix) = &item.prefix {
+                    part.push_str(&html_escape(prefix));
+                    part.push(' ');
+                }
+                match self.bibliography.get(&item.key) {
+                    Some(entry) => {
+                        let label = match self.style {
+                            CitationStyle::AuthorDate => format!(
+                                "{} {}",
+                                html_escape(&entry.author_label()),
+                                entry.year_label()
+                            ),
+                            CitationStyle::Numeric => self
+                                .cited
+                                .iter()
+                                .position(|e| e.key == entry.key)
+                                .map_or(0, |i| i + 1)
+                                .to_string(),
+                        };
+                        part.push_str(&format!(
+                            r##"<a href="#{}">{}</a>"##,
+                            html_escape(&entry.anchor()),
+                            label
+                        ));
+                    }
+                    None => part.push_str(&format!(
+                        r#"<span class="citation-unknown">@{}</span>"#,
+                        html_escape(&item.key)
+                    )),
+                }
+                if let Some(locator) = &item.locator {
+                    part.push_str(", ");
+                    part.push_str(&html_escape(locator));
+                }
+                part
+            })
+            .collect();
+
+        let (open, close) = match self.style {
+            CitationStyle::AuthorDate => ('(', ')'),
+            CitationStyle::Numeric => ('[', ']'),
+        };
+        format!(
+            r#"<span class="citation">{}{}{}</span>"#,
+            open,
+            parts.join("; "),
+            close
+        )
+    }
+
+    /// Bibliography section for the cited works, or an empty string
+    ///
+    /// Numeric bibliographies are numbered in citation order; author-date
+    /// bibliographies are sorted by author and year.
+    pub fn bibliography_html(&self) -> String {
+        if self.cited.is_empty() {
+            return String::new();
+        }
+        let mut entries = self.cited.clone();
+        let list = match self.style {
+            CitationStyle::Numeric => "ol",
+            CitationStyle::AuthorDate => {
+                entries.sort_by_key(|entry| entry.sort_key());
+                "ul"
+            }
+        };
+        let items: String = entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    r#"<li id="{}">{}</li>"#,
+                    html_escape(&entry.anchor()),
+                    format_reference(entry, self.style)
+                )
+            })
+            .collect();
+        format!(
+            r#"<section class="bibliography" id="references"><h2>References</h2><{0} class="bibliography-list">{1}</{0}></section>"#,
+            list, items
+        )
+    }
+}
+
+/// Sort entries for a reference list by author, year and key
+pub fn sort_entries(entries: &mut [BibEntry]) {
+    entries.sort_by_key(|entry| entry.sort_key());
+}
+
+/// Byte offset of the brace closing the one `text` starts with
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse `name = {value}` pairs of a BibTeX entry, lowercasing names
+///
+/// Values may be braced, quoted or bare (numbers and macros), joined by `#`.
+fn parse_bibtex_fields(text: &str) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    let mut rest = text;
+
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq]
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_lowercase();
+        let mut value = String::new();
+        let mut remaining = rest[eq + 1..].trim_start();
+
+        loop {
+            let (part, after) = if remaining.starts_with('{') {
+                match matching_brace(remaining) {
+                    Some(end) => (&remaining[1..end], &remaining[end + 1..]),
+                    None => (&remaining[1..], ""),
+                }
+            } else if let Some(quoted) = remaining.strip_prefix('"') {
+                let end = quoted
+                    .char_indices()
+                    .scan(0usize, |depth, (i, c)| {
+                        match c {
+                            '{' => *depth += 1,
+                            '}' => *depth = depth.saturating_sub(1),
+                            _ => {}
+                        }
+                        Some((i, c, *depth))
+                    })
+                    .find(|&(_, c, depth)| c == '"' && depth == 0)
+                    .map_or(quoted.len(), |(i, _, _)| i);
+                (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+            } else {
+                let end = remaining.find([',', '#']).unwrap_or(remaining.len());
+                (remaining[..end].trim(), &remaining[end..])
+            };
+            value.push_str(part);
+            remaining = after.trim_start();
+            match remaining.strip_prefix('#') {
+                Some(after) => remaining = after.trim_start(),
+                None => break,
+            }
+        }
+
+        fields.insert(name, value);
+        rest = remaining;
+    }
+
+    fields
+}
+
+/// Split a BibTeX name list on ` and ` outside braces
+fn split_bibtex_names(names: &str) -> Vec<Name> {
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = names.as_bytes();
+
+    for (i, c) in names.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0
+                && bytes[i..].len() >= 5
+                && bytes[i..i + 5].eq_ignore_ascii_case(b" and ") =>
+            {
+                result.push(&names[start..i]);
+                start = i + 5;
+            }
+            _ => {}
+        }
+    }
+    result.push(&names[start..]);
+
+    result
+        .into_iter()
+        .filter(|name| !name.trim().is_empty())
+        .map(Name::from_bibtex)
+        .collect()
+}
+
+/// Convert the LaTeX found in BibTeX values to plain text
+///
+/// Braces are removed, accent commands (`\"u`, `{\'e}`) become accented
+/// letters, `--` becomes an en dash and `~` a space.
+fn latex_to_text(value: &str) -> String {
+    let mut text = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '~' => text.push(' '),
+            '\\' => {
+                let Some(&command) = chars.peek() else {
+                    break;
+                };
+                let accent = match command {
+                    '"' => Some('\u{308}'),
+                    '\'' => Some('\u{301}'),
+                    '`' => Some('\u{300}'),
+                    '^' => Some('\u{302}'),
+                    '~' => Some('\u{303}'),
+                    'c' => Some('\u{327}'),
+                    _ => None,
+                };
+                chars.next();
+                match accent {
+                    Some(accent) => {
+                        while chars.peek().is_some_and(|c| *c == '{' || *c == ' ') {
+                            chars.next();
+                        }
+                        if let Some(base) = chars.next() {
+                            text.push(base);
+                            text.push(accent);
+                        }
+                    }
+                    None if command.is_ascii_alphabetic() => {
+                        // Drop other commands (`\textit`), keeping their argument
+                        while chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                            chars.next();
+                        }
+                    }
+                    None => text.push(command),
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+
+    text.replace("--", "–")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .nfc()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::TextMergeStream;
+
+    const BIBTEX: &str = r#"
+@comment{Sample library}
+Contact: library@example.com
+
+@article{smith2020,
+  author = {Smith, John and Alice Doe},
+  title = {On {PARA} Notes},
+  journal = "Journal of Notes",
+  volume = 12,
+  pages = {1--10},
+  year = 2020,
+  doi = {10.1000/xyz}
+}
+
+@book{gödel1931,
+  author = {G{\"o}del, Kurt and Escher, M. C. and {The Bach Society}},
+  title = {Incompleteness},
+  publisher = {ACM} # " Press",
+  date = {1931-03-01}
+}
+"#;
+
+    #[test]
+    fn test_parse_bibtex() {
+        let bibliography = Bibliography::from_bibtex(BIBTEX).unwrap();
+        assert_eq!(bibliography.len(), 2);
+
+        let smith = bibliography.get("smith2020").unwrap();
+        assert_eq!(smith.authors[1].full(), "Alice Doe");
+        assert_eq!(smith.title.as_deref(), Some("On PARA Notes"));
+        assert_eq!(smith.pages.as_deref(), Some("1–10"));
+        assert_eq!(smith.author_label(), "Smith and Doe");
+
+        let godel = bibliography.get("gödel1931").unwrap();
+        assert_eq!(godel.authors[0].inverted(), "Gödel, Kurt");
+        assert_eq!(godel.authors[2].family, "The Bach Society");
+        assert_eq!(godel.year.as_deref(), Some("1931"));
+        assert_eq!(godel.author_label(), "Gödel et al.");
+        assert_eq!(godel.publisher.as_deref(), Some("ACM Press"));
+
+        assert!(Bibliography::from_bibtex("@article{x, title = {open").is_err());
+    }
+
+    #[test]
+    fn test_parse_csl_json() {
+        let json = r#"[{"id": "doe2019", "type": "book", "title": "Notes",
+            "author": [{"family": "Doe", "given": "Alice"}, {"literal": "ACME"}],
+            "issued": {"date-parts": [[2019, 4]]}, "URL": "https://example.com"}]"#;
+        let bibliography = Bibliography::from_csl_json(json).unwrap();
+
+        let doe = bibliography.get("doe2019").unwrap();
+        assert_eq!(doe.year.as_deref(), Some("2019"));
+        assert_eq!(doe.authors[1].family, "ACME");
+        assert!(Bibliography::from_csl_json(r#"[{"title": "No id"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_extract_citations() {
+        let markdown = "As argued [@smith2020, p. 12] and [see @doe2019; @smith2020].\n\n`[@code]` and [@link](https://example.com), mail [me@example.com].\n\n```\n[@block]\n```\n";
+        let citations = extract_citations(markdown);
+
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].location, SourceLocation::new(1, 11));
+        assert_eq!(citations[0].items[0].locator.as_deref(), Some("p. 12"));
+        assert_eq!(citations[1].items[0].prefix.as_deref(), Some("see"));
+        assert_eq!(citations[1].items[1].key, "smith2020");
+    }
+
+    #[test]
+    fn test_render_citations() {
+        let bibliography = Bibliography::from_bibtex(BIBTEX).unwrap();
+        let markdown = "First [@smith2020, p. 12], then [@gödel1931; @missing].";
+        let citations = extract_citations(markdown);
+        let render = |style| {
+            let renderer = CitationRenderer::new(&bibliography, &citations, style);
+            let mut html = String::new();
+            pulldown_cmark::html::push_html(
+                &mut html,
+                renderer
+                    .render(TextMergeStream::new(Parser::new(markdown)).collect())
+                    .into_iter(),
+            );
+            (html, renderer.bibliography_html())
+        };
+
+        let (html, references) = render(CitationStyle::AuthorDate);
+        assert!(html.contains(
+            r##"<span class="citation">(<a href="#ref-smith2020">Smith and Doe 2020</a>, p. 12)</span>"##
+        ));
+        assert!(html.contains(r#"<span class="citation-unknown">@missing</span>"#));
+        assert!(references.starts_with(
+            r#"<section class="bibliography" id="references"><h2>References</h2><ul"#
+        ));
+        assert!(references.find("Gödel").unwrap() < references.find("Smith, John").unwrap());
+        assert!(references.contains(
+            r#"Smith, John and Alice Doe (2020). <cite>On PARA Notes</cite>. Journal of Notes, 12, 1–10. <a href="https://doi.org/10.1000/xyz">"#
+        ));
+
+        let (html, references) = render(CitationStyle::Numeric);
+        assert!(html.contains(r##"[<a href="#ref-gödel1931">2</a>; "##));
+        assert!(references.find("Smith").unwrap() < references.find("Gödel").unwrap());
+
+        assert_eq!(
+            unknown_citation_diagnostics(&citations, &bibliography)[0].to_string(),
+            "line 1, column 33: Unknown citation key '@missing'"
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! ABOUTME: Lenient date parsing for frontmatter values
+//! ABOUTME: Accepts dates, local datetimes, RFC 3339/2822 and unix timestamps
+
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+
+/// How precisely a frontmatter date was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePrecision {
+    /// `2025-06-14`
+    Day,
+    /// `2025-06-14 10:00`
+    Minute,
+    /// `2025-06-14T10:00:00Z`, RFC 2822 and unix timestamps
+    Second,
+}
+
+/// Smallest unix timestamp accepted (10 digits, September 2001), so years
+/// and compact dates such as `2024` or `20240115` are not read as seconds
+const MIN_TIMESTAMP: u64 = 1_000_000_000;
+
+/// A date from frontmatter, keeping its original offset and precision
+///
+/// Dereferences to the underlying `DateTime<FixedOffset>`, so comparisons and
+/// formatting work as with any chrono datetime.
+#[derive(Debug, Clone, Copy)]
+pub struct FrontmatterDate {
+    datetime: DateTime<FixedOffset>,
+    precision: DatePrecision,
+    /// True when the value had no timezone and was read as UTC
+    floating: bool,
+}
+
+impl FrontmatterDate {
+    /// Parse a frontmatter date string
+    ///
+    /// Accepted forms, tried in order: unix timestamps of 10 or more
+    /// digits, RFC 3339 (with `T` or a space), RFC 2822, local datetimes with
+    /// minute or second precision, and plain `YYYY-MM-DD` dates. Values
+    /// without a timezone are read as UTC until
+    /// [`FrontmatterDate::with_default_timezone`] is applied.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        if let Ok(timestamp) = value.parse::<i64>() {
+            return Self::from_unix_value(timestamp);
+        }
+
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+            return Some(Self::fixed(datetime, DatePrecision::Second));
+        }
+
+        for (format, precision) in [
+            ("%Y-%m-%d %H:%M:%S%.f%:z", DatePrecision::Second),
+            ("%Y-%m-%d %H:%M:%S%.f%z", DatePrecision::Second),
+            ("%Y-%m-%dT%H:%M%:z", DatePrecision::Minute),
+            ("%Y-%m-%d %H:%M%:z", DatePrecision::Minute),
+        ] {
+            if let Ok(datetime) = DateTime::parse_from_str(value, format) {
+                return Some(Self::fixed(datetime, precision));
+            }
+        }
+
+        if let Ok(datetime) = DateTime::parse_from_rfc2822(value) {
+            return Some(Self::fixed(datetime, DatePrecision::Second));
+        }
+
+        for (format, precision) in [
+            ("%Y-%m-%dT%H:%M:%S%.f", DatePrecision::Second),
+            ("%Y-%m-%d %H:%M:%S%.f", DatePrecision::Second),
+            ("%Y-%m-%dT%H:%M", DatePrecision::Minute),
+            ("%Y-%m-%d %H:%M", DatePrecision::Minute),
+        ] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+                return Some(Self::floating(naive, precision));
+            }
+        }
+
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .map(|date| Self::floating(date.and_time(NaiveTime::MIN), DatePrecision::Day))
+    }
+
+    /// Create a date from a unix timestamp in seconds
+    ///
+    /// Values too large to be seconds are treated as milliseconds.
+    pub fn from_timestamp(timestamp: i64) -> Option<Self> {
+        let datetime = if timestamp.abs() >= 100_000_000_000 {
+            DateTime::from_timestamp_millis(timestamp)?
+        } else {
+            DateTime::from_timestamp(timestamp, 0)?
+        };
+        Some(Self::from(datetime))
+    }
+
+    /// A timestamp written in frontmatter, unless it is too short to be one
+    fn from_unix_value(timestamp: i64) -> Option<Self> {
+        if timestamp.unsigned_abs() < MIN_TIMESTAMP {
+            return None;
+        }
+        Self::from_timestamp(timestamp)
+    }
+
+    fn fixed(datetime: DateTime<FixedOffset>, precision: DatePrecision) -> Self {
+        Self {
+            datetime,
+            precision,
+            floating: false,
+        }
+    }
+
+    fn floating(naive: NaiveDateTime, precision: DatePrecision) -> Self {
+        Self {
+            datetime: naive.and_utc().fixed_offset(),
+            precision,
+            floating: true,
+        }
+    }
+
+    /// Interpret a date written without a timezone in `tz`
+    ///
+    /// Dates with an explicit offset are returned unchanged.
+    pub fn with_default_timezone(self, tz: Tz) -> Self {
+        if !self.floating {
+            return self;
+        }
+
+        let naive = self.datetime.naive_local();
+        let datetime = tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .unwrap_or_else(|| {
+                // Wall-clock times skipped by a DST transition are read with the
+                // offset in effect just before it, so 02:30 becomes 03:30
+                let offset_before = tz.offset_from_utc_datetime(&(naive - TimeDelta::days(1)));
+                tz.from_utc_datetime(&(naive - offset_before.fix()))
+            })
+            .fixed_offset();
+
+        Self {
+            datetime,
+            precision: self.precision,
+            floating: false,
+        }
+    }
+
+    /// Precision of the original value
+    pub fn precision(&self) -> DatePrecision {
+        self.precision
+    }
+
+    /// The date in UTC
+    pub fn utc(&self) -> DateTime<Utc> {
+        self.datetime.with_timezone(&Utc)
+    }
+
+    /// Format the date with the precision it was written in, in its own offset
+    pub fn display(&self) -> String {
+        let format = match self.precision {
+            DatePrecision::Day => "%Y-%m-%d",
+            DatePrecision::Minute => "%Y-%m-%d %H:%M",
+            DatePrecision::Second => "%Y-%m-%d %H:%M:%S",
+        };
+        self.datetime.format(format).to_string()
+    }
+}
+
+impl From<DateTime<Utc>> for FrontmatterDate {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        Self::fixed(datetime.with_timezone(&Utc.fix()), DatePrecision::Second)
+    }
+}
+
+impl Deref for FrontmatterDate {
+    type Target = DateTime<FixedOffset>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.datetime
+    }
+}
+
+impl PartialEq for FrontmatterDate {
+    fn eq(&self, other: &Self) -> bool {
+        self.datetime == other.datetime
+    }
+}
+
+impl Eq for FrontmatterDate {}
+
+impl PartialOrd for FrontmatterDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrontmatterDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.datetime.cmp(&other.datetime)
+    }
+}
+
+impl Serialize for FrontmatterDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.datetime.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for FrontmatterDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(FrontmatterDateVisitor)
+    }
+}
+
+struct FrontmatterDateVisitor;
+
+impl Visitor<'_> for FrontmatterDateVisitor {
+    type Value = FrontmatterDate;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a date (YYYY-MM-DD), datetime, RFC 2822 date or unix timestamp")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if let Ok(number) = value.trim().parse::<i64>() {
+            return self.visit_i64(number);
+        }
+        FrontmatterDate::parse(value).ok_or_else(|| {
+            E::custom(format!(
+                "invalid date '{}': expected YYYY-MM-DD, a datetime such as 2025-06-14 10:00, an RFC 2822 date or a unix timestamp",
+                value
+            ))
+        })
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        if value.unsigned_abs() < MIN_TIMESTAMP {
+            return Err(E::custom(format!(
+                "invalid date '{}': write dates as YYYY-MM-DD (unix timestamps need at least 10 digits)",
+                value
+            )));
+        }
+        FrontmatterDate::from_timestamp(value)
+            .ok_or_else(|| E::custom(format!("unix timestamp {} is out of range", value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        i64::try_from(value)
+            .map_err(|_| E::custom(format!("unix timestamp {} is out of range", value)))
+            .and_then(|value| self.visit_i64(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_only_and_local_datetimes() {
+        let date = FrontmatterDate::parse("2025-06-14").unwrap();
+        assert_eq!(date.precision(), DatePrecision::Day);
+        assert_eq!(date.display(), "2025-06-14");
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 0, 0, 0).unwrap()
+        );
+
+        let date = FrontmatterDate::parse("2025-06-14 10:00").unwrap();
+        assert_eq!(date.precision(), DatePrecision::Minute);
+        assert_eq!(date.display(), "2025-06-14 10:00");
+
+        let date = FrontmatterDate::parse("2025-06-14T10:00:30").unwrap();
+        assert_eq!(date.display(), "2025-06-14 10:00:30");
+    }
+
+    #[test]
+    fn test_parse_zoned_formats() {
+        let date = FrontmatterDate::parse("2025-06-14T10:00:00+02:00").unwrap();
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 8, 0, 0).unwrap()
+        );
+        assert_eq!(date.display(), "2025-06-14 10:00:00");
+
+        let date = FrontmatterDate::parse("Sat, 14 Jun 2025 10:00:00 +0000").unwrap();
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 10, 0, 0).unwrap()
+        );
+
+        let date = FrontmatterDate::parse("1749895200").unwrap();
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 10, 0, 0).unwrap()
+        );
+
+        assert!(FrontmatterDate::parse("next tuesday").is_none());
+        assert!(FrontmatterDate::parse("2024").is_none());
+        assert!(FrontmatterDate::parse("20240115").is_none());
+    }
+
+    #[test]
+    fn test_default_timezone() {
+        let tokyo: Tz = "Asia/Tokyo".parse().unwrap();
+
+        let date = FrontmatterDate::parse("2025-06-14")
+            .unwrap()
+            .with_default_timezone(tokyo);
+        assert_eq!(date.display(), "2025-06-14");
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 13, 15, 0, 0).unwrap()
+        );
+
+        // Explicit offsets win over the default timezone
+        let date = FrontmatterDate::parse("2025-06-14T10:00:00Z")
+            .unwrap()
+            .with_default_timezone(tokyo);
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 10, 0, 0).unwrap()
+        );
+
+        // 02:30 does not exist on the day clocks spring forward
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let date = FrontmatterDate::parse("2024-03-10T02:30")
+            .unwrap()
+            .with_default_timezone(new_york);
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2024, 3, 10, 7, 30, 0).unwrap()
+        );
+        assert_eq!(date.display(), "2024-03-10 03:30");
+    }
+
+    #[test]
+    fn test_deserialize_from_yaml() {
+        let date: FrontmatterDate = serde_yaml::from_str("2025-06-14").unwrap();
+        assert_eq!(date.precision(), DatePrecision::Day);
+
+        let date: FrontmatterDate = serde_yaml::from_str("1749895200").unwrap();
+        assert_eq!(
+            date.utc(),
+            Utc.with_ymd_and_hms(2025, 6, 14, 10, 0, 0).unwrap()
+        );
+
+        let err = serde_yaml::from_str::<FrontmatterDate>("'soon'").unwrap_err();
+        assert!(err.to_string().contains("invalid date 'soon'"));
+
+        // Years and compact dates are not timestamps, quoted or not
+        for value in ["2024", "20240115", "'20240115'"] {
+            let err = serde_yaml::from_str::<FrontmatterDate>(value).unwrap_err();
+            assert!(
+                err.to_string().contains("write dates as YYYY-MM-DD"),
+                "{}",
+                err
+            );
+        }
+    }
+}
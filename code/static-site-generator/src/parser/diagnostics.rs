@@ -0,0 +1,169 @@
+//! ABOUTME: Diagnostics collected while parsing documents
+//! ABOUTME: Non-fatal problems that are reported in the build output
+
+use std::fmt;
+use std::path::Path;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Severity {
+    /// Reported, but the build succeeds
+    #[default]
+    Warning,
+    /// Reported, and the build exits with an error once the site is written
+    Error,
+}
+
+/// A non-fatal problem found while processing a document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Line in the source file (1-based), if known
+    pub line: Option<usize>,
+
+    /// Column on that line (1-based, in characters), if known
+    pub column: Option<usize>,
+
+    /// Text of the source line, shown as an excerpt under the message
+    pub source_line: Option<String>,
+
+    /// Human-readable description of the problem
+    pub message: String,
+
+    /// Whether the problem is a warning or an error
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    /// Create a warning pointing at a source line
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: None,
+            source_line: None,
+            message: message.into(),
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Point the diagnostic at a column of its line
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Attach the text of the source line for the excerpt
+    pub fn with_source_line(mut self, source_line: impl Into<String>) -> Self {
+        self.source_line = Some(source_line.into());
+        self
+    }
+
+    /// Set the severity of the diagnostic
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Check if the diagnostic is an error
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Format as `path:line:col: message`, the form editors can jump to
+    ///
+    /// Unknown parts of the location are left out.
+    pub fn located(&self, path: &Path) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                format!("{}:{}:{}: {}", path.display(), line, column, self.message)
+            }
+            (Some(line), None) => format!("{}:{}: {}", path.display(), line, self.message),
+            _ => format!("{}: {}", path.display(), self.message),
+        }
+    }
+
+    /// The source line with a caret under the column, if both are known
+    pub fn excerpt(&self) -> Option<String> {
+        Some(source_excerpt(
+            self.line?,
+            self.source_line.as_deref()?,
+            self.column,
+        ))
+    }
+}
+
+/// Format a source line with its line number and a caret under `column`
+///
+/// Tabs are shown as four spaces so the caret lines up.
+pub fn source_excerpt(line: usize, text: &str, column: Option<usize>) -> String {
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+    let shown = text.trim_end_matches(['\r', '\n']).replace('\t', "    ");
+    let mut excerpt = format!("{} |\n{} | {}\n", gutter, number, shown);
+
+    if let Some(column) = column {
+        let offset: usize = text
+            .chars()
+            .take(column.saturating_sub(1))
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        excerpt.push_str(&format!("{} | {}^\n", gutter, " ".repeat(offset)));
+    }
+    excerpt
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display() {
+        let diagnostic = Diagnostic::at_line(12, "Included file not found");
+        assert_eq!(diagnostic.to_string(), "line 12: Included file not found");
+
+        let diagnostic = Diagnostic {
+            line: None,
+            column: None,
+            source_line: None,
+            message: "Something went wrong".to_string(),
+            severity: Severity::Error,
+        };
+        assert_eq!(diagnostic.to_string(), "Something went wrong");
+        assert!(diagnostic.is_error());
+    }
+
+    #[test]
+    fn test_diagnostic_excerpt() {
+        let diagnostic = Diagnostic::at_line(4, "Tab characters are not allowed")
+            .with_column(2)
+            .with_source_line("\t- tab");
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 4, column 2: Tab characters are not allowed"
+        );
+        assert_eq!(
+            diagnostic.excerpt().unwrap(),
+            "  |\n4 |     - tab\n  |     ^\n"
+        );
+        assert!(Diagnostic::at_line(4, "No source").excerpt().is_none());
+        assert_eq!(
+            diagnostic.located(Path::new("notes/a.md")),
+            "notes/a.md:4:2: Tab characters are not allowed"
+        );
+        assert_eq!(
+            Diagnostic::at_line(7, "Unknown key").located(Path::new("a.md")),
+            "a.md:7: Unknown key"
+        );
+    }
+}
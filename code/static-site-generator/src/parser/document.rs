@@ -1,10 +1,20 @@
 //! ABOUTME: Document structure representing parsed markdown files with metadata
 //! ABOUTME: Combines frontmatter metadata with parsed content
 
+use super::citations::{BibEntry, Bibliography};
+use super::dates::FrontmatterDate;
+use super::diagnostics::Diagnostic;
+use super::languages::{translation_key, Translation, DEFAULT_LANGUAGE};
+use super::relationships::Relationship;
+use super::spans::LineMap;
+use super::stats::ContentStats;
+use super::tasks::{Task, TaskProgress};
+use super::toc::TocEntry;
 use super::wiki_links::ResolvedLink;
-use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Complete document with metadata and content
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,16 +37,69 @@ pub struct Document {
     /// Parsed HTML content
     pub html_content: String,
 
+    /// Word count, reading time and other content statistics
+    pub stats: ContentStats,
+
+    /// Task list items (`- [ ]`) in the content
+    pub tasks: Vec<Task>,
+
     /// PARA category detected from path
     pub category: String,
 
+    /// Language code, from the `lang` key or a `.xx.md` filename suffix
+    pub lang: String,
+
+    /// Key shared with translations of the document
+    pub translation_key: String,
+
+    /// Other language versions of the document
+    #[serde(skip)]
+    pub translations: Vec<Translation>,
+
     /// Resolved wiki links in this document
     #[serde(skip)]
     pub wiki_links: Vec<ResolvedLink>,
 
+    /// Typed relationships from frontmatter keys such as `implements`
+    #[serde(skip)]
+    pub relationships: Vec<Relationship>,
+
     /// Documents that link to this document
     #[serde(skip)]
     pub backlinks: Vec<BacklinkReference>,
+
+    /// Files outside the document that its output depends on (e.g. includes)
+    #[serde(skip)]
+    pub dependencies: Vec<PathBuf>,
+
+    /// Non-fatal problems found while parsing
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
+
+    /// Frontmatter parse failure, when the document was rendered without
+    /// its frontmatter
+    #[serde(skip)]
+    pub frontmatter_error: Option<Diagnostic>,
+
+    /// Maps lines of `raw_content` to lines of the source file
+    #[serde(skip)]
+    pub line_map: LineMap,
+
+    /// Site bibliography merged with the document's own `bibliography`
+    /// file, when it has one
+    #[serde(skip)]
+    pub bibliography: Option<Arc<Bibliography>>,
+
+    /// Works cited in the document, in order of first citation
+    #[serde(skip)]
+    pub citations: Vec<BibEntry>,
+
+    /// Glossary terms linked from the document
+    #[serde(skip)]
+    pub glossary_terms: Vec<String>,
+
+    /// Headings of the rendered document with their anchor IDs
+    pub toc: Vec<TocEntry>,
 }
 
 /// Reference to a document that links to this document
@@ -50,6 +113,10 @@ pub struct BacklinkReference {
 
     /// Context around the link (surrounding text)
     pub link_context: Option<String>,
+
+    /// Relationship heading for typed links (e.g. "Implemented by"), or
+    /// `None` for plain wiki links
+    pub relationship: Option<String>,
 }
 
 /// Document metadata typically found in YAML frontmatter
@@ -67,13 +134,13 @@ pub struct DocumentMetadata {
     pub tags: Vec<String>,
 
     /// Creation date
-    pub created: Option<DateTime<Utc>>,
+    pub created: Option<FrontmatterDate>,
 
     /// Last modification date
-    pub modified: Option<DateTime<Utc>>,
+    pub modified: Option<FrontmatterDate>,
 
     /// Publication date (if different from created)
-    pub date: Option<DateTime<Utc>>,
+    pub date: Option<FrontmatterDate>,
 
     /// Document status (draft, published, etc.)
     pub status: Option<String>,
@@ -89,12 +156,25 @@ pub struct DocumentMetadata {
     pub custom: std::collections::HashMap<String, serde_yaml::Value>,
 }
 
+impl DocumentMetadata {
+    /// Interpret dates written without a timezone in `tz`
+    pub fn apply_default_timezone(&mut self, tz: Tz) {
+        for date in [&mut self.created, &mut self.modified, &mut self.date]
+            .into_iter()
+            .flatten()
+        {
+            *date = date.with_default_timezone(tz);
+        }
+    }
+}
+
 impl Document {
     /// Create a new document with the given paths and category
     pub fn new(source_path: PathBuf, relative_path: PathBuf, category: String) -> Self {
         // Generate output path by replacing .md with .html
         let mut output_path = relative_path.clone();
         output_path.set_extension("html");
+        let translation_key = translation_key(&DocumentMetadata::default(), &relative_path);
 
         Self {
             source_path,
@@ -103,9 +183,23 @@ impl Document {
             metadata: DocumentMetadata::default(),
             raw_content: String::new(),
             html_content: String::new(),
+            stats: ContentStats::default(),
+            tasks: Vec::new(),
             category,
+            lang: DEFAULT_LANGUAGE.to_string(),
+            translation_key,
+            translations: Vec::new(),
             wiki_links: Vec::new(),
+            relationships: Vec::new(),
             backlinks: Vec::new(),
+            dependencies: Vec::new(),
+            diagnostics: Vec::new(),
+            frontmatter_error: None,
+            line_map: LineMap::default(),
+            bibliography: None,
+            citations: Vec::new(),
+            glossary_terms: Vec::new(),
+            toc: Vec::new(),
         }
     }
 
@@ -129,8 +223,28 @@ impl Document {
         self.metadata.status.as_deref() == Some("draft")
     }
 
+    /// Check if the document asks not to be indexed by search engines, with
+    /// `noindex: true` or `robots: noindex`
+    pub fn is_noindex(&self) -> bool {
+        let custom = &self.metadata.custom;
+        custom.get("noindex").and_then(|v| v.as_bool()) == Some(true)
+            || custom
+                .get("robots")
+                .and_then(|v| v.as_str())
+                .is_some_and(|robots| robots.contains("noindex"))
+    }
+
+    /// Task completion, if the document has any tasks
+    pub fn task_progress(&self) -> Option<TaskProgress> {
+        if self.tasks.is_empty() {
+            None
+        } else {
+            Some(TaskProgress::from_tasks(&self.tasks))
+        }
+    }
+
     /// Get the most relevant date (date > modified > created)
-    pub fn date(&self) -> Option<&DateTime<Utc>> {
+    pub fn date(&self) -> Option<&FrontmatterDate> {
         self.metadata
             .date
             .as_ref()
@@ -1,106 +1,465 @@
-//! ABOUTME: YAML frontmatter parsing functionality
+//! ABOUTME: YAML, TOML and JSON frontmatter parsing functionality
 //! ABOUTME: Extracts metadata from markdown file headers
 
+use super::diagnostics::{Diagnostic, Severity};
 use super::document::DocumentMetadata;
+use super::schema::declares_key;
+use super::spans::SourceLocation;
+use crate::utils::html_escape;
 use crate::{ParaSsgError, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::path::Path;
+
+lazy_static! {
+    /// Location suffix that YAML and JSON parsers append to their messages
+    static ref LOCATION_SUFFIX_RE: Regex = Regex::new(r" at line \d+ column \d+").unwrap();
+    /// Key named in a duplicate key error
+    static ref DUPLICATE_KEY_RE: Regex =
+        Regex::new(r#"duplicate (?:field|key|entry with key) [`"]([^`"]+)[`"]"#).unwrap();
+}
+
+/// Frontmatter formats recognised at the top of a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// YAML between `---` fences
+    Yaml,
+    /// TOML between `+++` fences (Hugo, Zola)
+    Toml,
+    /// A JSON object opened by a `{` line
+    Json,
+}
+
+impl FrontmatterFormat {
+    /// Human-readable name used in error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            FrontmatterFormat::Yaml => "YAML",
+            FrontmatterFormat::Toml => "TOML",
+            FrontmatterFormat::Json => "JSON",
+        }
+    }
+}
+
+impl std::fmt::Display for FrontmatterFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Frontmatter that failed to parse, located in the source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterError {
+    /// Human-readable description of the problem
+    pub message: String,
+
+    /// Line in the source file (1-based), if known
+    pub line: Option<usize>,
+
+    /// Column on that line (1-based), if known
+    pub column: Option<usize>,
+
+    /// Text of the offending line
+    pub source_line: Option<String>,
+
+    /// Markdown after the frontmatter, or the whole file when the
+    /// frontmatter is unclosed
+    pub body: String,
+}
+
+impl FrontmatterError {
+    /// Convert to a document diagnostic with a source excerpt
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            line: self.line,
+            column: self.column,
+            source_line: self.source_line.clone(),
+            message: self.message.clone(),
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Error for a document at `path`, reported as `path:line:col` with
+    /// the source excerpt
+    pub fn into_located_error(self, path: &Path) -> ParaSsgError {
+        let diagnostic = self.to_diagnostic();
+        with_excerpt(diagnostic.located(path), &diagnostic)
+    }
+}
+
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_diagnostic())
+    }
+}
+
+impl From<FrontmatterError> for ParaSsgError {
+    fn from(error: FrontmatterError) -> Self {
+        let diagnostic = error.to_diagnostic();
+        with_excerpt(diagnostic.to_string(), &diagnostic)
+    }
+}
+
+/// Parse error with the diagnostic's source excerpt below the message
+fn with_excerpt(message: String, diagnostic: &Diagnostic) -> ParaSsgError {
+    ParaSsgError::ParseError(match diagnostic.excerpt() {
+        Some(excerpt) => format!("{}\n{}", message, excerpt.trim_end()),
+        None => message,
+    })
+}
+
+/// A parse failure located relative to the text that was parsed
+struct RawError {
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl RawError {
+    fn new(message: String) -> Self {
+        Self {
+            message,
+            line: None,
+            column: None,
+        }
+    }
+
+    fn at(message: String, location: Option<(usize, usize)>) -> Self {
+        Self {
+            message,
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column).filter(|&c| c > 0),
+        }
+    }
+
+    /// Place the error in the file, given the lines before the parsed text
+    fn locate(self, content: &str, line_offset: usize, body: &str) -> FrontmatterError {
+        let line = self.line.map(|line| line + line_offset);
+        FrontmatterError {
+            message: self.message,
+            line,
+            column: self.column,
+            source_line: line
+                .and_then(|line| content.lines().nth(line - 1))
+                .map(str::to_string),
+            body: body.to_string(),
+        }
+    }
+}
 
 /// Parse frontmatter and content from markdown text
 ///
-/// Returns a tuple of (metadata, content) where content is the markdown
-/// without the frontmatter section.
+/// Supports YAML (`---`), TOML (`+++`) and JSON (a leading `{` line)
+/// frontmatter. Returns a tuple of (metadata, content) where content is the
+/// markdown without the frontmatter section.
 ///
 /// # Errors
 ///
-/// Returns error if YAML parsing fails
+/// Returns error if the frontmatter is unclosed or fails to parse
 pub fn parse_frontmatter(content: &str) -> Result<(DocumentMetadata, String)> {
-    // Check if content starts with frontmatter delimiter
-    if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
+    Ok(try_parse_frontmatter(content)?)
+}
+
+/// Parse frontmatter, keeping the location and body of a failure
+///
+/// Unlike [`parse_frontmatter`], the error carries the file-relative line
+/// and column of the problem and the markdown body, so callers can still
+/// render the document.
+pub fn try_parse_frontmatter(
+    content: &str,
+) -> std::result::Result<(DocumentMetadata, String), FrontmatterError> {
+    let split = split_frontmatter(content).map_err(|e| e.locate(content, 0, content))?;
+    let Some((format, raw, remaining_content)) = split else {
         // No frontmatter, return default metadata and full content
         return Ok((DocumentMetadata::default(), content.to_string()));
+    };
+
+    let (metadata, line_offset) = match format {
+        // Fenced frontmatter starts on the line after the opening fence
+        FrontmatterFormat::Yaml => (parse_yaml(raw), 1),
+        FrontmatterFormat::Toml => (parse_toml(raw), 1),
+        FrontmatterFormat::Json => (parse_json(raw), 0),
+    };
+    let metadata = metadata.map_err(|e| e.locate(content, line_offset, remaining_content))?;
+
+    Ok((metadata, remaining_content.to_string()))
+}
+
+/// Extract the raw frontmatter string and its format if present
+///
+/// For JSON frontmatter the returned string is the whole object.
+pub fn extract_frontmatter(content: &str) -> Option<(FrontmatterFormat, String)> {
+    split_frontmatter(content)
+        .ok()
+        .flatten()
+        .map(|(format, raw, _)| (format, raw.to_string()))
+}
+
+/// Split content into (format, raw frontmatter, remaining content)
+fn split_frontmatter(
+    content: &str,
+) -> std::result::Result<Option<(FrontmatterFormat, &str, &str)>, RawError> {
+    if let Some(split) = split_fenced(content, "---")? {
+        return Ok(Some((FrontmatterFormat::Yaml, split.0, split.1)));
+    }
+    if let Some(split) = split_fenced(content, "+++")? {
+        return Ok(Some((FrontmatterFormat::Toml, split.0, split.1)));
     }
+    if let Some(split) = split_json(content)? {
+        return Ok(Some((FrontmatterFormat::Json, split.0, split.1)));
+    }
+    Ok(None)
+}
 
-    // Find the closing delimiter
-    let content_after_first = if content.starts_with("---\n") {
-        &content[4..]
+/// Split frontmatter enclosed by `fence` lines (`---` or `+++`)
+fn split_fenced<'a>(
+    content: &'a str,
+    fence: &str,
+) -> std::result::Result<Option<(&'a str, &'a str)>, RawError> {
+    // Check if content starts with frontmatter delimiter
+    let content_after_first = if let Some(rest) = content
+        .strip_prefix(fence)
+        .and_then(|rest| rest.strip_prefix('\n'))
+    {
+        rest
+    } else if let Some(rest) = content
+        .strip_prefix(fence)
+        .and_then(|rest| rest.strip_prefix("\r\n"))
+    {
+        rest
     } else {
-        &content[5..] // ---\r\n
+        return Ok(None);
     };
 
-    // Look for the closing ---
+    // Look for the closing fence
     // Also check at the start in case of empty frontmatter
-    let end_pattern = "---\n";
-    let end_pattern_crlf = "---\r\n";
+    let end_pattern = format!("{}\n", fence);
+    let end_pattern_crlf = format!("{}\r\n", fence);
+    let end_line = format!("\n{}\n", fence);
+    let end_line_crlf = format!("\r\n{}\r\n", fence);
 
-    let (yaml_content, remaining_content) = if content_after_first.starts_with(end_pattern) {
+    let split = if let Some(rest) = content_after_first.strip_prefix(&end_pattern) {
         // Empty frontmatter
-        ("", &content_after_first[end_pattern.len()..])
-    } else if content_after_first.starts_with(end_pattern_crlf) {
+        ("", rest)
+    } else if let Some(rest) = content_after_first.strip_prefix(&end_pattern_crlf) {
         // Empty frontmatter with CRLF
-        ("", &content_after_first[end_pattern_crlf.len()..])
-    } else if let Some(end_pos) = content_after_first.find("\n---\n") {
+        ("", rest)
+    } else if let Some(end_pos) = content_after_first.find(&end_line) {
         (
             &content_after_first[..end_pos],
-            &content_after_first[end_pos + "\n---\n".len()..],
+            &content_after_first[end_pos + end_line.len()..],
         )
-    } else if let Some(end_pos) = content_after_first.find("\r\n---\r\n") {
+    } else if let Some(end_pos) = content_after_first.find(&end_line_crlf) {
         (
             &content_after_first[..end_pos],
-            &content_after_first[end_pos + "\r\n---\r\n".len()..],
+            &content_after_first[end_pos + end_line_crlf.len()..],
         )
     } else {
-        // No closing delimiter found
-        return Err(ParaSsgError::ParseError(
+        // No closing delimiter found; point at the opening fence
+        return Err(RawError::at(
             "Frontmatter starting delimiter found but no closing delimiter".to_string(),
+            Some((1, 1)),
         ));
     };
 
-    // Parse YAML content
-    let metadata: DocumentMetadata = serde_yaml::from_str(yaml_content).map_err(|e| {
+    Ok(Some(split))
+}
+
+/// Split a leading JSON object from the content
+///
+/// Only a first line consisting of `{` alone starts JSON frontmatter, so
+/// documents opening with inline braces are left untouched.
+fn split_json(content: &str) -> std::result::Result<Option<(&str, &str)>, RawError> {
+    let first_line = content.lines().next().unwrap_or("");
+    if first_line.trim_end() != "{" {
+        return Ok(None);
+    }
+
+    let mut stream = serde_json::Deserializer::from_str(content).into_iter::<serde_json::Value>();
+    match stream.next() {
+        Some(Ok(_)) => {}
+        Some(Err(e)) if e.is_eof() => {
+            return Err(RawError::at(
+                "Frontmatter starting delimiter found but no closing delimiter".to_string(),
+                Some((1, 1)),
+            ));
+        }
+        Some(Err(e)) => return Err(json_error(content, &e)),
+        None => return Ok(None),
+    }
+    let end = stream.byte_offset();
+
+    // Skip the rest of the closing line
+    let rest = content[end..].trim_start_matches([' ', '\t']);
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+        .unwrap_or(rest);
+
+    Ok(Some((&content[..end], rest)))
+}
+
+/// Parse YAML frontmatter
+fn parse_yaml(yaml_content: &str) -> std::result::Result<DocumentMetadata, RawError> {
+    serde_yaml::from_str(yaml_content).map_err(|e| {
         // Provide more helpful error messages for common YAML issues
-        let error_line = e.location().map(|loc| loc.line()).unwrap_or(0);
-        let error_msg = if yaml_content.contains(":\t") || yaml_content.contains("\t") {
-            format!(
-                "YAML parsing failed at line {}: Tab characters are not allowed in YAML. Use spaces for indentation. Error: {}",
-                error_line, e
+        let location = e.location().map(|loc| (loc.line(), loc.column()));
+        let detail = LOCATION_SUFFIX_RE.replace_all(&e.to_string(), "").to_string();
+        if let Some(tab) = tab_location(yaml_content) {
+            RawError::at(
+                format!(
+                    "YAML parsing failed: Tab characters are not allowed in YAML. Use spaces for indentation. Error: {}",
+                    detail
+                ),
+                Some(tab),
             )
-        } else if e.to_string().contains("duplicate key") {
-            format!(
-                "YAML parsing failed: Duplicate keys found in frontmatter. Each key should appear only once. Error: {}",
-                e
+        } else if let Some(duplicate) = duplicate_key_location(yaml_content, &detail) {
+            RawError::at(
+                format!(
+                    "YAML parsing failed: Duplicate keys found in frontmatter. Each key should appear only once. Error: {}",
+                    detail
+                ),
+                Some(duplicate),
             )
-        } else if e.to_string().contains("did not find expected") {
-            format!(
-                "YAML parsing failed at line {}: Invalid YAML syntax. Check for missing colons, quotes, or incorrect indentation. Error: {}",
-                error_line, e
+        } else if detail.contains("did not find expected") {
+            RawError::at(
+                format!(
+                    "YAML parsing failed: Invalid YAML syntax. Check for missing colons, quotes, or incorrect indentation. Error: {}",
+                    detail
+                ),
+                location,
             )
         } else {
-            format!("Failed to parse YAML frontmatter at line {}: {}", error_line, e)
-        };
-        ParaSsgError::ParseError(error_msg)
+            RawError::at(
+                format!("Failed to parse YAML frontmatter: {}", detail),
+                location,
+            )
+        }
+    })
+}
+
+/// Parse TOML frontmatter
+///
+/// The table is converted to JSON first so TOML datetimes deserialize into
+/// the same fields as quoted YAML dates.
+fn parse_toml(toml_content: &str) -> std::result::Result<DocumentMetadata, RawError> {
+    let table: toml::Table = toml::from_str(toml_content).map_err(|e| {
+        let location = e
+            .span()
+            .map(|span| SourceLocation::from_offset(toml_content, span.start))
+            .map(|location| (location.line, location.column));
+        let message = e.message().to_string();
+        if let Some(duplicate) = duplicate_key_location(toml_content, &message) {
+            RawError::at(
+                format!(
+                    "TOML parsing failed: Duplicate keys found in frontmatter. Each key should appear only once. Error: {}",
+                    message
+                ),
+                Some(duplicate),
+            )
+        } else {
+            RawError::at(
+                format!(
+                    "TOML parsing failed: Invalid TOML syntax. Check for missing equals signs, quotes, or unbalanced brackets. Error: {}",
+                    message
+                ),
+                location,
+            )
+        }
     })?;
 
-    Ok((metadata, remaining_content.to_string()))
+    serde_json::from_value(toml_to_json(toml::Value::Table(table)))
+        .map_err(|e| RawError::new(format!("Failed to parse TOML frontmatter: {}", e)))
 }
 
-/// Extract just the frontmatter YAML string if present
-pub fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
-        return None;
+/// Convert a TOML value to JSON, rendering datetimes as RFC 3339 strings
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Integer(i) => serde_json::Value::from(i),
+        toml::Value::Float(f) => serde_json::Value::from(f),
+        toml::Value::Boolean(b) => serde_json::Value::Bool(b),
+        toml::Value::Datetime(dt) => serde_json::Value::String(dt.to_string()),
+        toml::Value::Array(items) => items.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
     }
+}
 
-    let content_after_first = if content.starts_with("---\n") {
-        &content[4..]
-    } else {
-        &content[5..]
-    };
+/// Parse JSON frontmatter
+fn parse_json(json_content: &str) -> std::result::Result<DocumentMetadata, RawError> {
+    serde_json::from_str(json_content).map_err(|e| json_error(json_content, &e))
+}
 
-    if let Some(end_pos) = content_after_first.find("\n---\n") {
-        Some(content_after_first[..end_pos].to_string())
-    } else if let Some(end_pos) = content_after_first.find("\r\n---\r\n") {
-        Some(content_after_first[..end_pos].to_string())
+/// Build a frontmatter error from a JSON error
+fn json_error(json_content: &str, e: &serde_json::Error) -> RawError {
+    let location = Some((e.line(), e.column()));
+    let detail = LOCATION_SUFFIX_RE
+        .replace_all(&e.to_string(), "")
+        .to_string();
+    if let Some(duplicate) = duplicate_key_location(json_content, &detail) {
+        RawError::at(
+            format!(
+                "JSON parsing failed: Duplicate keys found in frontmatter. Each key should appear only once. Error: {}",
+                detail
+            ),
+            Some(duplicate),
+        )
+    } else if e.is_syntax() {
+        RawError::at(
+            format!(
+                "JSON parsing failed: Invalid JSON syntax. Check for missing commas, quotes, or unbalanced braces. Error: {}",
+                detail
+            ),
+            location,
+        )
     } else {
-        None
+        RawError::at(
+            format!("Failed to parse JSON frontmatter: {}", detail),
+            location,
+        )
+    }
+}
+
+/// Warning banner shown above a document rendered without its frontmatter
+pub fn render_frontmatter_banner(diagnostic: &Diagnostic) -> String {
+    let mut html = format!(
+        "<div class=\"frontmatter-error\" role=\"alert\">\n<p><strong>Frontmatter error</strong> ({}). The page was rendered without its metadata.</p>\n",
+        html_escape(&diagnostic.to_string())
+    );
+    if let Some(excerpt) = diagnostic.excerpt() {
+        html.push_str(&format!("<pre>{}</pre>\n", html_escape(&excerpt)));
     }
+    html.push_str("</div>\n");
+    html
+}
+
+/// Line and column (1-based) of the first tab in the text
+fn tab_location(text: &str) -> Option<(usize, usize)> {
+    text.lines().enumerate().find_map(|(index, line)| {
+        line.chars()
+            .position(|c| c == '\t')
+            .map(|column| (index + 1, column + 1))
+    })
+}
+
+/// Line and column of the second occurrence of the key named in a
+/// duplicate key error message
+fn duplicate_key_location(text: &str, message: &str) -> Option<(usize, usize)> {
+    let key = DUPLICATE_KEY_RE.captures(message)?.get(1)?.as_str();
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| declares_key(line, key))
+        .nth(1)
+        .map(|(index, line)| {
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            (index + 1, indent + 1)
+        })
 }
 
 #[cfg(test)]
@@ -138,15 +497,29 @@ mod tests {
 
         assert_eq!(metadata.title, Some("Test".to_string()));
         assert_eq!(
-            metadata.created,
+            metadata.created.map(|d| d.utc()),
             Some(Utc.with_ymd_and_hms(2023, 1, 15, 10, 0, 0).unwrap())
         );
         assert_eq!(
-            metadata.modified,
+            metadata.modified.map(|d| d.utc()),
             Some(Utc.with_ymd_and_hms(2023, 1, 16, 15, 30, 0).unwrap())
         );
     }
 
+    #[test]
+    fn test_parse_frontmatter_with_lenient_dates() {
+        let content =
+            "---\ndate: 2025-06-14\ncreated: 2025-06-14 10:00\nmodified: 1749895200\n---\nContent";
+        let (metadata, _) = parse_frontmatter(content).unwrap();
+
+        assert_eq!(metadata.date.unwrap().display(), "2025-06-14");
+        assert_eq!(metadata.created.unwrap().display(), "2025-06-14 10:00");
+        assert_eq!(
+            metadata.modified.map(|d| d.utc()),
+            Some(Utc.with_ymd_and_hms(2025, 6, 14, 10, 0, 0).unwrap())
+        );
+    }
+
     #[test]
     fn test_parse_frontmatter_with_custom_fields() {
         let content = "---\ntitle: Test\ncustom_field: value\nnested:\n  field: data\n---\nContent";
@@ -185,9 +558,108 @@ mod tests {
     #[test]
     fn test_extract_frontmatter() {
         let content = "---\ntitle: Test\n---\nContent";
-        let yaml = extract_frontmatter(content).unwrap();
+        let (format, yaml) = extract_frontmatter(content).unwrap();
 
+        assert_eq!(format, FrontmatterFormat::Yaml);
         assert_eq!(yaml, "title: Test");
+
+        let (format, toml) = extract_frontmatter("+++\ntitle = \"Test\"\n+++\nContent").unwrap();
+        assert_eq!(format, FrontmatterFormat::Toml);
+        assert_eq!(toml, "title = \"Test\"");
+
+        let (format, json) = extract_frontmatter("{\n  \"title\": \"Test\"\n}\nContent").unwrap();
+        assert_eq!(format, FrontmatterFormat::Json);
+        assert_eq!(json, "{\n  \"title\": \"Test\"\n}");
+
+        assert!(extract_frontmatter("{{< figure >}}\nContent").is_none());
+    }
+
+    #[test]
+    fn test_parse_toml_frontmatter() {
+        let content = "+++\ntitle = \"Hugo Post\"\ntags = [\"rust\", \"hugo\"]\ndate = 2023-01-15T10:00:00Z\n\n[extra]\nweight = 3\n+++\n# Content";
+        let (metadata, body) = parse_frontmatter(content).unwrap();
+
+        assert_eq!(metadata.title, Some("Hugo Post".to_string()));
+        assert_eq!(metadata.tags, vec!["rust".to_string(), "hugo".to_string()]);
+        assert_eq!(
+            metadata.date.map(|d| d.utc()),
+            Some(Utc.with_ymd_and_hms(2023, 1, 15, 10, 0, 0).unwrap())
+        );
+        assert!(metadata.custom.contains_key("extra"));
+        assert_eq!(body, "# Content");
+    }
+
+    #[test]
+    fn test_parse_json_frontmatter() {
+        let content = "{\n  \"title\": \"Generated\",\n  \"tags\": [\"json\"],\n  \"status\": \"draft\"\n}\n# Content";
+        let (metadata, body) = parse_frontmatter(content).unwrap();
+
+        assert_eq!(metadata.title, Some("Generated".to_string()));
+        assert_eq!(metadata.tags, vec!["json".to_string()]);
+        assert_eq!(metadata.status, Some("draft".to_string()));
+        assert_eq!(body, "# Content");
+    }
+
+    #[test]
+    fn test_toml_and_json_frontmatter_errors() {
+        let err = parse_frontmatter("+++\ntitle = \"Test\"\ntags = [\n+++\nContent")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("TOML parsing failed"), "{}", err);
+
+        let err = parse_frontmatter("+++\ntitle = \"A\"\ntitle = \"B\"\n+++\nContent")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Duplicate keys"), "{}", err);
+
+        let err = parse_frontmatter("+++\ntitle = \"Test\"\nNo closing delimiter")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no closing delimiter"), "{}", err);
+
+        let err = parse_frontmatter("{\n  \"title\": \"Test\",\n  \"tags\" [],\n}\nContent")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("line 3, column 10: JSON parsing failed"),
+            "{}",
+            err
+        );
+
+        let err = parse_frontmatter("{\n  \"title\": \"Test\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no closing delimiter"), "{}", err);
+    }
+
+    #[test]
+    fn test_frontmatter_error_locations() {
+        let content = "---\ntitle: A\ntags: [x]\ntitle: B\n---\nBody";
+        let error = try_parse_frontmatter(content).unwrap_err();
+        assert!(error.message.contains("Duplicate keys"), "{}", error);
+        assert_eq!((error.line, error.column), (Some(4), Some(1)));
+        assert_eq!(error.source_line.as_deref(), Some("title: B"));
+        assert_eq!(error.body, "Body");
+
+        let content = "---\ntitle: Test\ntags:\n  - ok\n\t- tab\n---\nBody";
+        let error = try_parse_frontmatter(content).unwrap_err();
+        assert!(error.message.contains("Tab characters"), "{}", error);
+        assert_eq!((error.line, error.column), (Some(5), Some(1)));
+
+        let content = "+++\ntitle = \"A\"\n\ntitle = \"B\"\n+++\nBody";
+        let error = try_parse_frontmatter(content).unwrap_err();
+        assert_eq!(error.line, Some(4));
+
+        let content = "---\ntitle: Test\nbad: [\n---\nBody";
+        let error = try_parse_frontmatter(content).unwrap_err();
+        assert!(error.line.unwrap() >= 3, "{}", error);
+
+        let error = try_parse_frontmatter("---\ntitle: Test\nBody").unwrap_err();
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.body, "---\ntitle: Test\nBody");
+
+        let err = parse_frontmatter("---\ntitle: A\ntitle: B\n---\n").unwrap_err();
+        assert!(err.to_string().ends_with("3 | title: B\n  | ^"), "{}", err);
     }
 
     #[test]
@@ -0,0 +1,477 @@
+//! ABOUTME: Glossary built from glossary.md definition lists and frontmatter terms
+//! ABOUTME: Links the first occurrence of each term in other documents to its definition
+
+use super::document::Document;
+use super::markdown::{extract_summary, generate_heading_id};
+use super::wiki_links::calculate_relative_path;
+use crate::config::UnicodeMatching;
+use crate::utils::html_escape;
+use lazy_static::lazy_static;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd, TextMergeStream};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Document whose definition lists make up the glossary
+pub const GLOSSARY_FILE_NAME: &str = "glossary.md";
+
+/// Output path of the generated glossary page
+pub const GLOSSARY_PAGE_PATH: &str = "glossary/index.html";
+
+lazy_static! {
+    /// Wiki links in unprocessed markdown, which count as existing links
+    static ref WIKI_LINK_RE: Regex = Regex::new(r"!?\[\[[^\]]*\]\]").unwrap();
+}
+
+/// A defined term
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlossaryTerm {
+    /// The term as written in its definition
+    pub term: String,
+
+    /// Definition as plain text, used for tooltips
+    pub definition: String,
+
+    /// Definition as HTML, used on the glossary page
+    pub definition_html: String,
+
+    /// Source file of the defining document
+    pub source_path: PathBuf,
+
+    /// Output path of the defining document
+    pub output_path: PathBuf,
+
+    /// Title of the defining document
+    pub source_title: String,
+
+    /// Whether the term comes from a `term` frontmatter key, so links go to
+    /// the defining note rather than the glossary page
+    pub from_frontmatter: bool,
+
+    /// Unicode matching used for the term's anchor, like heading IDs
+    matching: UnicodeMatching,
+}
+
+impl GlossaryTerm {
+    /// Anchor of the term on the glossary page
+    pub fn anchor(&self) -> String {
+        format!("term-{}", generate_heading_id(&self.term, self.matching))
+    }
+
+    /// Page and anchor that occurrences of the term link to
+    fn target(&self) -> (PathBuf, Option<String>) {
+        if self.from_frontmatter {
+            (self.output_path.clone(), None)
+        } else {
+            (PathBuf::from(GLOSSARY_PAGE_PATH), Some(self.anchor()))
+        }
+    }
+}
+
+/// All defined terms, with a matcher for finding them in text
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    terms: Vec<GlossaryTerm>,
+    /// Term index by lowercased term
+    index: HashMap<String, usize>,
+    /// Matches any term as a whole word, longest first
+    pattern: Option<Regex>,
+}
+
+impl Glossary {
+    /// Build a glossary from terms; later duplicates of a term are ignored
+    pub fn new(terms: Vec<GlossaryTerm>) -> Self {
+        let mut glossary = Self::default();
+        for term in terms {
+            let key = term.term.to_lowercase();
+            if term.term.trim().is_empty() || glossary.index.contains_key(&key) {
+                continue;
+            }
+            glossary.index.insert(key, glossary.terms.len());
+            glossary.terms.push(term);
+        }
+        glossary.terms.sort_by_key(|term| term.term.to_lowercase());
+        glossary.index = glossary
+            .terms
+            .iter()
+            .enumerate()
+            .map(|(i, term)| (term.term.to_lowercase(), i))
+            .collect();
+
+        let mut alternatives: Vec<&str> = glossary.terms.iter().map(|t| t.term.as_str()).collect();
+        alternatives.sort_by_key(|term| std::cmp::Reverse(term.len()));
+        if !alternatives.is_empty() {
+            let alternatives: Vec<String> = alternatives.into_iter().map(regex::escape).collect();
+            glossary.pattern = Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|"))).ok();
+        }
+        glossary
+    }
+
+    /// Collect terms from `glossary.md` definition lists and `term`
+    /// frontmatter keys
+    ///
+    /// A note with `term: Idempotency` (or a list of terms) defines them with
+    /// its description, or the start of its content. Anchors are generated
+    /// with `matching`, the mode used for heading IDs.
+    pub fn from_documents(documents: &[Document], matching: UnicodeMatching) -> Self {
+        let mut terms = Vec::new();
+
+        for doc in documents {
+            let defining =
+                |term: String, definition: String, definition_html: String| GlossaryTerm {
+                    term,
+                    definition,
+                    definition_html,
+                    source_path: doc.source_path.clone(),
+                    output_path: doc.output_path.clone(),
+                    source_title: doc.title().to_string(),
+                    from_frontmatter: false,
+                    matching,
+                };
+
+            if doc.relative_path == Path::new(GLOSSARY_FILE_NAME) {
+                for (term, definition, definition_html) in parse_definition_list(&doc.raw_content) {
+                    terms.push(defining(term, definition, definition_html));
+                }
+            }
+
+            let frontmatter_terms: Vec<String> = match doc.metadata.custom.get("term") {
+                Some(serde_yaml::Value::String(term)) => vec![term.clone()],
+                Some(serde_yaml::Value::Sequence(items)) => items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(String::from))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if frontmatter_terms.is_empty() {
+                continue;
+            }
+            let definition = doc
+                .metadata
+                .description
+                .clone()
+                .unwrap_or_else(|| extract_summary(&doc.raw_content, 200));
+            for term in frontmatter_terms {
+                terms.push(GlossaryTerm {
+                    from_frontmatter: true,
+                    ..defining(term, definition.clone(), html_escape(&definition))
+                });
+            }
+        }
+
+        Self::new(terms)
+    }
+
+    /// Terms sorted alphabetically
+    pub fn terms(&self) -> &[GlossaryTerm] {
+        &self.terms
+    }
+
+    /// Look up a term case-insensitively
+    pub fn get(&self, term: &str) -> Option<&GlossaryTerm> {
+        self.index
+            .get(&term.to_lowercase())
+            .map(|&i| &self.terms[i])
+    }
+
+    /// Whether the glossary has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Find first occurrences of terms in a run of text as (start, end,
+    /// term index) byte ranges
+    ///
+    /// Terms already in `linked`, terms defined by `current_doc_path`, and
+    /// text inside `[[wiki links]]` are skipped; found terms are added to
+    /// `linked`.
+    fn find_terms(
+        &self,
+        text: &str,
+        current_doc_path: &Path,
+        linked: &mut Vec<usize>,
+    ) -> Vec<(usize, usize, usize)> {
+        let Some(pattern) = &self.pattern else {
+            return Vec::new();
+        };
+        let wiki_links: Vec<(usize, usize)> = WIKI_LINK_RE
+            .find_iter(text)
+            .map(|m| (m.start(), m.end()))
+            .collect();
+
+        let mut found = Vec::new();
+        for m in pattern.find_iter(text) {
+            let Some(&index) = self.index.get(&m.as_str().to_lowercase()) else {
+                continue;
+            };
+            if linked.contains(&index)
+                || self.terms[index].output_path == current_doc_path
+                || wiki_links
+                    .iter()
+                    .any(|&(start, end)| m.start() < end && start < m.end())
+            {
+                continue;
+            }
+            linked.push(index);
+            found.push((m.start(), m.end(), index));
+        }
+        found
+    }
+}
+
+/// Parse `Term` / `: Definition` lists into (term, plain text, HTML) triples
+fn parse_definition_list(markdown: &str) -> Vec<(String, String, String)> {
+    let mut entries = Vec::new();
+    let mut titles: Vec<String> = Vec::new();
+    let mut title: Option<String> = None;
+    let mut definition: Option<(Vec<Event>, String)> = None;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_DEFINITION_LIST) {
+        match event {
+            Event::Start(Tag::DefinitionListTitle) => title = Some(String::new()),
+            Event::End(TagEnd::DefinitionListTitle) => titles.extend(title.take()),
+            Event::Start(Tag::DefinitionListDefinition) => {
+                definition = Some((Vec::new(), String::new()))
+            }
+            Event::End(TagEnd::DefinitionListDefinition) => {
+                if let Some((events, text)) = definition.take() {
+                    let mut definition_html = String::new();
+                    html::push_html(&mut definition_html, events.into_iter());
+                    for term in titles.drain(..) {
+                        entries.push((term, text.trim().to_string(), definition_html.clone()));
+                    }
+                }
+            }
+            Event::End(TagEnd::DefinitionList) => titles.clear(),
+            event => {
+                if let Some(title) = &mut title {
+                    match &event {
+                        Event::Text(text) | Event::Code(text) => title.push_str(text),
+                        // Several terms on consecutive lines share a definition
+                        Event::SoftBreak | Event::HardBreak => titles.push(std::mem::take(title)),
+                        _ => {}
+                    }
+                } else if let Some((events, text)) = &mut definition {
+                    match &event {
+                        Event::Text(t) | Event::Code(t) => text.push_str(t),
+                        Event::SoftBreak | Event::HardBreak => text.push(' '),
+                        _ => {}
+                    }
+                    events.push(event);
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+/// Tracks whether the current event is somewhere terms should not be linked
+#[derive(Default)]
+struct Context {
+    code_blocks: usize,
+    headings: usize,
+    links: usize,
+    html_links: usize,
+    metadata_blocks: usize,
+}
+
+impl Context {
+    fn update(&mut self, event: &Event<'_>) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => self.code_blocks += 1,
+            Event::End(TagEnd::CodeBlock) => self.code_blocks -= 1,
+            Event::Start(Tag::Heading { .. } | Tag::DefinitionListTitle) => self.headings += 1,
+            Event::End(TagEnd::Heading(_) | TagEnd::DefinitionListTitle) => self.headings -= 1,
+            Event::Start(Tag::Link { .. } | Tag::Image { .. }) => self.links += 1,
+            Event::End(TagEnd::Link | TagEnd::Image) => self.links -= 1,
+            Event::Start(Tag::MetadataBlock(_)) => self.metadata_blocks += 1,
+            Event::End(TagEnd::MetadataBlock(_)) => self.metadata_blocks -= 1,
+            // Resolved wiki links are inline `<a>` HTML around their text
+            Event::InlineHtml(html) => {
+                let html = html.trim_start();
+                if html.starts_with("<a ") && !html.contains("</a>") {
+                    self.html_links += 1;
+                } else if html.starts_with("</a>") {
+                    self.html_links = self.html_links.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn allows_terms(&self) -> bool {
+        self.code_blocks == 0
+            && self.headings == 0
+            && self.links == 0
+            && self.html_links == 0
+            && self.metadata_blocks == 0
+    }
+}
+
+/// Link the first occurrence of each glossary term to its definition
+///
+/// Links are relative to `current_doc_path` and carry the definition as a
+/// tooltip. Headings, code and existing links are skipped, as are terms the
+/// current document defines.
+pub fn render_glossary_terms<'a>(
+    events: Vec<Event<'a>>,
+    glossary: &Glossary,
+    current_doc_path: &Path,
+) -> Vec<Event<'a>> {
+    if glossary.is_empty() {
+        return events;
+    }
+    let mut output = Vec::with_capacity(events.len());
+    let mut context = Context::default();
+    let mut linked = Vec::new();
+
+    for event in events {
+        context.update(&event);
+        let Event::Text(text) = &event else {
+            output.push(event);
+            continue;
+        };
+        if !context.allows_terms() {
+            output.push(event);
+            continue;
+        }
+        let found = glossary.find_terms(text, current_doc_path, &mut linked);
+        if found.is_empty() {
+            output.push(event);
+            continue;
+        }
+
+        let mut last = 0;
+        for (start, end, index) in found {
+            let term = &glossary.terms[index];
+            let (target, anchor) = term.target();
+            let href = calculate_relative_path(current_doc_path, &target)
+                .map(|path| match &anchor {
+                    Some(anchor) => format!("{}#{}", path, anchor),
+                    None => path,
+                })
+                .unwrap_or_default();
+            output.push(Event::Text(CowStr::from(text[last..start].to_string())));
+            output.push(Event::InlineHtml(CowStr::from(format!(
+                r#"<a href="{}" class="glossary-term" title="{}">{}</a>"#,
+                html_escape(&href),
+                html_escape(&term.definition),
+                html_escape(&text[start..end])
+            ))));
+            last = end;
+        }
+        output.push(Event::Text(CowStr::from(text[last..].to_string())));
+    }
+
+    output.retain(|event| !matches!(event, Event::Text(text) if text.is_empty()));
+    output
+}
+
+/// Terms that `render_glossary_terms` links in a document's markdown
+pub fn glossary_terms_used<'g>(
+    markdown: &str,
+    glossary: &'g Glossary,
+    current_doc_path: &Path,
+) -> Vec<&'g GlossaryTerm> {
+    let mut context = Context::default();
+    let mut linked = Vec::new();
+
+    for event in TextMergeStream::new(Parser::new_ext(markdown, Options::ENABLE_DEFINITION_LIST)) {
+        context.update(&event);
+        if let Event::Text(text) = &event {
+            if context.allows_terms() {
+                glossary.find_terms(text, current_doc_path, &mut linked);
+            }
+        }
+    }
+
+    linked.into_iter().map(|i| &glossary.terms[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLOSSARY: &str = "# Glossary\n\nIdempotency\n: Applying an operation twice has the same effect as once.\n\nPod\nPods\n: The smallest *deployable* unit in Kubernetes.\n";
+
+    fn glossary() -> Glossary {
+        let mut doc = Document::new(
+            PathBuf::from("/in/glossary.md"),
+            PathBuf::from(GLOSSARY_FILE_NAME),
+            "root".to_string(),
+        );
+        doc.raw_content = GLOSSARY.to_string();
+
+        let mut note = Document::new(
+            PathBuf::from("/in/resources/k8s.md"),
+            PathBuf::from("resources/k8s.md"),
+            "resources".to_string(),
+        );
+        note.metadata.custom.insert(
+            "term".to_string(),
+            serde_yaml::Value::String("Kubernetes".to_string()),
+        );
+        note.metadata.description = Some("Container orchestration".to_string());
+
+        Glossary::from_documents(&[doc, note], UnicodeMatching::default())
+    }
+
+    fn render(markdown: &str, path: &str) -> String {
+        let options = Options::ENABLE_DEFINITION_LIST;
+        let events: Vec<Event> = TextMergeStream::new(Parser::new_ext(markdown, options)).collect();
+        let mut output = String::new();
+        html::push_html(
+            &mut output,
+            render_glossary_terms(events, &glossary(), Path::new(path)).into_iter(),
+        );
+        output
+    }
+
+    #[test]
+    fn test_glossary_from_documents() {
+        let glossary = glossary();
+        let terms: Vec<&str> = glossary.terms().iter().map(|t| t.term.as_str()).collect();
+
+        assert_eq!(terms, vec!["Idempotency", "Kubernetes", "Pod", "Pods"]);
+        let pod = glossary.get("pod").unwrap();
+        assert_eq!(
+            pod.definition,
+            "The smallest deployable unit in Kubernetes."
+        );
+        assert!(pod.definition_html.contains("<em>deployable</em>"));
+        assert_eq!(pod.anchor(), "term-pod");
+        assert_eq!(
+            glossary.get("kubernetes").unwrap().definition,
+            "Container orchestration"
+        );
+    }
+
+    #[test]
+    fn test_link_first_occurrence() {
+        let html = render(
+            "## Pods\n\nA pod runs on Kubernetes. Another pod, and `Pod` in code.\n\nSee [Pods](x.html) and <a href=\"y.html\" class=\"wiki-link\">Idempotency</a>; idempotency matters.\n",
+            "projects/notes.html",
+        );
+
+        assert!(html.contains("<h2>Pods</h2>"));
+        assert!(html.contains(
+            r#"A <a href="../glossary/index.html#term-pod" class="glossary-term" title="The smallest deployable unit in Kubernetes.">pod</a> runs on <a href="../resources/k8s.html" class="glossary-term" title="Container orchestration">Kubernetes</a>. Another pod"#
+        ));
+        assert!(html.contains(r#"<a href="x.html">Pods</a>"#));
+        assert!(html.contains(r#"class="glossary-term" title="Applying an operation twice has the same effect as once.">idempotency</a> matters"#));
+    }
+
+    #[test]
+    fn test_defining_document_is_not_linked() {
+        let glossary = glossary();
+        let used = glossary_terms_used(
+            "Kubernetes schedules a pod; see [[Idempotency]].",
+            &glossary,
+            Path::new("resources/k8s.html"),
+        );
+
+        assert_eq!(used.len(), 1);
+        assert_eq!(used[0].term, "Pod");
+    }
+}
@@ -0,0 +1,294 @@
+//! ABOUTME: Inline #hashtag extraction and rendering
+//! ABOUTME: Finds tags in body text and turns them into links to tag pages
+
+use super::tags::TAGS_DIR;
+use crate::config::InlineTagMode;
+use crate::utils::html_escape;
+use lazy_static::lazy_static;
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd, TextMergeStream};
+use regex::Regex;
+use std::path::Path;
+
+lazy_static! {
+    /// A `#tag` at the start of text or after whitespace or opening punctuation.
+    /// Tags may nest with `/` (`#area/health`). `[` is not a lead so that
+    /// `[[#Heading]]` links are not tags.
+    static ref HASHTAG_RE: Regex =
+        Regex::new(r"(^|[\s({,;])#([\p{L}\p{N}_][\p{L}\p{N}_/-]*)").unwrap();
+
+    /// A 3-, 6- or 8-digit hex colour such as `b4b4b4`
+    static ref HEX_COLOUR_RE: Regex =
+        Regex::new(r"^(?:[0-9a-fA-F]{3}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$").unwrap();
+}
+
+/// Convert a tag to its URL slug, keeping `/` for nested tags
+///
+/// `Area/Mental Health` becomes `area/mental-health`.
+pub fn tag_slug(tag: &str) -> String {
+    tag.split('/')
+        .map(|segment| {
+            segment
+                .to_lowercase()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Find hashtags in a run of text as (start, end, tag) byte ranges
+///
+/// The range covers the `#` and the tag. Purely numeric tags (`#123`) are
+/// ignored, since they are usually issue references, as are hex colours
+/// (`#f0f0f0`).
+fn find_hashtags(text: &str) -> Vec<(usize, usize, &str)> {
+    HASHTAG_RE
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let name = captures.get(2)?;
+            let tag = name.as_str().trim_end_matches(['/', '-']);
+            if tag.chars().all(|c| c.is_ascii_digit()) || HEX_COLOUR_RE.is_match(tag) {
+                return None;
+            }
+            let start = name.start() - 1;
+            Some((start, name.start() + tag.len(), tag))
+        })
+        .collect()
+}
+
+/// Tracks whether the current event is somewhere hashtags should be ignored
+#[derive(Default)]
+struct Context {
+    code_blocks: usize,
+    headings: usize,
+    links: usize,
+    html_links: usize,
+    metadata_blocks: usize,
+}
+
+impl Context {
+    fn update(&mut self, event: &Event<'_>) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => self.code_blocks += 1,
+            Event::End(TagEnd::CodeBlock) => self.code_blocks -= 1,
+            Event::Start(Tag::Heading { .. }) => self.headings += 1,
+            Event::End(TagEnd::Heading(_)) => self.headings -= 1,
+            Event::Start(Tag::Link { .. } | Tag::Image { .. }) => self.links += 1,
+            Event::End(TagEnd::Link | TagEnd::Image) => self.links -= 1,
+            Event::Start(Tag::MetadataBlock(_)) => self.metadata_blocks += 1,
+            Event::End(TagEnd::MetadataBlock(_)) => self.metadata_blocks -= 1,
+            // Resolved wiki links are inline `<a>` HTML around their text
+            Event::InlineHtml(html) => {
+                let html = html.trim_start();
+                if html.starts_with("<a ") && !html.contains("</a>") {
+                    self.html_links += 1;
+                } else if html.starts_with("</a>") {
+                    self.html_links = self.html_links.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn allows_tags(&self) -> bool {
+        self.code_blocks == 0
+            && self.headings == 0
+            && self.links == 0
+            && self.html_links == 0
+            && self.metadata_blocks == 0
+    }
+}
+
+/// Extract inline hashtags from markdown body text
+///
+/// Code, link text and URLs, and headings are skipped. Tags are returned in
+/// order of first appearance, de-duplicated by [`tag_slug`].
+pub fn extract_hashtags(markdown: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut context = Context::default();
+
+    for event in TextMergeStream::new(Parser::new_ext(markdown, Options::empty())) {
+        context.update(&event);
+        if let Event::Text(text) = &event {
+            if !context.allows_tags() {
+                continue;
+            }
+            for (_, _, tag) in find_hashtags(text) {
+                if !tags.iter().any(|t| tag_slug(t) == tag_slug(tag)) {
+                    tags.push(tag.to_string());
+                }
+            }
+        }
+    }
+
+    tags
+}
+
+/// Render hashtags in text events as links to tag pages, or strip them
+///
+/// Links are relative to `current_doc_path` (e.g. `../tags/rust/` from
+/// `projects/note.html`). Paragraphs left empty by stripping are dropped.
+pub fn render_hashtags<'a>(
+    events: Vec<Event<'a>>,
+    mode: InlineTagMode,
+    current_doc_path: &Path,
+) -> Vec<Event<'a>> {
+    let depth = current_doc_path
+        .parent()
+        .map(|parent| parent.components().count())
+        .unwrap_or(0);
+    let tags_root = format!("{}{}/", "../".repeat(depth), TAGS_DIR);
+
+    let mut output = Vec::with_capacity(events.len());
+    let mut context = Context::default();
+
+    for event in events {
+        context.update(&event);
+        let Event::Text(text) = &event else {
+            output.push(event);
+            continue;
+        };
+        let hashtags = find_hashtags(text);
+        if hashtags.is_empty() || !context.allows_tags() {
+            output.push(event);
+            continue;
+        }
+
+        let mut last = 0;
+        for (start, end, tag) in hashtags {
+            match mode {
+                InlineTagMode::Link => {
+                    output.push(Event::Text(CowStr::from(text[last..start].to_string())));
+                    output.push(Event::InlineHtml(CowStr::from(format!(
+                        r#"<a href="{}{}/" class="hashtag">#{}</a>"#,
+                        tags_root,
+                        tag_slug(tag),
+                        html_escape(tag)
+                    ))));
+                }
+                InlineTagMode::Strip => {
+                    output.push(Event::Text(CowStr::from(
+                        text[last..start].trim_end().to_string(),
+                    )));
+                }
+            }
+            last = end;
+        }
+        output.push(Event::Text(CowStr::from(text[last..].to_string())));
+    }
+
+    if mode == InlineTagMode::Strip {
+        remove_empty_paragraphs(&mut output);
+    }
+    output.retain(|event| !matches!(event, Event::Text(text) if text.is_empty()));
+    output
+}
+
+/// Remove paragraphs that contain only whitespace text
+fn remove_empty_paragraphs(events: &mut Vec<Event<'_>>) {
+    let mut i = 0;
+    while i < events.len() {
+        if matches!(events[i], Event::Start(Tag::Paragraph)) {
+            let end = events[i + 1..]
+                .iter()
+                .position(|event| !matches!(event, Event::Text(text) if text.trim().is_empty()))
+                .map(|offset| i + 1 + offset);
+            if let Some(end) = end {
+                if matches!(events[end], Event::End(TagEnd::Paragraph)) {
+                    events.drain(i..=end);
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::html;
+
+    fn render(markdown: &str, mode: InlineTagMode, path: &str) -> String {
+        let events: Vec<Event> =
+            TextMergeStream::new(Parser::new_ext(markdown, Options::empty())).collect();
+        let mut output = String::new();
+        html::push_html(
+            &mut output,
+            render_hashtags(events, mode, Path::new(path)).into_iter(),
+        );
+        output
+    }
+
+    #[test]
+    fn test_tag_slug() {
+        assert_eq!(tag_slug("kubernetes"), "kubernetes");
+        assert_eq!(tag_slug("Area/Mental Health"), "area/mental-health");
+        assert_eq!(tag_slug("C++"), "c");
+    }
+
+    #[test]
+    fn test_extract_hashtags() {
+        let markdown = "# Heading #notatag\n\nDeploying #kubernetes with #area/health and #Kubernetes.\n\n`#code` and [#link](https://example.com/#anchor) and issue #123\n\n```\n#comment\n```\n";
+
+        assert_eq!(
+            extract_hashtags(markdown),
+            vec!["kubernetes", "area/health"]
+        );
+    }
+
+    #[test]
+    fn test_hashtags_need_a_boundary() {
+        assert!(extract_hashtags("see https://example.com/page#section").is_empty());
+        assert!(extract_hashtags("C#sharp and a#b").is_empty());
+        assert_eq!(extract_hashtags("(#tag)"), vec!["tag"]);
+        assert!(extract_hashtags("See [[#Heading]] and [[Note#Section]]").is_empty());
+    }
+
+    #[test]
+    fn test_hex_colours_are_not_tags() {
+        let markdown =
+            "Borders (#b4b4b4), accent #0ea5e9, short #fff, alpha #2a2a2aff and #f0f0f0.";
+        assert!(extract_hashtags(markdown).is_empty());
+        assert_eq!(
+            extract_hashtags("#cafe #beef42x #design"),
+            vec!["cafe", "beef42x", "design"]
+        );
+    }
+
+    #[test]
+    fn test_render_hashtags_as_links() {
+        let html = render(
+            "Notes on #rust/async.",
+            InlineTagMode::Link,
+            "resources/note.html",
+        );
+
+        assert!(html.contains(
+            r##"Notes on <a href="../tags/rust/async/" class="hashtag">#rust/async</a>."##
+        ));
+
+        // Resolved wiki links are already links
+        let html = render(
+            r#"See <a href="x.html" class="wiki-link">the #rust notes</a> and #go"#,
+            InlineTagMode::Link,
+            "note.html",
+        );
+        assert!(html.contains(r#"<a href="x.html" class="wiki-link">the #rust notes</a>"#));
+        assert!(html.contains(r##"<a href="tags/go/" class="hashtag">#go</a>"##));
+    }
+
+    #[test]
+    fn test_strip_hashtags() {
+        let html = render(
+            "Notes on pods #kubernetes\n\n#todo #later\n",
+            InlineTagMode::Strip,
+            "note.html",
+        );
+
+        assert_eq!(html, "<p>Notes on pods</p>\n");
+    }
+}
@@ -0,0 +1,500 @@
+//! ABOUTME: Source file includes for fenced code blocks
+//! ABOUTME: Resolves ```lang include="path" lines="10-40" fences at build time
+
+use super::diagnostics::Diagnostic;
+use super::spans::LineMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    /// Regex pattern for key="value" attributes in a fence info string
+    static ref FENCE_ATTR_RE: Regex = Regex::new(r#"(\w+)="([^"]*)""#).unwrap();
+}
+
+/// Attributes consumed by the include directive
+const INCLUDE_ATTRS: &[&str] = &["include", "lines", "region"];
+
+/// Result of resolving code block includes in a document
+#[derive(Debug, Default)]
+pub struct IncludeResult {
+    /// Markdown content with included files substituted into their code blocks
+    pub content: String,
+
+    /// Files that were successfully included
+    pub dependencies: Vec<PathBuf>,
+
+    /// Problems with missing files, ranges or regions
+    pub diagnostics: Vec<Diagnostic>,
+
+    /// File line of each line of `content`
+    pub line_map: LineMap,
+}
+
+/// An opening code fence and its parsed info string
+struct Fence {
+    marker: char,
+    length: usize,
+    attrs: Vec<(String, String)>,
+    info: String,
+}
+
+/// Resolve `include` directives on fenced code blocks
+///
+/// Paths are resolved relative to `note_dir`, or relative to `root` when they
+/// start with `/`, and must stay inside `root` or `input_dir`. A block can select a line range (`lines="10-40"`) or a region
+/// delimited by `ANCHOR: name` and `ANCHOR_END: name` markers (`region="name"`).
+/// If an include fails, the block keeps its original body and a diagnostic is
+/// recorded; `line_offset` is added to reported line numbers and to the
+/// lines of the returned line map.
+pub fn resolve_includes(
+    content: &str,
+    note_dir: &Path,
+    root: &Path,
+    input_dir: &Path,
+    line_offset: usize,
+) -> IncludeResult {
+    let mut result = IncludeResult::default();
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(fence) = parse_opening_fence(lines[i]) else {
+            result.content.push_str(lines[i]);
+            result.line_map.push(line_offset + i + 1);
+            i += 1;
+            continue;
+        };
+
+        // Find the closing fence (or the end of the document)
+        let body_start = i + 1;
+        let mut body_end = body_start;
+        while body_end < lines.len() && !is_closing_fence(lines[body_end], &fence) {
+            body_end += 1;
+        }
+        let block_end = (body_end + 1).min(lines.len());
+
+        let Some(include_path) = fence_attr(&fence, "include") else {
+            for (j, line) in lines.iter().enumerate().take(block_end).skip(i) {
+                result.content.push_str(line);
+                result.line_map.push(line_offset + j + 1);
+            }
+            i = block_end;
+            continue;
+        };
+
+        let fence_line = line_offset + i + 1;
+        let body = match read_include(include_path, &fence, note_dir, root, input_dir) {
+            Ok((path, text)) => {
+                if !result.dependencies.contains(&path) {
+                    result.dependencies.push(path);
+                }
+                text
+            }
+            Err(message) => {
+                let column = lines[i].len() - lines[i].trim_start_matches(' ').len() + 1;
+                result
+                    .diagnostics
+                    .push(Diagnostic::at_line(fence_line, message).with_column(column));
+                lines[body_start..body_end].concat()
+            }
+        };
+
+        // Every line of the substituted block maps to the fence
+        let block_start = result.content.len();
+        push_code_block(&mut result.content, &fence, &body);
+        for _ in result.content[block_start..].lines() {
+            result.line_map.push(fence_line);
+        }
+        i = block_end;
+    }
+
+    result
+}
+
+/// Parse a line as an opening code fence
+fn parse_opening_fence(line: &str) -> Option<Fence> {
+    let trimmed = line.trim_end_matches(['\n', '\r']);
+    let indent = trimmed.len() - trimmed.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+
+    let rest = &trimmed[indent..];
+    let marker = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = rest.chars().take_while(|c| *c == marker).count();
+    if length < 3 {
+        return None;
+    }
+
+    let info = rest[length..].trim();
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+
+    let attrs = FENCE_ATTR_RE
+        .captures_iter(info)
+        .map(|cap| (cap[1].to_string(), cap[2].to_string()))
+        .collect();
+
+    Some(Fence {
+        marker,
+        length,
+        attrs,
+        info: info.to_string(),
+    })
+}
+
+/// Check whether a line closes the given fence
+fn is_closing_fence(line: &str, fence: &Fence) -> bool {
+    let trimmed = line.trim();
+    let length = trimmed.chars().take_while(|c| *c == fence.marker).count();
+    length >= fence.length && length == trimmed.chars().count()
+}
+
+/// Look up an attribute on a fence
+fn fence_attr<'a>(fence: &'a Fence, name: &str) -> Option<&'a str> {
+    fence
+        .attrs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Read the included file and select the requested lines or region
+fn read_include(
+    include_path: &str,
+    fence: &Fence,
+    note_dir: &Path,
+    root: &Path,
+    input_dir: &Path,
+) -> std::result::Result<(PathBuf, String), String> {
+    let path = match include_path.strip_prefix('/') {
+        Some(rooted) => root.join(rooted),
+        None => note_dir.join(include_path),
+    };
+
+    // Resolve `..` and symlinks before checking the file stays inside the site
+    let path = fs::canonicalize(&path)
+        .map_err(|e| format!("Included file '{}' could not be read: {}", include_path, e))?;
+    let inside = |dir: &Path| {
+        fs::canonicalize(dir)
+            .map(|dir| path.starts_with(dir))
+            .unwrap_or(false)
+    };
+    if !inside(root) && !inside(input_dir) {
+        return Err(format!(
+            "Included file '{}' is outside the include root and input directory",
+            include_path
+        ));
+    }
+
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("Included file '{}' could not be read: {}", include_path, e))?;
+
+    let selected = if let Some(region) = fence_attr(fence, "region") {
+        select_region(&text, region)
+            .ok_or_else(|| format!("Region '{}' not found in '{}'", region, include_path))?
+    } else if let Some(range) = fence_attr(fence, "lines") {
+        select_lines(&text, range).map_err(|e| format!("{} in '{}'", e, include_path))?
+    } else {
+        text
+    };
+
+    Ok((path, selected))
+}
+
+/// Select an inclusive, 1-based line range such as `10-40`, `10-`, `-40` or `10`
+fn select_lines(text: &str, range: &str) -> std::result::Result<String, String> {
+    let invalid = || format!("Invalid line range '{}'", range);
+    let parse = |s: &str| s.trim().parse::<usize>().map_err(|_| invalid());
+
+    let total = text.lines().count();
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (
+            if start.trim().is_empty() {
+                1
+            } else {
+                parse(start)?
+            },
+            if end.trim().is_empty() {
+                total
+            } else {
+                parse(end)?
+            },
+        ),
+        None => {
+            let line = parse(range)?;
+            (line, line)
+        }
+    };
+
+    if start == 0 || start > end {
+        return Err(invalid());
+    }
+    if end > total {
+        return Err(format!(
+            "Line range {} is out of bounds (file has {} lines)",
+            range, total
+        ));
+    }
+
+    Ok(text
+        .lines()
+        .skip(start - 1)
+        .take(end - start + 1)
+        .map(|line| format!("{}\n", line))
+        .collect())
+}
+
+/// Select the lines between `ANCHOR: name` and `ANCHOR_END: name` markers
+fn select_region(text: &str, name: &str) -> Option<String> {
+    let start_marker = format!("ANCHOR: {}", name);
+    let end_marker = format!("ANCHOR_END: {}", name);
+
+    let mut lines = text.lines();
+    lines.find(|line| line.trim_end().ends_with(&start_marker))?;
+
+    let mut selected = String::new();
+    for line in lines {
+        if line.trim_end().ends_with(&end_marker) {
+            return Some(selected);
+        }
+        // Drop markers of other regions nested inside this one
+        if line.contains("ANCHOR: ") || line.contains("ANCHOR_END: ") {
+            continue;
+        }
+        selected.push_str(line);
+        selected.push('\n');
+    }
+
+    None
+}
+
+/// Write a code block with the include attributes removed from its info string
+fn push_code_block(output: &mut String, fence: &Fence, body: &str) {
+    let info = FENCE_ATTR_RE
+        .replace_all(&fence.info, |cap: &regex::Captures| {
+            if INCLUDE_ATTRS.contains(&&cap[1]) {
+                String::new()
+            } else {
+                cap[0].to_string()
+            }
+        })
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // The fence must be longer than any run of fence characters in the body
+    let longest_run = body
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            trimmed.chars().take_while(|c| *c == fence.marker).count()
+        })
+        .max()
+        .unwrap_or(0);
+    let marker: String = std::iter::repeat(fence.marker)
+        .take(fence.length.max(longest_run + 1))
+        .collect();
+
+    output.push_str(&marker);
+    output.push_str(&info);
+    output.push('\n');
+    output.push_str(body);
+    if !body.is_empty() && !body.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(&marker);
+    output.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SOURCE: &str =
+        "fn one() {}\n// ANCHOR: two\nfn two() {}\n// ANCHOR_END: two\nfn three() {}\n";
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("notes")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("code")).unwrap();
+        fs::write(temp_dir.path().join("code/lib.rs"), SOURCE).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_include_whole_file_relative_to_note() {
+        let temp_dir = setup();
+        let content = "Intro\n\n```rust include=\"../code/lib.rs\"\n```\n\nAfter\n";
+
+        let result = resolve_includes(
+            content,
+            &temp_dir.path().join("notes"),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.dependencies.len(), 1);
+        assert!(result.content.contains("```rust\nfn one() {}\n"));
+        assert!(result.content.contains("fn three() {}\n```\n"));
+        assert!(result.content.starts_with("Intro\n"));
+        assert!(result.content.ends_with("After\n"));
+
+        // The included lines map to the fence, and later lines to the file
+        let after = result.content.lines().count();
+        assert_eq!(result.line_map.file_line(4), 3);
+        assert_eq!(result.line_map.file_line(after), 6);
+    }
+
+    #[test]
+    fn test_include_line_range_from_root() {
+        let temp_dir = setup();
+        let content = "```rust include=\"/code/lib.rs\" lines=\"3-3\"\n```\n";
+
+        let result = resolve_includes(
+            content,
+            Path::new("/elsewhere"),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.content, "```rust\nfn two() {}\n```\n");
+    }
+
+    #[test]
+    fn test_include_region() {
+        let temp_dir = setup();
+        let content = "```rust include=\"/code/lib.rs\" region=\"two\"\n```\n";
+
+        let result = resolve_includes(
+            content,
+            temp_dir.path(),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.content, "```rust\nfn two() {}\n```\n");
+    }
+
+    #[test]
+    fn test_include_missing_file_reports_line() {
+        let temp_dir = setup();
+        let content = "Text\n\n```rust include=\"missing.rs\"\nplaceholder\n```\n";
+
+        let result = resolve_includes(
+            content,
+            temp_dir.path(),
+            temp_dir.path(),
+            temp_dir.path(),
+            4,
+        );
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].line, Some(7));
+        assert_eq!(result.diagnostics[0].column, Some(1));
+        assert!(result.diagnostics[0].message.contains("missing.rs"));
+        assert!(result.content.contains("```rust\nplaceholder\n```"));
+        assert!(result.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_include_outside_root_is_rejected() {
+        let temp_dir = setup();
+        let site = temp_dir.path().join("notes");
+        let content = "```rust include=\"../code/lib.rs\"\nplaceholder\n```\n";
+
+        let result = resolve_includes(content, &site, &site, &site, 0);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0]
+            .message
+            .contains("outside the include root"));
+        assert!(result.content.contains("```rust\nplaceholder\n```"));
+        assert!(result.dependencies.is_empty());
+
+        // An include root outside the input directory is allowed
+        let result = resolve_includes(content, &site, temp_dir.path(), &site, 0);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_include_out_of_range_lines() {
+        let temp_dir = setup();
+        let content = "```rust include=\"/code/lib.rs\" lines=\"4-40\"\n```\n";
+
+        let result = resolve_includes(
+            content,
+            temp_dir.path(),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].message.contains("out of bounds"));
+    }
+
+    #[test]
+    fn test_include_missing_region() {
+        let temp_dir = setup();
+        let content = "```rust include=\"/code/lib.rs\" region=\"nope\"\n```\n";
+
+        let result = resolve_includes(
+            content,
+            temp_dir.path(),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].message.contains("Region 'nope'"));
+    }
+
+    #[test]
+    fn test_plain_code_blocks_untouched() {
+        let content = "```rust\nlet x = \"include=\\\"a\\\"\";\n```\n~~~\nplain\n~~~\n";
+        let result = resolve_includes(content, Path::new("."), Path::new("."), Path::new("."), 0);
+
+        assert_eq!(result.content, content);
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_fence_longer_than_included_backticks() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("doc.md"), "```\nnested\n```\n").unwrap();
+        let content = "```markdown include=\"doc.md\"\n```\n";
+
+        let result = resolve_includes(
+            content,
+            temp_dir.path(),
+            temp_dir.path(),
+            temp_dir.path(),
+            0,
+        );
+
+        assert_eq!(result.content, "````markdown\n```\nnested\n```\n````\n");
+    }
+
+    #[test]
+    fn test_select_lines() {
+        let text = "a\nb\nc\nd\n";
+        assert_eq!(select_lines(text, "2-3").unwrap(), "b\nc\n");
+        assert_eq!(select_lines(text, "3-").unwrap(), "c\nd\n");
+        assert_eq!(select_lines(text, "-1").unwrap(), "a\n");
+        assert_eq!(select_lines(text, "4").unwrap(), "d\n");
+        assert!(select_lines(text, "3-2").is_err());
+        assert!(select_lines(text, "x").is_err());
+    }
+}
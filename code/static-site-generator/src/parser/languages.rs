@@ -0,0 +1,270 @@
+//! ABOUTME: Document languages from `lang` frontmatter or `.xx.md` filename suffixes
+//! ABOUTME: Groups translations of a note and selects documents for per-language listings
+
+use super::diagnostics::Diagnostic;
+use super::document::{Document, DocumentMetadata};
+use super::schema::key_line;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Language of documents without a `lang` key or filename suffix, unless
+/// configured otherwise
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// ISO 639-1 codes recognised as filename suffixes (`note.es.md`)
+const LANGUAGE_CODES: [&str; 184] = [
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// Another language version of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    /// Language code
+    pub lang: String,
+
+    /// Title of the translated document
+    pub title: String,
+
+    /// Output path of the translated document
+    pub output_path: PathBuf,
+}
+
+/// Language suffix of a file name (`es` for `note.es.md`, `pt-BR` for
+/// `note.pt-BR.md`)
+///
+/// Only ISO 639-1 codes, optionally with a region, count as suffixes, so
+/// `v1.2.md` or `notes.old.md` are not mistaken for translations.
+pub fn filename_language(relative_path: &Path) -> Option<String> {
+    let stem = relative_path.file_stem()?.to_str()?;
+    let (_, suffix) = stem.rsplit_once('.')?;
+    is_language_code(suffix).then(|| suffix.to_string())
+}
+
+/// Check for an ISO 639-1 code, optionally with a two-letter region (`pt-BR`)
+fn is_language_code(code: &str) -> bool {
+    let (language, region) = match code.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (code, None),
+    };
+    let valid_region = region.map_or(true, |region| {
+        region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    LANGUAGE_CODES.contains(&language) && valid_region
+}
+
+/// Language of a document: the `lang` frontmatter key, then the filename
+/// suffix, then `default_language`
+///
+/// The language becomes part of output paths, so a `lang` value that is not
+/// a language code is ignored and reported.
+pub fn document_language(
+    metadata: &DocumentMetadata,
+    frontmatter: &str,
+    relative_path: &Path,
+    default_language: &str,
+) -> (String, Option<Diagnostic>) {
+    let lang = metadata
+        .custom
+        .get("lang")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty());
+
+    let mut diagnostic = None;
+    if let Some(lang) = lang.filter(|lang| !is_language_code(lang)) {
+        diagnostic = Some(Diagnostic::at_line(
+            key_line(frontmatter, "lang").unwrap_or(1),
+            format!(
+                "Invalid language '{}': expected a code like 'es' or 'pt-BR'",
+                lang
+            ),
+        ));
+    }
+
+    let lang = lang
+        .filter(|lang| is_language_code(lang))
+        .map(String::from)
+        .or_else(|| filename_language(relative_path))
+        .unwrap_or_else(|| default_language.to_string());
+    (lang, diagnostic)
+}
+
+/// Key shared by all translations of a note
+///
+/// Defaults to the relative path without its extension and language
+/// suffix, so `notes/idea.md` and `notes/idea.es.md` match. A
+/// `translation_key` frontmatter value groups files with different names.
+pub fn translation_key(metadata: &DocumentMetadata, relative_path: &Path) -> String {
+    if let Some(key) = metadata
+        .custom
+        .get("translation_key")
+        .and_then(|value| value.as_str())
+    {
+        return key.to_string();
+    }
+    let mut path = relative_path.with_extension("");
+    if filename_language(relative_path).is_some() {
+        path = path.with_extension("");
+    }
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Fill in each document's translations from documents sharing its
+/// translation key, ordered by language
+pub fn link_translations(documents: &mut [Document]) {
+    let mut groups: HashMap<&str, Vec<Translation>> = HashMap::new();
+    for doc in documents.iter() {
+        groups
+            .entry(doc.translation_key.as_str())
+            .or_default()
+            .push(Translation {
+                lang: doc.lang.clone(),
+                title: doc.title().to_string(),
+                output_path: doc.output_path.clone(),
+            });
+    }
+    let groups: HashMap<String, Vec<Translation>> = groups
+        .into_iter()
+        .filter(|(_, translations)| translations.len() > 1)
+        .map(|(key, mut translations)| {
+            translations.sort_by(|a, b| a.lang.cmp(&b.lang));
+            (key.to_string(), translations)
+        })
+        .collect();
+
+    for doc in documents.iter_mut() {
+        doc.translations = groups
+            .get(&doc.translation_key)
+            .map(|translations| {
+                translations
+                    .iter()
+                    .filter(|t| t.output_path != doc.output_path)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+}
+
+/// Languages used by documents, with `default_language` first
+pub fn site_languages(documents: &[Document], default_language: &str) -> Vec<String> {
+    let mut languages = vec![default_language.to_string()];
+    let mut others: Vec<String> = documents
+        .iter()
+        .map(|doc| doc.lang.clone())
+        .filter(|lang| lang != default_language)
+        .collect();
+    others.sort();
+    others.dedup();
+    languages.extend(others);
+    languages
+}
+
+/// Whether a document belongs in listings for `lang`
+///
+/// Listings in the default language also include documents that have no
+/// translation in it, so untranslated notes are not hidden.
+pub fn listed_in_language(doc: &Document, lang: &str, default_language: &str) -> bool {
+    doc.lang == lang
+        || (lang == default_language
+            && !doc.translations.iter().any(|t| t.lang == default_language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(path: &str, lang: &str) -> Document {
+        let mut doc = Document::new(
+            PathBuf::from(path),
+            PathBuf::from(path),
+            "resources".to_string(),
+        );
+        doc.lang = lang.to_string();
+        doc.translation_key = translation_key(&doc.metadata, Path::new(path));
+        doc
+    }
+
+    #[test]
+    fn test_filename_language() {
+        assert_eq!(
+            filename_language(Path::new("notes/idea.es.md")).as_deref(),
+            Some("es")
+        );
+        assert_eq!(
+            filename_language(Path::new("idea.pt-BR.md")).as_deref(),
+            Some("pt-BR")
+        );
+        assert_eq!(filename_language(Path::new("idea.md")), None);
+        assert_eq!(filename_language(Path::new("notes.old.md")), None);
+        assert_eq!(filename_language(Path::new("v1.2.md")), None);
+
+        let mut metadata = DocumentMetadata::default();
+        assert_eq!(
+            document_language(&metadata, "", Path::new("a.md"), "en"),
+            ("en".to_string(), None)
+        );
+        metadata.custom.insert(
+            "lang".to_string(),
+            serde_yaml::Value::String("fr".to_string()),
+        );
+        assert_eq!(
+            document_language(&metadata, "", Path::new("a.es.md"), "en"),
+            ("fr".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_invalid_lang_is_reported() {
+        for lang in ["../x", "en/us", "english"] {
+            let mut metadata = DocumentMetadata::default();
+            metadata.custom.insert(
+                "lang".to_string(),
+                serde_yaml::Value::String(lang.to_string()),
+            );
+            let frontmatter = format!("---\ntitle: A\nlang: {}\n---\n", lang);
+
+            let (language, diagnostic) =
+                document_language(&metadata, &frontmatter, Path::new("a.md"), "de");
+
+            assert_eq!(language, "de");
+            let diagnostic = diagnostic.unwrap();
+            assert_eq!(diagnostic.line, Some(3));
+            assert!(diagnostic.message.contains(lang));
+        }
+    }
+
+    #[test]
+    fn test_link_translations() {
+        let mut documents = vec![
+            document("resources/idea.md", "en"),
+            document("resources/idea.es.md", "es"),
+            document("resources/solo.es.md", "es"),
+        ];
+        link_translations(&mut documents);
+
+        assert_eq!(documents[0].translations.len(), 1);
+        assert_eq!(documents[0].translations[0].lang, "es");
+        assert_eq!(
+            documents[1].translations[0].output_path,
+            Path::new("resources/idea.html")
+        );
+        assert!(documents[2].translations.is_empty());
+
+        assert!(listed_in_language(&documents[0], "en", "en"));
+        assert!(!listed_in_language(&documents[1], "en", "en"));
+        assert!(listed_in_language(&documents[2], "en", "en"));
+        assert_eq!(site_languages(&documents, "en"), vec!["en", "es"]);
+    }
+}
@@ -1,11 +1,24 @@
 //! ABOUTME: Markdown to HTML conversion functionality
 //! ABOUTME: Converts markdown content to clean HTML output
 
+use super::callouts::render_callouts;
+use super::citations::{extract_citations, unknown_citation_diagnostics, CitationRenderer};
+use super::diagnostics::Diagnostic;
+use super::glossary::render_glossary_terms;
+use super::hashtags::render_hashtags;
+use super::math::latex_to_mathml;
+use super::query::{extract_queries, render_queries};
+use super::shortcodes::{expand_shortcodes, restore_shortcodes};
+use super::spans::SourceLocation;
+use super::toc::add_heading_ids;
+use super::unicode::fold_case_insensitive;
 use super::wiki_links::{
     parse_wiki_links, replace_wiki_links_with_html, resolve_wiki_links, ResolvedLink,
 };
+use crate::config::{ParserConfig, UnicodeMatching};
+use crate::utils::html_escape;
 use crate::Result;
-use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd, TextMergeStream};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -17,26 +30,131 @@ use std::path::{Path, PathBuf};
 ///
 /// Currently infallible but returns Result for future compatibility
 pub fn markdown_to_html(content: &str) -> Result<String> {
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_TABLES);
-    options.insert(Options::ENABLE_FOOTNOTES);
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TASKLISTS);
-    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    markdown_to_html_with_diagnostics(content, Path::new(""), &ParserConfig::default())
+        .map(|(html, _)| html)
+}
 
-    let parser = Parser::new_ext(content, options);
+/// Convert markdown content to HTML, collecting non-fatal problems
+///
+/// `$inline$` and `$$display$$` math is rendered to MathML. Malformed
+/// expressions are shown as source and reported with their line number
+/// (relative to `content`). Blockquotes starting with `[!type]` are
+/// rendered as callouts, including custom types from `config`. Inline
+/// `#hashtags` link to tag pages relative to `current_doc_path`, or are
+/// stripped, depending on `config.inline_tags`. Fenced `query` blocks are
+/// replaced by their results over `config.query_index` (no documents until
+/// the index is built); invalid queries are reported. `{{< shortcodes >}}`
+/// are expanded from theme templates or built-in handlers. `[@key]`
+/// citations are resolved against `config.bibliography`, a bibliography
+/// section is appended, and unknown keys are reported. The first occurrence
+/// of each term in `config.glossary` links to its definition, and
+/// `Term` / `: Definition` lists render as definition lists.
+///
+/// # Errors
+///
+/// Currently infallible but returns Result for future compatibility
+pub fn markdown_to_html_with_diagnostics(
+    content: &str,
+    current_doc_path: &Path,
+    config: &ParserConfig,
+) -> Result<(String, Vec<Diagnostic>)> {
+    let shortcodes = expand_shortcodes(content, current_doc_path, config);
+    let content = shortcodes.content.as_str();
+
+    let parser = Parser::new_ext(content, markdown_options()).into_offset_iter();
+    let mut diagnostics: Vec<Diagnostic> = shortcodes.diagnostics;
+    diagnostics.extend(extract_queries(content).into_iter().filter_map(|block| {
+        let message = block.query.err()?;
+        Some(Diagnostic::at_line(
+            block.line,
+            format!("Invalid query: {}", message),
+        ))
+    }));
+
+    let citations = config.bibliography.as_deref().map(|bibliography| {
+        let citations = extract_citations(content);
+        diagnostics.extend(unknown_citation_diagnostics(&citations, bibliography));
+        CitationRenderer::new(bibliography, &citations, config.citation_style)
+    });
 
-    // Process events to handle special cases if needed
-    let parser = parser.map(|event| match event {
-        // We could modify events here if needed
-        // For now, pass through unchanged
+    // Render math to MathML; everything else passes through unchanged
+    let parser = parser.map(|(event, range)| match event {
+        Event::InlineMath(tex) => render_math(&tex, false, content, range.start, &mut diagnostics),
+        Event::DisplayMath(tex) => render_math(&tex, true, content, range.start, &mut diagnostics),
         _ => event,
     });
+    let events = render_queries(
+        add_heading_ids(
+            TextMergeStream::new(parser).collect(),
+            config.unicode_matching,
+        ),
+        config.query_index.as_deref().map_or(&[], Vec::as_slice),
+        current_doc_path,
+    );
+    let events = render_callouts(events, &config.callout_types);
+    let events = match &citations {
+        Some(citations) => citations.render(events),
+        None => events,
+    };
+    let events = render_hashtags(events, config.inline_tags, current_doc_path);
+    let events = match &config.glossary {
+        Some(glossary) => render_glossary_terms(events, glossary, current_doc_path),
+        None => events,
+    };
 
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
+    if let Some(citations) = &citations {
+        html_output.push_str(&citations.bibliography_html());
+    }
+    let html_output = restore_shortcodes(&html_output, &shortcodes.rendered);
 
-    Ok(html_output)
+    Ok((html_output, diagnostics))
+}
+
+/// Markdown extensions used for rendering documents
+pub(crate) fn markdown_options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    options.insert(Options::ENABLE_MATH);
+    options.insert(Options::ENABLE_DEFINITION_LIST);
+    options
+}
+
+/// Render a math event to MathML, falling back to the source on error
+fn render_math<'a>(
+    tex: &str,
+    display: bool,
+    content: &str,
+    offset: usize,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Event<'a> {
+    match latex_to_mathml(tex, display) {
+        Ok(mathml) => Event::InlineHtml(CowStr::from(mathml)),
+        Err(e) => {
+            let location = SourceLocation::from_offset(content, offset);
+            diagnostics.push(
+                Diagnostic::at_line(
+                    location.line,
+                    format!("Invalid math expression '{}': {}", tex, e),
+                )
+                .with_column(location.column),
+            );
+
+            let delimiter = if display { "$$" } else { "$" };
+            Event::InlineHtml(CowStr::from(format!(
+                r#"<code class="math-error" title="{}">{}{}{}</code>"#,
+                html_escape(&e.message),
+                delimiter,
+                html_escape(tex),
+                delimiter
+            )))
+        }
+    }
 }
 
 /// Convert markdown to HTML with custom heading ID generation
@@ -51,39 +169,23 @@ pub fn markdown_to_html_with_ids(content: &str) -> Result<String> {
     options.insert(Options::ENABLE_SMART_PUNCTUATION);
     options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
 
-    let parser = Parser::new_ext(content, options);
-    let mut heading_text = String::new();
-    let mut in_heading = false;
-
-    let parser = parser.map(|event| match event {
-        Event::Start(Tag::Heading { .. }) => {
-            in_heading = true;
-            heading_text.clear();
-            event
-        }
-        Event::Text(ref text) if in_heading => {
-            heading_text.push_str(text);
-            event
-        }
-        Event::End(TagEnd::Heading(level)) => {
-            in_heading = false;
-            // Generate ID from heading text
-            let _id = generate_heading_id(&heading_text);
-            // For now, just pass through - in the future we could inject the ID
-            Event::End(TagEnd::Heading(level))
-        }
-        _ => event,
-    });
+    let events = add_heading_ids(
+        Parser::new_ext(content, options).collect(),
+        UnicodeMatching::default(),
+    );
 
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
 
     Ok(html_output)
 }
 
 /// Generate a URL-safe ID from heading text
-fn generate_heading_id(text: &str) -> String {
-    text.to_lowercase()
+///
+/// The text is normalized like link targets, so `Café` and its decomposed
+/// form get the same ID (and `cafe` when diacritics are folded).
+pub fn generate_heading_id(text: &str, matching: UnicodeMatching) -> String {
+    fold_case_insensitive(text, matching)
         .chars()
         .map(|c| {
             if c.is_alphanumeric() || c == '-' || c == '_' {
@@ -108,19 +210,21 @@ pub fn markdown_to_html_with_wiki_links(
     content: &str,
     current_doc_path: &Path,
     document_lookup: &HashMap<String, PathBuf>,
+    config: &ParserConfig,
 ) -> Result<(String, Vec<ResolvedLink>)> {
     // First, parse wiki links from the raw markdown
     let wiki_links = parse_wiki_links(content);
 
     // Resolve wiki links to actual document paths
-    let resolved_links = resolve_wiki_links(wiki_links, document_lookup);
+    let resolved_links = resolve_wiki_links(wiki_links, document_lookup, config.unicode_matching);
 
     // Replace wiki links with HTML in the markdown content
     let content_with_html_links =
         replace_wiki_links_with_html(content, &resolved_links, current_doc_path)?;
 
     // Now convert the modified markdown to HTML
-    let html = markdown_to_html(&content_with_html_links)?;
+    let (html, _) =
+        markdown_to_html_with_diagnostics(&content_with_html_links, current_doc_path, config)?;
 
     Ok((html, resolved_links))
 }
@@ -182,7 +286,7 @@ mod tests {
         let markdown = "# Hello\n\nThis is a **bold** text with *italics*.";
         let html = markdown_to_html(markdown).unwrap();
 
-        assert!(html.contains("<h1>Hello</h1>"));
+        assert!(html.contains(r#"<h1 id="hello">Hello</h1>"#));
         assert!(html.contains("<strong>bold</strong>"));
         assert!(html.contains("<em>italics</em>"));
     }
@@ -230,12 +334,74 @@ mod tests {
         assert!(html.contains("Not completed"));
     }
 
+    #[test]
+    fn test_markdown_with_math() {
+        let markdown = "Inline $x^2$ and display:\n\n$$\\frac{a}{b}$$\n\nCosts $5 and $10.";
+        let (html, diagnostics) =
+            markdown_to_html_with_diagnostics(markdown, Path::new(""), &ParserConfig::default())
+                .unwrap();
+
+        assert!(diagnostics.is_empty());
+        assert!(
+            html.contains(r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="inline">"#)
+        );
+        assert!(html.contains("<msup><mi>x</mi><mn>2</mn></msup>"));
+        assert!(html.contains(r#"display="block""#));
+        assert!(html.contains("<mfrac>"));
+        assert!(!html.contains("$x^2$"));
+    }
+
+    #[test]
+    fn test_markdown_with_malformed_math() {
+        let markdown = "Fine $a$\n\nBroken $\\frac{1}$ here";
+        let (html, diagnostics) =
+            markdown_to_html_with_diagnostics(markdown, Path::new(""), &ParserConfig::default())
+                .unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert!(diagnostics[0].message.contains("Missing denominator"));
+        assert!(html.contains(r#"<code class="math-error""#));
+        assert!(html.contains("<p>Broken "));
+    }
+
+    #[test]
+    fn test_rendered_heading_ids_match_table_of_contents() {
+        let markdown = "# Cafe\u{301} Notes\n\n## See [[Page]]\n\n## Café notes\n";
+        let config = ParserConfig {
+            unicode_matching: UnicodeMatching::FoldDiacritics,
+            ..ParserConfig::default()
+        };
+
+        let (html, _) =
+            markdown_to_html_with_diagnostics(markdown, Path::new(""), &config).unwrap();
+        let toc = crate::parser::table_of_contents(markdown, config.unicode_matching);
+
+        let ids: Vec<&str> = toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["cafe-notes", "see-page", "cafe-notes-1"]);
+        for id in ids {
+            assert!(html.contains(&format!(r#"id="{}""#, id)), "{}", html);
+        }
+    }
+
     #[test]
     fn test_generate_heading_id() {
-        assert_eq!(generate_heading_id("Hello World"), "hello-world");
-        assert_eq!(generate_heading_id("Test 123!"), "test-123_");
-        assert_eq!(generate_heading_id("Multiple   Spaces"), "multiple-spaces");
-        assert_eq!(generate_heading_id("CamelCase"), "camelcase");
+        let matching = UnicodeMatching::default();
+        assert_eq!(generate_heading_id("Hello World", matching), "hello-world");
+        assert_eq!(generate_heading_id("Test 123!", matching), "test-123_");
+        assert_eq!(
+            generate_heading_id("Multiple   Spaces", matching),
+            "multiple-spaces"
+        );
+        assert_eq!(generate_heading_id("CamelCase", matching), "camelcase");
+        assert_eq!(
+            generate_heading_id("Cafe\u{301} Ｎotes", matching),
+            generate_heading_id("Café Notes", matching)
+        );
+        assert_eq!(
+            generate_heading_id("Café", UnicodeMatching::FoldDiacritics),
+            "cafe"
+        );
     }
 
     #[test]
@@ -266,8 +432,13 @@ mod tests {
         let mut lookup = HashMap::new();
         lookup.insert("test link".to_string(), PathBuf::from("test.html"));
 
-        let (html, resolved) =
-            markdown_to_html_with_wiki_links(content, current_path, &lookup).unwrap();
+        let (html, resolved) = markdown_to_html_with_wiki_links(
+            content,
+            current_path,
+            &lookup,
+            &ParserConfig::default(),
+        )
+        .unwrap();
 
         // Check HTML output
         assert!(html.contains(r#"<a href="test.html" class="wiki-link">test link</a>"#));
@@ -0,0 +1,779 @@
+//! ABOUTME: LaTeX math to MathML conversion at build time
+//! ABOUTME: Supports the common TeX subset used in notes without any JavaScript
+
+use crate::utils::html_escape;
+use std::fmt;
+
+/// Error raised for a malformed or unsupported math expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathError {
+    /// Byte offset of the problem within the expression
+    pub offset: usize,
+
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+type MathResult<T> = std::result::Result<T, MathError>;
+
+/// Convert a LaTeX math expression to a MathML `<math>` element
+///
+/// The original TeX source is kept as an annotation so it survives copy/paste.
+///
+/// # Errors
+///
+/// Returns error with the offending offset if the expression is malformed or
+/// uses an unsupported command
+pub fn latex_to_mathml(source: &str, display: bool) -> MathResult<String> {
+    let tokens = tokenize(source)?;
+    let mut parser = MathParser {
+        tokens,
+        pos: 0,
+        source,
+    };
+    let body = parser.parse_row(None)?;
+
+    Ok(format!(
+        r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="{}"><semantics><mrow>{}</mrow><annotation encoding="application/x-tex">{}</annotation></semantics></math>"#,
+        if display { "block" } else { "inline" },
+        body,
+        html_escape(source)
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Command(String),
+    Letter(char),
+    Number(String),
+    Symbol(char),
+    Open,
+    Close,
+    Superscript,
+    Subscript,
+    Ampersand,
+    NewRow,
+}
+
+/// Split a TeX expression into tokens paired with their byte offsets
+fn tokenize(source: &str) -> MathResult<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '\\' => {
+                let Some(&(_, next)) = chars.get(i + 1) else {
+                    return Err(MathError {
+                        offset,
+                        message: "Trailing backslash".to_string(),
+                    });
+                };
+                if next == '\\' {
+                    tokens.push((offset, Token::NewRow));
+                    i += 2;
+                } else if next.is_ascii_alphabetic() {
+                    let name: String = chars[i + 1..]
+                        .iter()
+                        .map(|(_, c)| *c)
+                        .take_while(char::is_ascii_alphabetic)
+                        .collect();
+                    i += 1 + name.chars().count();
+                    tokens.push((offset, Token::Command(name)));
+                } else {
+                    tokens.push((offset, Token::Command(next.to_string())));
+                    i += 2;
+                }
+                continue;
+            }
+            '{' => tokens.push((offset, Token::Open)),
+            '}' => tokens.push((offset, Token::Close)),
+            '^' => tokens.push((offset, Token::Superscript)),
+            '_' => tokens.push((offset, Token::Subscript)),
+            '&' => tokens.push((offset, Token::Ampersand)),
+            c if c.is_ascii_digit() || c == '.' => {
+                let number: String = chars[i..]
+                    .iter()
+                    .map(|(_, c)| *c)
+                    .take_while(|c| c.is_ascii_digit() || *c == '.')
+                    .collect();
+                i += number.len();
+                tokens.push((offset, Token::Number(number)));
+                continue;
+            }
+            c if c.is_alphabetic() => tokens.push((offset, Token::Letter(c))),
+            c => tokens.push((offset, Token::Symbol(c))),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Where a row of expressions should stop
+#[derive(Clone, Copy, PartialEq)]
+enum RowEnd {
+    /// A closing brace `}`
+    Group,
+    /// A `\right` delimiter
+    Right,
+    /// An `\end{...}` of an environment, or a cell/row separator inside it
+    Environment,
+}
+
+struct MathParser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    source: &'a str,
+}
+
+impl MathParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(o, _)| *o)
+            .unwrap_or(self.source.len())
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> MathResult<T> {
+        Err(MathError {
+            offset: self.offset(),
+            message: message.into(),
+        })
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, t)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    /// Parse expressions until the given terminator (or end of input)
+    fn parse_row(&mut self, end: Option<RowEnd>) -> MathResult<String> {
+        let mut row = String::new();
+
+        loop {
+            match (self.peek(), end) {
+                (None, None) => return Ok(row),
+                (None, Some(RowEnd::Group)) => return self.error("Missing closing brace '}'"),
+                (None, Some(RowEnd::Right)) => return self.error("Missing \\right delimiter"),
+                (None, Some(RowEnd::Environment)) => return self.error("Missing \\end"),
+                (Some(Token::Close), Some(RowEnd::Group)) => return Ok(row),
+                (Some(Token::Close), _) => return self.error("Unexpected closing brace '}'"),
+                (Some(Token::Command(name)), Some(RowEnd::Right)) if name == "right" => {
+                    return Ok(row)
+                }
+                (Some(Token::Command(name)), Some(RowEnd::Environment)) if name == "end" => {
+                    return Ok(row)
+                }
+                (Some(Token::Ampersand | Token::NewRow), Some(RowEnd::Environment)) => {
+                    return Ok(row)
+                }
+                (Some(Token::Ampersand), _) => {
+                    return self.error("'&' is only allowed inside an environment")
+                }
+                (Some(Token::NewRow), _) => {
+                    // Line breaks outside environments have no effect in inline MathML
+                    self.pos += 1;
+                }
+                _ => row.push_str(&self.parse_scripted()?),
+            }
+        }
+    }
+
+    /// Parse an atom followed by optional sub/superscripts
+    fn parse_scripted(&mut self) -> MathResult<String> {
+        let (base, is_large_op) = self.parse_atom()?;
+        let mut sub = None;
+        let mut sup = None;
+
+        loop {
+            match self.peek() {
+                Some(Token::Subscript) if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_argument("subscript")?);
+                }
+                Some(Token::Superscript) if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_argument("superscript")?);
+                }
+                Some(Token::Subscript) => return self.error("Double subscript"),
+                Some(Token::Superscript) => return self.error("Double superscript"),
+                _ => break,
+            }
+        }
+
+        let (under, over, both) = if is_large_op {
+            ("munder", "mover", "munderover")
+        } else {
+            ("msub", "msup", "msubsup")
+        };
+
+        Ok(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => format!("<{0}>{1}{2}</{0}>", under, base, sub),
+            (None, Some(sup)) => format!("<{0}>{1}{2}</{0}>", over, base, sup),
+            (Some(sub), Some(sup)) => format!("<{0}>{1}{2}{3}</{0}>", both, base, sub, sup),
+        })
+    }
+
+    /// Parse a required argument: a braced group or a single atom
+    fn parse_argument(&mut self, what: &str) -> MathResult<String> {
+        match self.peek() {
+            None | Some(Token::Close) => self.error(format!("Missing {}", what)),
+            Some(Token::Open) => self.parse_group(),
+            _ => Ok(self.parse_atom()?.0),
+        }
+    }
+
+    /// Parse a `{...}` group into an `<mrow>`
+    fn parse_group(&mut self) -> MathResult<String> {
+        self.pos += 1;
+        let row = self.parse_row(Some(RowEnd::Group))?;
+        self.pos += 1;
+        Ok(format!("<mrow>{}</mrow>", row))
+    }
+
+    /// Read the raw source text of a `{...}` group (for \text, \begin, ...)
+    fn parse_raw_group(&mut self, what: &str) -> MathResult<String> {
+        if self.peek() != Some(&Token::Open) {
+            return self.error(format!("Expected '{{' after {}", what));
+        }
+        let start = self.offset() + 1;
+        self.pos += 1;
+
+        let mut depth = 0;
+        loop {
+            match self.peek() {
+                None => return self.error("Missing closing brace '}'"),
+                Some(Token::Close) if depth == 0 => {
+                    let text = self.source[start..self.offset()].to_string();
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some(Token::Close) => depth -= 1,
+                Some(Token::Open) => depth += 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Parse a single atom, returning its MathML and whether it is a large operator
+    fn parse_atom(&mut self) -> MathResult<(String, bool)> {
+        let offset = self.offset();
+        let Some(token) = self.next() else {
+            return self.error("Unexpected end of expression");
+        };
+
+        let atom = match token {
+            Token::Letter(c) => format!("<mi>{}</mi>", html_escape(&c.to_string())),
+            Token::Number(n) => format!("<mn>{}</mn>", n),
+            Token::Symbol(c) => format!("<mo>{}</mo>", html_escape(&c.to_string())),
+            Token::Open => {
+                self.pos -= 1;
+                self.parse_group()?
+            }
+            Token::Command(name) => return self.parse_command(&name, offset),
+            Token::Superscript | Token::Subscript => {
+                // Scripts without a base attach to an empty element
+                self.pos -= 1;
+                String::from("<mrow></mrow>")
+            }
+            Token::Close | Token::Ampersand | Token::NewRow => {
+                self.pos -= 1;
+                return self.error("Unexpected token");
+            }
+        };
+
+        Ok((atom, false))
+    }
+
+    /// Parse a backslash command
+    fn parse_command(&mut self, name: &str, offset: usize) -> MathResult<(String, bool)> {
+        if let Some(symbol) = greek_letter(name) {
+            return Ok((format!("<mi>{}</mi>", symbol), false));
+        }
+        if let Some(symbol) = large_operator(name) {
+            return Ok((format!("<mo largeop=\"true\">{}</mo>", symbol), true));
+        }
+        if let Some(symbol) = operator_symbol(name) {
+            return Ok((format!("<mo>{}</mo>", html_escape(symbol)), false));
+        }
+        if FUNCTION_NAMES.contains(&name) {
+            let is_limit = matches!(name, "lim" | "max" | "min" | "sup" | "inf");
+            return Ok((format!("<mi>{}</mi>", name), is_limit));
+        }
+
+        let atom = match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument("numerator")?;
+                let denominator = self.parse_argument("denominator")?;
+                format!("<mfrac>{}{}</mfrac>", numerator, denominator)
+            }
+            "binom" => {
+                let top = self.parse_argument("argument")?;
+                let bottom = self.parse_argument("argument")?;
+                format!(
+                    "<mrow><mo>(</mo><mfrac linethickness=\"0\">{}{}</mfrac><mo>)</mo></mrow>",
+                    top, bottom
+                )
+            }
+            "sqrt" => {
+                if self.peek() == Some(&Token::Symbol('[')) {
+                    self.pos += 1;
+                    let mut index = String::new();
+                    while self.peek() != Some(&Token::Symbol(']')) {
+                        if self.peek().is_none() {
+                            return self.error("Missing ']' in \\sqrt index");
+                        }
+                        index.push_str(&self.parse_scripted()?);
+                    }
+                    self.pos += 1;
+                    let radicand = self.parse_argument("radicand")?;
+                    format!("<mroot>{}<mrow>{}</mrow></mroot>", radicand, index)
+                } else {
+                    format!("<msqrt>{}</msqrt>", self.parse_argument("radicand")?)
+                }
+            }
+            "text" | "textrm" | "mbox" | "operatorname" => {
+                let text = self.parse_raw_group(&format!("\\{}", name))?;
+                if name == "operatorname" {
+                    format!("<mi>{}</mi>", html_escape(&text))
+                } else {
+                    format!("<mtext>{}</mtext>", html_escape(&text))
+                }
+            }
+            "mathbf" | "mathrm" | "mathit" | "mathbb" | "mathcal" | "mathsf" | "mathtt"
+            | "boldsymbol" => {
+                let variant = match name {
+                    "mathbf" | "boldsymbol" => "bold",
+                    "mathrm" => "normal",
+                    "mathit" => "italic",
+                    "mathbb" => "double-struck",
+                    "mathcal" => "script",
+                    "mathsf" => "sans-serif",
+                    _ => "monospace",
+                };
+                let text = self.parse_raw_group(&format!("\\{}", name))?;
+                format!(
+                    "<mi mathvariant=\"{}\">{}</mi>",
+                    variant,
+                    html_escape(text.trim())
+                )
+            }
+            "hat" | "bar" | "vec" | "tilde" | "dot" | "ddot" | "overline" | "widehat"
+            | "widetilde" => {
+                let accent = match name {
+                    "hat" | "widehat" => "^",
+                    "bar" | "overline" => "\u{00AF}",
+                    "vec" => "\u{2192}",
+                    "tilde" | "widetilde" => "~",
+                    "dot" => "\u{02D9}",
+                    _ => "\u{00A8}",
+                };
+                let base = self.parse_argument("argument")?;
+                format!("<mover accent=\"true\">{}<mo>{}</mo></mover>", base, accent)
+            }
+            "underline" => {
+                let base = self.parse_argument("argument")?;
+                format!("<munder accentunder=\"true\">{}<mo>_</mo></munder>", base)
+            }
+            "left" => {
+                let open = self.parse_delimiter()?;
+                let inner = self.parse_row(Some(RowEnd::Right))?;
+                self.pos += 1; // \right
+                let close = self.parse_delimiter()?;
+                format!(
+                    "<mrow><mo fence=\"true\" stretchy=\"true\">{}</mo>{}<mo fence=\"true\" stretchy=\"true\">{}</mo></mrow>",
+                    open, inner, close
+                )
+            }
+            "right" => return self.error("\\right without matching \\left"),
+            "begin" => self.parse_environment()?,
+            "end" => return self.error("\\end without matching \\begin"),
+            "," | ":" | ";" | "quad" | "qquad" | "!" | " " => {
+                let width = match name {
+                    "," => "0.1667em",
+                    ":" => "0.2222em",
+                    ";" | " " => "0.2778em",
+                    "quad" => "1em",
+                    "qquad" => "2em",
+                    _ => "-0.1667em",
+                };
+                format!("<mspace width=\"{}\"></mspace>", width)
+            }
+            "{" | "}" | "%" | "$" | "#" | "_" | "&" | "|" => {
+                let symbol = if name == "|" { "\u{2016}" } else { name };
+                format!("<mo>{}</mo>", html_escape(symbol))
+            }
+            _ => {
+                return Err(MathError {
+                    offset,
+                    message: format!("Unsupported command '\\{}'", name),
+                })
+            }
+        };
+
+        Ok((atom, false))
+    }
+
+    /// Parse the delimiter following `\left` or `\right`
+    fn parse_delimiter(&mut self) -> MathResult<String> {
+        match self.next() {
+            // `.` is the null delimiter (tokenized as a number)
+            Some(Token::Number(n)) if n == "." => Ok(String::new()),
+            Some(Token::Symbol(c)) => Ok(html_escape(&c.to_string())),
+            Some(Token::Command(name)) => match name.as_str() {
+                "{" | "lbrace" => Ok("{".to_string()),
+                "}" | "rbrace" => Ok("}".to_string()),
+                "|" | "Vert" => Ok("\u{2016}".to_string()),
+                "langle" => Ok("\u{27E8}".to_string()),
+                "rangle" => Ok("\u{27E9}".to_string()),
+                "lfloor" => Ok("\u{230A}".to_string()),
+                "rfloor" => Ok("\u{230B}".to_string()),
+                "lceil" => Ok("\u{2308}".to_string()),
+                "rceil" => Ok("\u{2309}".to_string()),
+                "vert" => Ok("|".to_string()),
+                _ => {
+                    self.pos -= 1;
+                    self.error(format!("Invalid delimiter '\\{}'", name))
+                }
+            },
+            _ => {
+                self.pos -= 1;
+                self.error("Missing delimiter after \\left or \\right")
+            }
+        }
+    }
+
+    /// Parse `\begin{env} ... \end{env}` into an `<mtable>`
+    fn parse_environment(&mut self) -> MathResult<String> {
+        let env_offset = self.offset();
+        let env = self.parse_raw_group("\\begin")?;
+        let (open, close) = match env.as_str() {
+            "matrix" | "aligned" | "align" | "align*" | "gathered" | "array" => ("", ""),
+            "pmatrix" => ("(", ")"),
+            "bmatrix" => ("[", "]"),
+            "Bmatrix" => ("{", "}"),
+            "vmatrix" => ("|", "|"),
+            "Vmatrix" => ("\u{2016}", "\u{2016}"),
+            "cases" => ("{", ""),
+            _ => {
+                return Err(MathError {
+                    offset: env_offset,
+                    message: format!("Unsupported environment '{}'", env),
+                })
+            }
+        };
+        if env == "array" {
+            // Column specification is not needed for MathML layout
+            self.parse_raw_group("\\begin{array}")?;
+        }
+
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        loop {
+            cells.push(format!(
+                "<mtd>{}</mtd>",
+                self.parse_row(Some(RowEnd::Environment))?
+            ));
+            match self.next() {
+                Some(Token::Ampersand) => {}
+                Some(Token::NewRow) => {
+                    rows.push(format!("<mtr>{}</mtr>", cells.concat()));
+                    cells.clear();
+                }
+                _ => {
+                    // \end
+                    let end_env = self.parse_raw_group("\\end")?;
+                    if end_env != env {
+                        return self
+                            .error(format!("\\begin{{{}}} ended by \\end{{{}}}", env, end_env));
+                    }
+                    if cells.len() > 1 || cells[0] != "<mtd></mtd>" {
+                        rows.push(format!("<mtr>{}</mtr>", cells.concat()));
+                    }
+                    break;
+                }
+            }
+        }
+
+        let table = format!("<mtable>{}</mtable>", rows.concat());
+        if open.is_empty() && close.is_empty() {
+            Ok(table)
+        } else {
+            Ok(format!(
+                "<mrow><mo fence=\"true\">{}</mo>{}<mo fence=\"true\">{}</mo></mrow>",
+                open, table, close
+            ))
+        }
+    }
+}
+
+/// Function names rendered upright
+const FUNCTION_NAMES: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "lim", "max", "min", "sup", "inf", "det", "gcd", "deg", "dim", "ker",
+    "arg", "Pr",
+];
+
+/// Map Greek letter commands to Unicode
+fn greek_letter(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "ell" => "ℓ",
+        "hbar" => "ℏ",
+        "emptyset" | "varnothing" => "∅",
+        "aleph" => "ℵ",
+        _ => return None,
+    })
+}
+
+/// Map big operator commands to Unicode
+fn large_operator(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        "bigoplus" => "⨁",
+        "bigotimes" => "⨂",
+        _ => return None,
+    })
+}
+
+/// Map operator and relation commands to Unicode
+fn operator_symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "cdot" => "⋅",
+        "times" => "×",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "wedge" | "land" => "∧",
+        "vee" | "lor" => "∨",
+        "neg" | "lnot" => "¬",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "ll" => "≪",
+        "gg" => "≫",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "supset" => "⊃",
+        "subseteq" => "⊆",
+        "supseteq" => "⊇",
+        "forall" => "∀",
+        "exists" => "∃",
+        "mid" => "∣",
+        "parallel" => "∥",
+        "perp" => "⊥",
+        "angle" => "∠",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "vert" => "|",
+        "Vert" => "‖",
+        "prime" => "′",
+        "degree" => "°",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inner(source: &str) -> String {
+        let html = latex_to_mathml(source, false).unwrap();
+        let start = html.find("<semantics><mrow>").unwrap() + "<semantics><mrow>".len();
+        let end = html.find("</mrow><annotation").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn test_simple_expression() {
+        assert_eq!(inner("x + 2"), "<mi>x</mi><mo>+</mo><mn>2</mn>");
+    }
+
+    #[test]
+    fn test_wrapper_and_annotation() {
+        let html = latex_to_mathml("a<b", true).unwrap();
+        assert!(html
+            .starts_with(r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="block">"#));
+        assert!(html.contains(r#"<annotation encoding="application/x-tex">a&lt;b</annotation>"#));
+    }
+
+    #[test]
+    fn test_scripts() {
+        assert_eq!(inner("x^2"), "<msup><mi>x</mi><mn>2</mn></msup>");
+        assert_eq!(
+            inner("a_{ij}^n"),
+            "<msubsup><mi>a</mi><mrow><mi>i</mi><mi>j</mi></mrow><mi>n</mi></msubsup>"
+        );
+    }
+
+    #[test]
+    fn test_fraction_and_sqrt() {
+        assert_eq!(
+            inner(r"\frac{1}{2}"),
+            "<mfrac><mrow><mn>1</mn></mrow><mrow><mn>2</mn></mrow></mfrac>"
+        );
+        assert_eq!(inner(r"\sqrt x"), "<msqrt><mi>x</mi></msqrt>");
+        assert_eq!(
+            inner(r"\sqrt[3]{8}"),
+            "<mroot><mrow><mn>8</mn></mrow><mrow><mn>3</mn></mrow></mroot>"
+        );
+    }
+
+    #[test]
+    fn test_symbols_and_large_operators() {
+        assert_eq!(
+            inner(r"\sum_{i=1}^n \alpha_i"),
+            "<munderover><mo largeop=\"true\">∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msub><mi>α</mi><mi>i</mi></msub>"
+        );
+        assert_eq!(inner(r"a \leq b"), "<mi>a</mi><mo>≤</mo><mi>b</mi>");
+        assert_eq!(inner(r"\sin x"), "<mi>sin</mi><mi>x</mi>");
+    }
+
+    #[test]
+    fn test_text_and_fonts() {
+        assert_eq!(inner(r"\text{if } x"), "<mtext>if </mtext><mi>x</mi>");
+        assert_eq!(
+            inner(r"\mathbb{R}"),
+            "<mi mathvariant=\"double-struck\">R</mi>"
+        );
+    }
+
+    #[test]
+    fn test_left_right() {
+        assert_eq!(
+            inner(r"\left( x \right)"),
+            "<mrow><mo fence=\"true\" stretchy=\"true\">(</mo><mi>x</mi><mo fence=\"true\" stretchy=\"true\">)</mo></mrow>"
+        );
+    }
+
+    #[test]
+    fn test_matrix_environment() {
+        assert_eq!(
+            inner(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"),
+            "<mrow><mo fence=\"true\">(</mo><mtable><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr><mtr><mtd><mi>c</mi></mtd><mtd><mi>d</mi></mtd></mtr></mtable><mo fence=\"true\">)</mo></mrow>"
+        );
+    }
+
+    #[test]
+    fn test_errors_report_offset() {
+        let err = latex_to_mathml(r"x + \foo", false).unwrap_err();
+        assert_eq!(err.offset, 4);
+        assert!(err.message.contains("\\foo"));
+
+        let err = latex_to_mathml(r"\frac{1}{2", false).unwrap_err();
+        assert!(err.message.contains("Missing closing brace"));
+
+        let err = latex_to_mathml("a}", false).unwrap_err();
+        assert_eq!(err.offset, 1);
+
+        let err = latex_to_mathml("x^", false).unwrap_err();
+        assert!(err.message.contains("Missing superscript"));
+
+        let err = latex_to_mathml(r"\begin{matrix} a \end{pmatrix}", false).unwrap_err();
+        assert!(err.message.contains("ended by"));
+    }
+}
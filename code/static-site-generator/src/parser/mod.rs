@@ -1,20 +1,60 @@
 //! ABOUTME: Document parsing module for markdown and frontmatter processing
 //! ABOUTME: Handles conversion of markdown files to structured document objects
 
+pub mod callouts;
+pub mod citations;
+pub mod dates;
+pub mod diagnostics;
 pub mod document;
 pub mod frontmatter;
+pub mod glossary;
+pub mod hashtags;
+pub mod includes;
+pub mod languages;
 pub mod markdown;
+pub mod math;
+pub mod query;
+pub mod relationships;
+pub mod schema;
+pub mod shortcodes;
+pub mod spans;
+pub mod stats;
+pub mod tags;
+pub mod tasks;
+pub mod toc;
+pub mod unicode;
 pub mod wiki_links;
 
+pub use callouts::*;
+pub use citations::*;
+pub use dates::*;
+pub use diagnostics::*;
 pub use document::*;
 pub use frontmatter::*;
+pub use glossary::*;
+pub use hashtags::*;
+pub use includes::*;
+pub use languages::*;
 pub use markdown::*;
+pub use math::*;
+pub use query::*;
+pub use relationships::*;
+pub use schema::*;
+pub use shortcodes::*;
+pub use spans::*;
+pub use stats::*;
+pub use tags::*;
+pub use tasks::*;
+pub use toc::*;
+pub use unicode::*;
 pub use wiki_links::*;
 
+use crate::config::ParserConfig;
 use crate::{ParaSsgError, Result};
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Parse a markdown file into a Document
 ///
@@ -28,6 +68,25 @@ pub fn parse_document(
     source_path: &Path,
     relative_path: &Path,
     category: String,
+) -> Result<Document> {
+    parse_document_with_config(
+        source_path,
+        relative_path,
+        category,
+        &ParserConfig::default(),
+    )
+}
+
+/// Parse a markdown file into a Document using the given parser configuration
+///
+/// # Errors
+///
+/// Returns error if file cannot be read or parsing fails
+pub fn parse_document_with_config(
+    source_path: &Path,
+    relative_path: &Path,
+    category: String,
+    config: &ParserConfig,
 ) -> Result<Document> {
     // Read file content
     let content = fs::read_to_string(source_path).map_err(|e| {
@@ -37,20 +96,121 @@ pub fn parse_document(
         ))
     })?;
 
-    // Parse frontmatter and extract content
-    let (mut metadata, raw_content) = parse_frontmatter(&content)?;
+    // Parse frontmatter and extract content, falling back to the body alone
+    // when recovery is enabled
+    let (mut metadata, raw_content, frontmatter_error) = match try_parse_frontmatter(&content) {
+        Ok((metadata, raw_content)) => (metadata, raw_content, None),
+        Err(error) if config.recover_frontmatter => {
+            let diagnostic = error.to_diagnostic();
+            (DocumentMetadata::default(), error.body, Some(diagnostic))
+        }
+        Err(error) => return Err(error.into_located_error(source_path)),
+    };
+    if let Some(tz) = config.default_timezone {
+        metadata.apply_default_timezone(tz);
+    }
+
+    // Merge inline #hashtags into the frontmatter tags
+    for tag in extract_hashtags(&raw_content) {
+        if !metadata.tags.iter().any(|t| tag_slug(t) == tag_slug(&tag)) {
+            metadata.tags.push(tag);
+        }
+    }
 
-    // If no dates in frontmatter, use file modification time
+    // Check frontmatter against the configured schema
+    let frontmatter = &content[..content.len() - raw_content.len()];
+    let schema_diagnostics = config
+        .schema
+        .as_ref()
+        .filter(|_| frontmatter_error.is_none())
+        .map(|schema| schema.validate(relative_path, &category, &metadata, frontmatter))
+        .unwrap_or_default();
+
+    // Read typed relationships; targets are resolved once all documents are known
+    let (relationships, relationship_diagnostics) = extract_relationships(
+        &metadata,
+        frontmatter,
+        &relationship_types(&config.relationship_types),
+    );
+
+    // Dates missing from frontmatter come from git history, if enabled
+    if let Some(dates) = config
+        .git_history
+        .as_ref()
+        .and_then(|history| history.dates(source_path))
+    {
+        metadata.created.get_or_insert(dates.created.into());
+        metadata.modified.get_or_insert(dates.modified.into());
+    }
+
+    // If still no dates, use file modification time
     if metadata.date.is_none() && metadata.modified.is_none() && metadata.created.is_none() {
         if let Ok(file_metadata) = fs::metadata(source_path) {
             if let Ok(modified_time) = file_metadata.modified() {
-                metadata.modified = Some(DateTime::from(modified_time));
+                metadata.modified = Some(DateTime::<Utc>::from(modified_time).into());
             }
         }
     }
 
+    // Substitute included source files into code blocks
+    let frontmatter_lines = frontmatter.matches('\n').count();
+    let note_dir = source_path.parent().unwrap_or_else(|| Path::new(""));
+    let input_dir = input_root(source_path, relative_path);
+    let include_root = config
+        .include_root
+        .clone()
+        .unwrap_or_else(|| input_dir.clone());
+    let includes = resolve_includes(
+        &raw_content,
+        note_dir,
+        &include_root,
+        &input_dir,
+        frontmatter_lines,
+    );
+    let raw_content = includes.content;
+    let line_map = includes.line_map;
+    let mut dependencies = includes.dependencies;
+
+    // A `bibliography` file in the frontmatter adds to the site bibliography
+    let mut bibliography_diagnostics = Vec::new();
+    let bibliography = metadata
+        .custom
+        .get("bibliography")
+        .and_then(|value| value.as_str())
+        .and_then(|file| {
+            let path = note_dir.join(file);
+            dependencies.push(path.clone());
+            match Bibliography::load(&path) {
+                Ok(local) => {
+                    let mut merged = config.bibliography.as_deref().cloned().unwrap_or_default();
+                    merged.merge(local);
+                    Some(Arc::new(merged))
+                }
+                Err(e) => {
+                    let line = schema::key_line(frontmatter, "bibliography").unwrap_or(1);
+                    bibliography_diagnostics.push(Diagnostic::at_line(
+                        line,
+                        format!("Could not load bibliography: {}", e),
+                    ));
+                    None
+                }
+            }
+        });
+    let document_config;
+    let config = match &bibliography {
+        Some(bibliography) => {
+            document_config = ParserConfig {
+                bibliography: Some(bibliography.clone()),
+                ..config.clone()
+            };
+            &document_config
+        }
+        None => config,
+    };
+
     // Convert markdown to HTML
-    let html_content = markdown_to_html(&raw_content)?;
+    let (html_content, markdown_diagnostics) =
+        markdown_to_html_with_diagnostics(&raw_content, relative_path, config)?;
 
     // Create document
     let mut doc = Document::new(
@@ -59,13 +219,62 @@ pub fn parse_document(
         category,
     );
 
+    let (lang, language_diagnostic) =
+        document_language(&metadata, frontmatter, relative_path, config.language());
+    doc.lang = lang;
+    doc.translation_key = translation_key(&metadata, relative_path);
     doc.metadata = metadata;
+    doc.stats = ContentStats::from_markdown(&raw_content);
+    doc.tasks = extract_tasks(&raw_content);
+    doc.toc = table_of_contents(&raw_content, config.unicode_matching);
     doc.raw_content = raw_content;
-    doc.html_content = html_content;
+    doc.html_content = match (&frontmatter_error, config.dev_build) {
+        (Some(diagnostic), true) => render_frontmatter_banner(diagnostic) + &html_content,
+        _ => html_content,
+    };
+    doc.dependencies = dependencies;
+    if let Some(templates) = &config.shortcodes {
+        doc.dependencies.extend(
+            shortcode_names(&doc.raw_content)
+                .iter()
+                .filter_map(|name| templates.path(name))
+                .map(Path::to_path_buf),
+        );
+    }
+    doc.relationships = relationships;
+    doc.citations = config
+        .bibliography
+        .as_deref()
+        .map(|bibliography| cited_entries(&doc.raw_content, bibliography))
+        .unwrap_or_default();
+    doc.bibliography = bibliography;
+    doc.diagnostics = frontmatter_error.iter().cloned().collect();
+    doc.frontmatter_error = frontmatter_error;
+    doc.diagnostics.extend(schema_diagnostics);
+    doc.diagnostics.extend(relationship_diagnostics);
+    doc.diagnostics.extend(language_diagnostic);
+    doc.diagnostics.extend(includes.diagnostics);
+    doc.diagnostics.extend(bibliography_diagnostics);
+    doc.diagnostics
+        .extend(markdown_diagnostics.into_iter().map(|mut diagnostic| {
+            diagnostic.line = diagnostic.line.map(|line| line_map.file_line(line));
+            diagnostic
+        }));
+    doc.line_map = line_map;
 
     Ok(doc)
 }
 
+/// Derive the input directory from a source path and its relative path
+fn input_root(source_path: &Path, relative_path: &Path) -> PathBuf {
+    let depth = relative_path.components().count();
+    source_path
+        .ancestors()
+        .nth(depth)
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,12 +308,56 @@ This is a test document with **markdown**.
         assert_eq!(doc.metadata.title, Some("Test Document".to_string()));
         assert_eq!(doc.metadata.tags, vec!["rust", "testing"]);
         assert!(doc.raw_content.contains("# Test Document"));
-        assert!(doc.html_content.contains("<h1>Test Document</h1>"));
+        assert!(doc
+            .html_content
+            .contains(r#"<h1 id="test-document">Test Document</h1>"#));
         assert!(doc.html_content.contains("<strong>markdown</strong>"));
         assert_eq!(doc.category, "root");
         assert_eq!(doc.output_path, Path::new("test.html"));
     }
 
+    #[test]
+    fn test_recover_from_frontmatter_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("broken.md");
+        fs::write(
+            &file_path,
+            "---\ntitle: Broken\ntags:\n\t- tab\n---\n# Body\n\nStill **rendered**.\n",
+        )
+        .unwrap();
+
+        assert!(parse_document(&file_path, Path::new("broken.md"), "root".to_string()).is_err());
+
+        let mut config = ParserConfig {
+            recover_frontmatter: true,
+            ..ParserConfig::default()
+        };
+        let doc = parse_document_with_config(
+            &file_path,
+            Path::new("broken.md"),
+            "root".to_string(),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(doc.metadata.title, None);
+        assert!(doc.html_content.starts_with(r#"<h1 id="body">Body</h1>"#));
+        assert_eq!(doc.diagnostics[0].line, Some(4));
+        assert_eq!(doc.diagnostics[0].source_line.as_deref(), Some("\t- tab"));
+
+        config.dev_build = true;
+        let doc = parse_document_with_config(
+            &file_path,
+            Path::new("broken.md"),
+            "root".to_string(),
+            &config,
+        )
+        .unwrap();
+        assert!(doc
+            .html_content
+            .starts_with("<div class=\"frontmatter-error\""));
+        assert!(doc.html_content.contains("<strong>rendered</strong>"));
+    }
+
     #[test]
     fn test_parse_document_no_frontmatter() {
         let temp_dir = TempDir::new().unwrap();
@@ -118,6 +371,105 @@ This is a test document with **markdown**.
         assert_eq!(doc.metadata.title, None);
         assert_eq!(doc.title(), "simple");
         assert!(doc.raw_content.contains("# Simple Document"));
-        assert!(doc.html_content.contains("<h1>Simple Document</h1>"));
+        assert!(doc
+            .html_content
+            .contains(r#"<h1 id="simple-document">Simple Document</h1>"#));
+    }
+
+    #[test]
+    fn test_parse_document_with_include() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("projects")).unwrap();
+        fs::write(temp_dir.path().join("snippet.py"), "print('hi')\n").unwrap();
+
+        let file_path = temp_dir.path().join("projects/note.md");
+        let content = "---\ntitle: Note\n---\n```python include=\"/snippet.py\"\n```\n\n```python include=\"gone.py\"\n```\n";
+        fs::write(&file_path, content).unwrap();
+
+        let doc = parse_document(
+            &file_path,
+            Path::new("projects/note.md"),
+            "projects".to_string(),
+        )
+        .unwrap();
+
+        assert!(doc.raw_content.contains("print('hi')"));
+        assert!(doc.html_content.contains("language-python"));
+        assert_eq!(doc.dependencies.len(), 1);
+        assert_eq!(doc.diagnostics.len(), 1);
+        assert_eq!(doc.diagnostics[0].line, Some(7));
+    }
+
+    #[test]
+    fn test_document_bibliography() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("local.bib"),
+            "@book{doe2019, author = {Doe, Alice}, title = {Notes}, year = 2019}\n",
+        )
+        .unwrap();
+
+        let file_path = temp_dir.path().join("note.md");
+        let content = "---\nbibliography: local.bib\n---\nSee [@doe2019, p. 3] and [@smith2020].\n\nAlso [@nobody].\n";
+        fs::write(&file_path, content).unwrap();
+
+        let mut site = Bibliography::default();
+        site.insert(BibEntry {
+            key: "smith2020".to_string(),
+            year: Some("2020".to_string()),
+            ..BibEntry::default()
+        });
+        let config = ParserConfig {
+            bibliography: Some(Arc::new(site)),
+            ..ParserConfig::default()
+        };
+        let doc = parse_document_with_config(
+            &file_path,
+            Path::new("note.md"),
+            "root".to_string(),
+            &config,
+        )
+        .unwrap();
+
+        assert!(doc
+            .html_content
+            .contains(r##"<a href="#ref-doe2019">Doe 2019</a>, p. 3)"##));
+        assert!(doc
+            .html_content
+            .contains(r#"<section class="bibliography""#));
+        assert_eq!(
+            doc.citations
+                .iter()
+                .map(|e| e.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["doe2019", "smith2020"]
+        );
+        assert_eq!(doc.dependencies, vec![temp_dir.path().join("local.bib")]);
+        assert_eq!(doc.diagnostics.len(), 1);
+        assert_eq!(
+            doc.diagnostics[0].to_string(),
+            "line 6, column 6: Unknown citation key '@nobody'"
+        );
+    }
+
+    #[test]
+    fn test_locations_map_past_frontmatter_and_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("snippet.py"), "a = 1\nb = 2\nc = 3\n").unwrap();
+
+        let file_path = temp_dir.path().join("note.md");
+        let content = "---\ntitle: Note\n---\n```python include=\"snippet.py\"\n```\n\nSee [[Missing]] and $\\frac{1}$.\n";
+        fs::write(&file_path, content).unwrap();
+
+        let doc = parse_document(&file_path, Path::new("note.md"), "root".to_string()).unwrap();
+
+        let math = &doc.diagnostics[0];
+        assert_eq!((math.line, math.column), (Some(7), Some(21)));
+
+        let link = &parse_wiki_links(&doc.raw_content)[0];
+        assert_eq!(
+            doc.line_map.locate(link.location),
+            SourceLocation::new(7, 5)
+        );
     }
 }
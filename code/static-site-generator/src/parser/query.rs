@@ -0,0 +1,589 @@
+//! ABOUTME: Build-time document queries from fenced `query` blocks
+//! ABOUTME: Parses the query grammar, filters the document set and renders lists or tables
+
+use super::dates::FrontmatterDate;
+use super::document::{Document, DocumentMetadata};
+use super::hashtags::tag_slug;
+use super::wiki_links::calculate_relative_path;
+use crate::utils::html_escape;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// Language tag of fenced code blocks evaluated as queries
+pub const QUERY_LANGUAGE: &str = "query";
+
+/// Terms understood by the query grammar, for error messages
+const QUERY_KEYS: &str = "tag, status, category, path, sort, limit, format, columns";
+
+/// A document as seen by queries: its paths, title and frontmatter
+#[derive(Debug, Clone)]
+pub struct QueryRecord {
+    /// Path to the source markdown file
+    pub source_path: PathBuf,
+    /// Path relative to the input directory
+    pub relative_path: PathBuf,
+    /// Output path of the generated page
+    pub output_path: PathBuf,
+    /// Document title
+    pub title: String,
+    /// Effective PARA category
+    pub category: String,
+    /// Frontmatter metadata
+    pub metadata: DocumentMetadata,
+}
+
+impl QueryRecord {
+    /// Capture the queryable parts of a document
+    pub fn from_document(doc: &Document) -> Self {
+        Self {
+            source_path: doc.source_path.clone(),
+            relative_path: doc.relative_path.clone(),
+            output_path: doc.output_path.clone(),
+            title: doc.title().to_string(),
+            category: doc.effective_category().to_string(),
+            metadata: doc.metadata.clone(),
+        }
+    }
+
+    /// Text value of a column or sort field, if the document has one
+    fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "title" => Some(self.title.clone()),
+            "category" => Some(self.category.clone()),
+            "path" => Some(self.relative_path.display().to_string()),
+            "tags" => (!self.metadata.tags.is_empty()).then(|| self.metadata.tags.join(", ")),
+            "status" => self.metadata.status.clone(),
+            "author" => self.metadata.author.clone(),
+            "description" => self.metadata.description.clone(),
+            "created" | "modified" | "date" => self.date_field(name).map(|d| d.display()),
+            _ => self.metadata.custom.get(name).and_then(yaml_to_text),
+        }
+    }
+
+    fn date_field(&self, name: &str) -> Option<&FrontmatterDate> {
+        match name {
+            "created" => self.metadata.created.as_ref(),
+            "modified" => self.metadata.modified.as_ref(),
+            "date" => self.metadata.date.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// How query results are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryFormat {
+    /// A bulleted list of links
+    List,
+    /// A table with one column per selected field
+    Table,
+}
+
+/// A single filter term
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    /// Has one of the tags (or a nested tag below it)
+    Tag(Vec<String>),
+    /// Status is one of the values
+    Status(Vec<String>),
+    /// Category is one of the values
+    Category(Vec<String>),
+    /// Relative path starts with one of the prefixes
+    Path(Vec<String>),
+}
+
+/// A parsed query
+///
+/// Grammar: whitespace-separated `key:value` terms. Values may be quoted
+/// (`tag:"two words"`) and comma-separated to match any of several values.
+///
+/// - `tag:`, `status:`, `category:`, `path:` filter documents; all terms must match
+/// - `sort:field` orders results, optionally followed by `asc` or `desc`
+/// - `limit:N` keeps the first N results
+/// - `format:list` or `format:table` picks the output (table when columns are given)
+/// - `columns:title,status,modified` selects table columns (any frontmatter key)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    filters: Vec<Filter>,
+    sort: Option<(String, bool)>,
+    limit: Option<usize>,
+    format: QueryFormat,
+    columns: Vec<String>,
+}
+
+impl Query {
+    /// Parse a query, describing the first problem found on failure
+    pub fn parse(source: &str) -> std::result::Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut filters = Vec::new();
+        let mut sort = None;
+        let mut limit = None;
+        let mut format = None;
+        let mut columns = Vec::new();
+
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            let Some((key, value)) = token.split_once(':') else {
+                return Err(format!(
+                    "Expected 'key:value' but found '{}' (keys: {})",
+                    token, QUERY_KEYS
+                ));
+            };
+            let key = key.to_lowercase();
+            if value.is_empty() {
+                return Err(format!("Missing value for '{}:'", key));
+            }
+            let values = || {
+                value
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect::<Vec<_>>()
+            };
+
+            match key.as_str() {
+                "tag" => filters.push(Filter::Tag(values())),
+                "status" => filters.push(Filter::Status(values())),
+                "category" => filters.push(Filter::Category(values())),
+                "path" => filters.push(Filter::Path(values())),
+                "sort" => {
+                    let descending = match tokens.peek().map(|t| t.to_lowercase()).as_deref() {
+                        Some("desc") => true,
+                        Some("asc") => false,
+                        _ => {
+                            sort = Some((value.to_lowercase(), false));
+                            continue;
+                        }
+                    };
+                    tokens.next();
+                    sort = Some((value.to_lowercase(), descending));
+                }
+                "limit" => match value.parse::<usize>() {
+                    Ok(n) if n > 0 => limit = Some(n),
+                    _ => {
+                        return Err(format!(
+                            "Invalid limit '{}': expected a positive number",
+                            value
+                        ))
+                    }
+                },
+                "format" => {
+                    format = Some(match value.to_lowercase().as_str() {
+                        "list" => QueryFormat::List,
+                        "table" => QueryFormat::Table,
+                        _ => {
+                            return Err(format!(
+                                "Unknown format '{}': expected 'list' or 'table'",
+                                value
+                            ))
+                        }
+                    })
+                }
+                "columns" => columns = values().iter().map(|c| c.to_lowercase()).collect(),
+                _ => {
+                    return Err(format!(
+                        "Unknown query key '{}' (keys: {})",
+                        key, QUERY_KEYS
+                    ))
+                }
+            }
+        }
+
+        let format = format.unwrap_or(if columns.is_empty() {
+            QueryFormat::List
+        } else {
+            QueryFormat::Table
+        });
+        if format == QueryFormat::Table && columns.is_empty() {
+            columns = vec!["title".to_string(), "modified".to_string()];
+        }
+
+        Ok(Self {
+            filters,
+            sort,
+            limit,
+            format,
+            columns,
+        })
+    }
+
+    /// Documents matching the query, sorted and limited
+    ///
+    /// Drafts never match. Without `sort:` results are ordered by title;
+    /// documents missing the sort field come last.
+    pub fn evaluate<'a>(&self, records: &'a [QueryRecord]) -> Vec<&'a QueryRecord> {
+        let mut results: Vec<&QueryRecord> = records
+            .iter()
+            .filter(|r| r.metadata.status.as_deref() != Some("draft"))
+            .filter(|r| self.filters.iter().all(|f| matches_filter(f, r)))
+            .collect();
+
+        let (field, descending) = self
+            .sort
+            .clone()
+            .unwrap_or_else(|| ("title".to_string(), false));
+        results.sort_by(|a, b| {
+            let ordering = match (a.date_field(&field), b.date_field(&field)) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => return Ordering::Less,
+                (None, Some(_)) => return Ordering::Greater,
+                (None, None) => match (a.field(&field), b.field(&field)) {
+                    (Some(x), Some(y)) => x.to_lowercase().cmp(&y.to_lowercase()),
+                    (Some(_), None) => return Ordering::Less,
+                    (None, Some(_)) => return Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                },
+            };
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        if let Some(limit) = self.limit {
+            results.truncate(limit);
+        }
+        results
+    }
+
+    /// Render results as HTML, linking relative to `current_doc_path`
+    pub fn render(&self, results: &[&QueryRecord], current_doc_path: &Path) -> String {
+        if results.is_empty() {
+            return r#"<p class="query-results query-empty">No matching documents.</p>"#
+                .to_string();
+        }
+
+        let link = |record: &QueryRecord| {
+            let href = calculate_relative_path(current_doc_path, &record.output_path)
+                .unwrap_or_else(|_| record.output_path.display().to_string());
+            format!(
+                r#"<a href="{}" class="wiki-link">{}</a>"#,
+                html_escape(&href),
+                html_escape(&record.title)
+            )
+        };
+
+        let mut html = String::new();
+        match self.format {
+            QueryFormat::List => {
+                html.push_str(r#"<ul class="query-results">"#);
+                for record in results {
+                    html.push_str(&format!("<li>{}</li>", link(record)));
+                }
+                html.push_str("</ul>");
+            }
+            QueryFormat::Table => {
+                html.push_str(r#"<table class="query-results"><thead><tr>"#);
+                for column in &self.columns {
+                    html.push_str(&format!("<th>{}</th>", html_escape(&column_title(column))));
+                }
+                html.push_str("</tr></thead><tbody>");
+                for record in results {
+                    html.push_str("<tr>");
+                    for column in &self.columns {
+                        let cell = if column == "title" {
+                            link(record)
+                        } else {
+                            html_escape(&record.field(column).unwrap_or_default())
+                        };
+                        html.push_str(&format!("<td>{}</td>", cell));
+                    }
+                    html.push_str("</tr>");
+                }
+                html.push_str("</tbody></table>");
+            }
+        }
+        html
+    }
+}
+
+/// A `query` block found in markdown
+#[derive(Debug, Clone)]
+pub struct QueryBlock {
+    /// Line of the opening fence (1-based, relative to the content)
+    pub line: usize,
+    /// The parsed query, or why it could not be parsed
+    pub query: std::result::Result<Query, String>,
+}
+
+/// Find and parse all `query` blocks in markdown content
+pub fn extract_queries(content: &str) -> Vec<QueryBlock> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (event, range) in Parser::new_ext(content, Options::empty()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) if is_query_block(&kind) => {
+                let line = content[..range.start].matches('\n').count() + 1;
+                current = Some((line, String::new()));
+            }
+            Event::Text(text) => {
+                if let Some((_, source)) = current.as_mut() {
+                    source.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((line, source)) = current.take() {
+                    blocks.push(QueryBlock {
+                        line,
+                        query: Query::parse(&source),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Replace `query` blocks with their results
+///
+/// Invalid queries are shown as an error message in place of the block.
+pub fn render_queries<'a>(
+    events: Vec<Event<'a>>,
+    records: &[QueryRecord],
+    current_doc_path: &Path,
+) -> Vec<Event<'a>> {
+    let mut output = Vec::with_capacity(events.len());
+    let mut query_source: Option<String> = None;
+
+    for event in events {
+        match event {
+            Event::Start(Tag::CodeBlock(ref kind)) if is_query_block(kind) => {
+                query_source = Some(String::new());
+            }
+            Event::Text(ref text) if query_source.is_some() => {
+                if let Some(source) = query_source.as_mut() {
+                    source.push_str(text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) if query_source.is_some() => {
+                let source = query_source.take().unwrap_or_default();
+                let html = match Query::parse(&source) {
+                    Ok(query) => query.render(&query.evaluate(records), current_doc_path),
+                    Err(message) => format!(
+                        r#"<div class="query-error"><strong>Invalid query:</strong> {}<pre><code>{}</code></pre></div>"#,
+                        html_escape(&message),
+                        html_escape(source.trim())
+                    ),
+                };
+                output.push(Event::Html(CowStr::from(html)));
+            }
+            _ => output.push(event),
+        }
+    }
+
+    output
+}
+
+fn is_query_block(kind: &CodeBlockKind<'_>) -> bool {
+    matches!(kind, CodeBlockKind::Fenced(lang) if lang.split_whitespace().next() == Some(QUERY_LANGUAGE))
+}
+
+fn matches_filter(filter: &Filter, record: &QueryRecord) -> bool {
+    match filter {
+        Filter::Tag(tags) => tags.iter().any(|wanted| {
+            let wanted = tag_slug(wanted);
+            record.metadata.tags.iter().any(|tag| {
+                let tag = tag_slug(tag);
+                tag == wanted || tag.starts_with(&format!("{}/", wanted))
+            })
+        }),
+        Filter::Status(statuses) => record
+            .metadata
+            .status
+            .as_ref()
+            .is_some_and(|status| statuses.iter().any(|s| s.eq_ignore_ascii_case(status))),
+        Filter::Category(categories) => categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(&record.category)),
+        Filter::Path(prefixes) => prefixes.iter().any(|prefix| {
+            record
+                .relative_path
+                .starts_with(prefix.trim_end_matches('/'))
+        }),
+    }
+}
+
+/// Split a query into terms, keeping quoted values together
+fn tokenize(source: &str) -> std::result::Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in source.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("Unterminated quote".to_string());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Render a frontmatter value as table text
+fn yaml_to_text(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::Null => None,
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Sequence(items) => Some(
+            items
+                .iter()
+                .filter_map(yaml_to_text)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
+/// Column heading for a field name: `due_date` becomes `Due date`
+fn column_title(column: &str) -> String {
+    let text = column.replace(['_', '-'], " ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::frontmatter::parse_frontmatter;
+
+    fn record(path: &str, frontmatter: &str) -> QueryRecord {
+        let (metadata, _) = parse_frontmatter(&format!("---\n{}\n---\n", frontmatter)).unwrap();
+        let relative_path = PathBuf::from(path);
+        QueryRecord {
+            source_path: PathBuf::from("/input").join(path),
+            output_path: relative_path.with_extension("html"),
+            title: metadata.title.clone().unwrap_or_default(),
+            category: path.split('/').next().unwrap_or("root").to_string(),
+            relative_path,
+            metadata,
+        }
+    }
+
+    fn records() -> Vec<QueryRecord> {
+        vec![
+            record(
+                "projects/forge/cluster.md",
+                "title: Cluster Design\ntags: [kubernetes/networking]\nstatus: active\nmodified: 2025-03-01\nowner: sam",
+            ),
+            record(
+                "projects/forge/deploy.md",
+                "title: Deploy Plan\ntags: [kubernetes]\nstatus: active\nmodified: 2025-05-01",
+            ),
+            record(
+                "projects/forge/old.md",
+                "title: Old Design\ntags: [kubernetes]\nstatus: completed\nmodified: 2024-01-01",
+            ),
+            record(
+                "resources/k8s.md",
+                "title: Kubernetes Notes\ntags: [kubernetes]\nstatus: active",
+            ),
+            record(
+                "projects/forge/wip.md",
+                "title: WIP\ntags: [kubernetes]\nstatus: draft",
+            ),
+        ]
+    }
+
+    fn titles(results: &[&QueryRecord]) -> Vec<String> {
+        results.iter().map(|r| r.title.clone()).collect()
+    }
+
+    #[test]
+    fn test_query_filters_sort_and_limit() {
+        let records = records();
+        let query = Query::parse(
+            "tag:kubernetes status:active category:projects sort:modified desc limit:10",
+        )
+        .unwrap();
+
+        assert_eq!(
+            titles(&query.evaluate(&records)),
+            vec!["Deploy Plan", "Cluster Design"]
+        );
+
+        let query = Query::parse("status:active,completed path:projects/forge limit:2").unwrap();
+        assert_eq!(
+            titles(&query.evaluate(&records)),
+            vec!["Cluster Design", "Deploy Plan"]
+        );
+    }
+
+    #[test]
+    fn test_query_errors() {
+        assert!(Query::parse("owner:sam")
+            .unwrap_err()
+            .contains("Unknown query key 'owner'"));
+        assert!(Query::parse("limit:none")
+            .unwrap_err()
+            .contains("Invalid limit"));
+        assert!(Query::parse("kubernetes")
+            .unwrap_err()
+            .contains("Expected 'key:value'"));
+        assert!(Query::parse("tag:\"open")
+            .unwrap_err()
+            .contains("Unterminated quote"));
+        assert!(Query::parse("format:grid")
+            .unwrap_err()
+            .contains("Unknown format"));
+    }
+
+    #[test]
+    fn test_render_table() {
+        let records = records();
+        let query = Query::parse("tag:kubernetes/networking columns:title,status,owner").unwrap();
+        let html = query.render(
+            &query.evaluate(&records),
+            Path::new("projects/forge/index.html"),
+        );
+
+        assert!(html.contains("<th>Title</th><th>Status</th><th>Owner</th>"));
+        assert!(html.contains(
+            r#"<td><a href="cluster.html" class="wiki-link">Cluster Design</a></td><td>active</td><td>sam</td>"#
+        ));
+    }
+
+    #[test]
+    fn test_extract_and_render_query_blocks() {
+        let content = "# Designs\n\n```query\ntag:kubernetes\nstatus:active\n```\n\n```query\nsort:\n```\n\n```rust\ntag:ignored\n```\n";
+
+        let blocks = extract_queries(content);
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].query.is_ok());
+        assert_eq!(blocks[1].line, 8);
+        assert!(blocks[1].query.is_err());
+
+        let events: Vec<Event> = Parser::new(content).collect();
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(
+            &mut html,
+            render_queries(events, &records(), Path::new("index.html")).into_iter(),
+        );
+        assert!(html.contains(r#"<ul class="query-results">"#));
+        assert!(html.contains(">Kubernetes Notes</a>"));
+        assert!(html.contains(
+            r#"<div class="query-error"><strong>Invalid query:</strong> Missing value for &#39;sort:&#39;"#
+        ));
+        assert!(html.contains("tag:ignored"));
+    }
+}
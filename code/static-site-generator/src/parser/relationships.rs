@@ -0,0 +1,360 @@
+//! ABOUTME: Typed relationships between documents declared in frontmatter
+//! ABOUTME: Reads keys like `implements` and `refines` and resolves them like wiki links
+
+use super::diagnostics::Diagnostic;
+use super::document::DocumentMetadata;
+use super::schema::key_line;
+use super::wiki_links::{normalize_for_lookup, resolve_target};
+use crate::config::{RelationshipType, UnicodeMatching};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Relationship keys recognized without configuration: (key, label, inverse label)
+const BUILTIN_RELATIONSHIPS: &[(&str, &str, &str)] = &[
+    ("related_docs", "Related", "Related"),
+    ("implements", "Implements", "Implemented by"),
+    ("refines", "Refines", "Refined by"),
+    (PROJECT_KEY, "Project", "Project documents"),
+];
+
+/// Relationship key whose targets may also name a folder under `projects/`
+const PROJECT_KEY: &str = "project";
+
+/// Directory holding project folders, relative to the output root
+const PROJECTS_DIR: &str = "projects";
+
+/// A link from one document to another through a frontmatter key
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relationship {
+    /// Frontmatter key the link came from
+    pub key: String,
+    /// Heading shown on the linking document
+    pub label: String,
+    /// Heading shown on the target document
+    pub inverse_label: String,
+    /// Target as written (title, filename or path)
+    pub target: String,
+    /// Frontmatter line of the key (1-based), if found
+    pub line: Option<usize>,
+    /// Output path of the target document, once resolved
+    pub resolved_path: Option<PathBuf>,
+    /// Title of the target document, once resolved
+    pub target_title: Option<String>,
+}
+
+impl Relationship {
+    /// Check if the target could not be found
+    pub fn is_broken(&self) -> bool {
+        self.resolved_path.is_none()
+    }
+}
+
+/// Combine the built-in relationship types with configured ones
+///
+/// A configured type with the same key as a built-in replaces its labels.
+pub fn relationship_types(custom: &[RelationshipType]) -> Vec<RelationshipType> {
+    let mut types: Vec<RelationshipType> = BUILTIN_RELATIONSHIPS
+        .iter()
+        .map(|(key, label, inverse)| RelationshipType::new(key, label, inverse))
+        .collect();
+
+    for relationship_type in custom {
+        match types.iter_mut().find(|t| t.key == relationship_type.key) {
+            Some(existing) => *existing = relationship_type.clone(),
+            None => types.push(relationship_type.clone()),
+        }
+    }
+
+    types
+}
+
+/// Read relationship targets from a document's frontmatter
+///
+/// Values may be a single target or a list, written plain (`Spec`), as a
+/// path (`projects/spec.md`) or as a wiki link (`"[[Spec|the spec]]"`).
+/// Values that are not text are reported as warnings.
+pub fn extract_relationships(
+    metadata: &DocumentMetadata,
+    frontmatter: &str,
+    types: &[RelationshipType],
+) -> (Vec<Relationship>, Vec<Diagnostic>) {
+    let mut relationships: Vec<Relationship> = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for relationship_type in types {
+        let Some(value) = metadata.custom.get(&relationship_type.key) else {
+            continue;
+        };
+        let line = key_line(frontmatter, &relationship_type.key);
+
+        let mut targets = Vec::new();
+        if !collect_targets(value, &mut targets) {
+            diagnostics.push(Diagnostic::at_line(
+                line.unwrap_or(1),
+                format!(
+                    "Relationship '{}' must be a document name or a list of names",
+                    relationship_type.key
+                ),
+            ));
+        }
+
+        for target in targets {
+            let target = normalize_target(&target);
+            let duplicate = relationships
+                .iter()
+                .any(|r| r.key == relationship_type.key && r.target == target);
+            if target.is_empty() || duplicate {
+                continue;
+            }
+            relationships.push(Relationship {
+                key: relationship_type.key.clone(),
+                label: relationship_type.label.clone(),
+                inverse_label: relationship_type.inverse_label.clone(),
+                target,
+                line,
+                resolved_path: None,
+                target_title: None,
+            });
+        }
+    }
+
+    (relationships, diagnostics)
+}
+
+/// Build a lookup of project folders from document output paths
+///
+/// Folders are keyed by name and by path (`why`, `projects/why`) and map to
+/// their index page, so `project: why` can name the folder itself.
+pub fn build_project_folder_lookup<'a>(
+    output_paths: impl IntoIterator<Item = &'a Path>,
+    matching: UnicodeMatching,
+) -> HashMap<String, PathBuf> {
+    let mut lookup = HashMap::new();
+
+    for path in output_paths {
+        let mut components = path.components();
+        if components.next().map(|c| c.as_os_str()) != Some(PROJECTS_DIR.as_ref()) {
+            continue;
+        }
+        // Only folders with documents inside them have an index page
+        let (Some(folder), Some(_)) = (components.next(), components.next()) else {
+            continue;
+        };
+        let Some(name) = folder.as_os_str().to_str() else {
+            continue;
+        };
+
+        let folder = Path::new(PROJECTS_DIR).join(name);
+        for key in [name, &folder.to_string_lossy()] {
+            lookup
+                .entry(normalize_for_lookup(key, matching))
+                .or_insert_with(|| folder.join("index.html"));
+        }
+    }
+
+    lookup
+}
+
+/// Resolve relationship targets against the document lookup
+///
+/// `project` targets that match no document fall back to `project_folders`
+/// (see [`build_project_folder_lookup`]). `titles` maps output paths to
+/// document titles for display; `matching` must be the mode the lookups
+/// were built with.
+pub fn resolve_relationships(
+    relationships: &mut [Relationship],
+    document_lookup: &HashMap<String, PathBuf>,
+    project_folders: &HashMap<String, PathBuf>,
+    titles: &HashMap<PathBuf, String>,
+    matching: UnicodeMatching,
+) {
+    for relationship in relationships {
+        relationship.resolved_path =
+            resolve_target(&relationship.target, document_lookup, matching).or_else(|| {
+                (relationship.key == PROJECT_KEY)
+                    .then(|| resolve_target(&relationship.target, project_folders, matching))
+                    .flatten()
+            });
+        relationship.target_title = relationship
+            .resolved_path
+            .as_ref()
+            .and_then(|path| titles.get(path))
+            .cloned();
+    }
+}
+
+/// Warnings for relationships whose target does not exist
+pub fn unresolved_relationship_diagnostics(relationships: &[Relationship]) -> Vec<Diagnostic> {
+    relationships
+        .iter()
+        .filter(|r| r.is_broken())
+        .map(|r| {
+            Diagnostic::at_line(
+                r.line.unwrap_or(1),
+                format!("Unresolved '{}' target '{}'", r.key, r.target),
+            )
+        })
+        .collect()
+}
+
+/// Collect text targets from a frontmatter value, returning false if any
+/// part of it is not text
+///
+/// Nested lists are flattened because unquoted YAML `[[Spec]]` parses as a
+/// list containing a list.
+fn collect_targets(value: &Value, targets: &mut Vec<String>) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => {
+            targets.push(s.clone());
+            true
+        }
+        Value::Sequence(items) => items
+            .iter()
+            .fold(true, |ok, item| collect_targets(item, targets) && ok),
+        _ => false,
+    }
+}
+
+/// Strip wiki link brackets, display text, heading anchors and `.md`
+fn normalize_target(target: &str) -> String {
+    let target = target.trim();
+    let target = target
+        .strip_prefix("[[")
+        .and_then(|t| t.strip_suffix("]]"))
+        .unwrap_or(target);
+    let target = target.split(['|', '#']).next().unwrap_or("").trim();
+    target.strip_suffix(".md").unwrap_or(target).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::frontmatter::parse_frontmatter;
+    use crate::parser::wiki_links::build_document_lookup;
+
+    fn types() -> Vec<RelationshipType> {
+        relationship_types(&[])
+    }
+
+    #[test]
+    fn test_extract_relationships() {
+        let content = "---\ntitle: Parser\nimplements: [[Markdown Spec]]\nrefines:\n  - \"[[Old Parser|the old one]]\"\n  - resources/design.md\nrelated_docs: 42\n---\nBody\n";
+        let (metadata, body) = parse_frontmatter(content).unwrap();
+        let frontmatter = &content[..content.len() - body.len()];
+
+        let (relationships, diagnostics) = extract_relationships(&metadata, frontmatter, &types());
+
+        let targets: Vec<_> = relationships
+            .iter()
+            .map(|r| (r.label.as_str(), r.target.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                ("Implements", "Markdown Spec"),
+                ("Refines", "Old Parser"),
+                ("Refines", "resources/design"),
+            ]
+        );
+        assert_eq!(relationships[0].line, Some(3));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(7));
+    }
+
+    #[test]
+    fn test_configured_types_override_builtins() {
+        let types = relationship_types(&[
+            RelationshipType::new("implements", "Builds", "Built by"),
+            RelationshipType::new("supersedes", "Supersedes", "Superseded by"),
+        ]);
+
+        let implements = types.iter().find(|t| t.key == "implements").unwrap();
+        assert_eq!(implements.inverse_label, "Built by");
+        assert_eq!(types.len(), BUILTIN_RELATIONSHIPS.len() + 1);
+    }
+
+    #[test]
+    fn test_resolve_relationships() {
+        let documents = vec![(
+            PathBuf::from("resources/markdown-spec.html"),
+            "Markdown Spec".to_string(),
+        )];
+        let lookup = build_document_lookup(&documents, UnicodeMatching::default());
+        let titles: HashMap<_, _> = documents.into_iter().collect();
+
+        let (metadata, _) =
+            parse_frontmatter("---\nimplements: [\"[[markdown-spec]]\", Missing]\n---\n").unwrap();
+        let (mut relationships, _) = extract_relationships(&metadata, "", &types());
+        resolve_relationships(
+            &mut relationships,
+            &lookup,
+            &HashMap::new(),
+            &titles,
+            UnicodeMatching::default(),
+        );
+
+        assert_eq!(
+            relationships[0].resolved_path,
+            Some(PathBuf::from("resources/markdown-spec.html"))
+        );
+        assert_eq!(
+            relationships[0].target_title.as_deref(),
+            Some("Markdown Spec")
+        );
+        assert!(relationships[1].is_broken());
+
+        let diagnostics = unresolved_relationship_diagnostics(&relationships);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'Missing'"));
+    }
+
+    #[test]
+    fn test_project_resolves_to_project_folder() {
+        let documents = vec![(
+            PathBuf::from("projects/blog-feature/spec.html"),
+            "Blog Spec".to_string(),
+        )];
+        let lookup = build_document_lookup(&documents, UnicodeMatching::default());
+        let folders = build_project_folder_lookup(
+            documents.iter().map(|(path, _)| path.as_path()),
+            UnicodeMatching::default(),
+        );
+        let titles: HashMap<_, _> = documents.into_iter().collect();
+
+        let (metadata, _) = parse_frontmatter(
+            "---\nproject: blog-feature\nrefines: [blog-feature, \"[[Blog Spec]]\"]\n---\n",
+        )
+        .unwrap();
+        let (mut relationships, _) = extract_relationships(&metadata, "", &types());
+        resolve_relationships(
+            &mut relationships,
+            &lookup,
+            &folders,
+            &titles,
+            UnicodeMatching::default(),
+        );
+
+        let resolved: Vec<_> = relationships
+            .iter()
+            .map(|r| (r.key.as_str(), r.resolved_path.clone()))
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![
+                ("refines", None),
+                (
+                    "refines",
+                    Some(PathBuf::from("projects/blog-feature/spec.html"))
+                ),
+                (
+                    "project",
+                    Some(PathBuf::from("projects/blog-feature/index.html"))
+                ),
+            ]
+        );
+        assert_eq!(relationships[2].target_title, None);
+        assert_eq!(unresolved_relationship_diagnostics(&relationships).len(), 1);
+    }
+}
@@ -0,0 +1,493 @@
+//! ABOUTME: Frontmatter schema validation
+//! ABOUTME: Checks metadata against configured types, required keys, enums and patterns
+
+use super::dates::FrontmatterDate;
+use super::diagnostics::{Diagnostic, Severity};
+use super::document::DocumentMetadata;
+use crate::{ParaSsgError, Result};
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// File name of the schema looked up in the input directory by default
+pub const SCHEMA_FILE_NAME: &str = "frontmatter-schema.toml";
+
+/// Expected type of a frontmatter field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Date,
+    List,
+    Map,
+}
+
+impl FieldType {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            FieldType::String => value.is_string(),
+            FieldType::Number => value.is_number(),
+            FieldType::Integer => value.is_i64() || value.is_u64(),
+            FieldType::Boolean => value.is_boolean(),
+            FieldType::Date => match value {
+                Value::String(s) => FrontmatterDate::parse(s).is_some(),
+                Value::Number(n) => n.is_i64(),
+                _ => false,
+            },
+            FieldType::List => value.is_array(),
+            FieldType::Map => value.is_object(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "a string",
+            FieldType::Number => "a number",
+            FieldType::Integer => "an integer",
+            FieldType::Boolean => "a boolean",
+            FieldType::Date => "a date",
+            FieldType::List => "a list",
+            FieldType::Map => "a map",
+        }
+    }
+}
+
+/// Describe the JSON type of a value for error messages
+fn value_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "nothing",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a map",
+    }
+}
+
+/// Constraints on a single frontmatter field, as written in the schema file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldSpec {
+    #[serde(rename = "type")]
+    field_type: Option<FieldType>,
+    #[serde(default)]
+    required: bool,
+    #[serde(rename = "enum")]
+    allowed: Option<Vec<String>>,
+    pattern: Option<String>,
+    #[serde(default)]
+    severity: SeveritySpec,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SeveritySpec {
+    #[default]
+    Error,
+    Warning,
+}
+
+/// A set of field constraints limited to a category and/or path glob
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    category: Option<String>,
+    glob: Option<String>,
+    #[serde(default)]
+    fields: BTreeMap<String, FieldSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaSpec {
+    #[serde(default)]
+    fields: BTreeMap<String, FieldSpec>,
+    #[serde(default)]
+    rules: Vec<RuleSpec>,
+}
+
+/// Compiled constraints on a single frontmatter field
+#[derive(Debug, Clone)]
+pub struct FieldRule {
+    pub field_type: Option<FieldType>,
+    pub required: bool,
+    pub allowed: Option<Vec<String>>,
+    pub pattern: Option<Regex>,
+    pub severity: Severity,
+}
+
+/// Field rules that apply to documents in a category and/or matching a glob
+#[derive(Debug, Clone)]
+struct ScopedRules {
+    category: Option<String>,
+    glob: Option<GlobMatcher>,
+    fields: BTreeMap<String, FieldRule>,
+}
+
+impl ScopedRules {
+    fn applies_to(&self, relative_path: &Path, category: &str) -> bool {
+        let category_matches = self
+            .category
+            .as_deref()
+            .map_or(true, |c| c.eq_ignore_ascii_case(category));
+        let glob_matches = self
+            .glob
+            .as_ref()
+            .map_or(true, |glob| glob.is_match(relative_path));
+        category_matches && glob_matches
+    }
+}
+
+/// Frontmatter schema loaded from a TOML file
+///
+/// ```toml
+/// [fields.title]
+/// type = "string"
+/// required = true
+///
+/// [[rules]]
+/// category = "projects"
+/// [rules.fields.project]
+/// required = true
+/// severity = "warning"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FrontmatterSchema {
+    fields: BTreeMap<String, FieldRule>,
+    rules: Vec<ScopedRules>,
+}
+
+impl FrontmatterSchema {
+    /// Load a schema from a TOML file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or the schema is invalid
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            ParaSsgError::Io(std::io::Error::new(
+                e.kind(),
+                format!("Failed to read schema '{}': {}", path.display(), e),
+            ))
+        })?;
+        Self::from_toml(&content).map_err(|e| match e {
+            ParaSsgError::ParseError(message) => ParaSsgError::ParseError(format!(
+                "Invalid schema '{}': {}",
+                path.display(),
+                message
+            )),
+            e => e,
+        })
+    }
+
+    /// Parse a schema from TOML text
+    ///
+    /// # Errors
+    ///
+    /// Returns error for unknown keys, invalid globs or invalid patterns
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let spec: SchemaSpec =
+            toml::from_str(content).map_err(|e| ParaSsgError::ParseError(e.message().into()))?;
+
+        let rules = spec
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let glob = rule
+                    .glob
+                    .map(|pattern| {
+                        Glob::new(&pattern)
+                            .map(|glob| glob.compile_matcher())
+                            .map_err(|e| {
+                                ParaSsgError::ParseError(format!(
+                                    "invalid glob '{}': {}",
+                                    pattern, e
+                                ))
+                            })
+                    })
+                    .transpose()?;
+                Ok(ScopedRules {
+                    category: rule.category,
+                    glob,
+                    fields: compile_fields(rule.fields)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            fields: compile_fields(spec.fields)?,
+            rules,
+        })
+    }
+
+    /// Field rules for a document; scoped rules override global ones
+    fn rules_for(&self, relative_path: &Path, category: &str) -> BTreeMap<&str, &FieldRule> {
+        let mut fields: BTreeMap<&str, &FieldRule> = self
+            .fields
+            .iter()
+            .map(|(name, rule)| (name.as_str(), rule))
+            .collect();
+        for scoped in self
+            .rules
+            .iter()
+            .filter(|scoped| scoped.applies_to(relative_path, category))
+        {
+            fields.extend(
+                scoped
+                    .fields
+                    .iter()
+                    .map(|(name, rule)| (name.as_str(), rule)),
+            );
+        }
+        fields
+    }
+
+    /// Validate document metadata against the schema
+    ///
+    /// `frontmatter` is the start of the source file up to the end of the
+    /// frontmatter; it is used to point diagnostics at the offending line.
+    /// Missing fields are reported at line 1. Empty strings and lists count
+    /// as missing for `required`.
+    pub fn validate(
+        &self,
+        relative_path: &Path,
+        category: &str,
+        metadata: &DocumentMetadata,
+        frontmatter: &str,
+    ) -> Vec<Diagnostic> {
+        let category = metadata.category.as_deref().unwrap_or(category);
+        let values = match serde_json::to_value(metadata) {
+            Ok(Value::Object(values)) => values,
+            _ => return Vec::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for (name, rule) in self.rules_for(relative_path, category) {
+            let value = values.get(name).filter(|value| !is_empty(value));
+            let line = key_line(frontmatter, name).unwrap_or(1);
+            let mut report = |message: String| {
+                diagnostics.push(Diagnostic::at_line(line, message).with_severity(rule.severity));
+            };
+
+            let Some(value) = value else {
+                if rule.required {
+                    report(format!("Missing required frontmatter field '{}'", name));
+                }
+                continue;
+            };
+
+            if let Some(field_type) = rule.field_type {
+                if !field_type.matches(value) {
+                    report(format!(
+                        "Frontmatter field '{}' should be {}, found {}",
+                        name,
+                        field_type.name(),
+                        value_kind(value)
+                    ));
+                    continue;
+                }
+            }
+
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                value => vec![value],
+            };
+            for item in items {
+                let text = match item {
+                    Value::String(s) => s.clone(),
+                    Value::Number(_) | Value::Bool(_) => item.to_string(),
+                    _ => continue,
+                };
+
+                if let Some(allowed) = &rule.allowed {
+                    if !allowed.contains(&text) {
+                        report(format!(
+                            "Frontmatter field '{}' has value '{}', expected one of: {}",
+                            name,
+                            text,
+                            allowed.join(", ")
+                        ));
+                    }
+                }
+
+                if let Some(pattern) = &rule.pattern {
+                    if !pattern.is_match(&text) {
+                        report(format!(
+                            "Frontmatter field '{}' value '{}' does not match pattern '{}'",
+                            name,
+                            text,
+                            pattern.as_str()
+                        ));
+                    }
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn compile_fields(fields: BTreeMap<String, FieldSpec>) -> Result<BTreeMap<String, FieldRule>> {
+    fields
+        .into_iter()
+        .map(|(name, spec)| {
+            let pattern = spec
+                .pattern
+                .map(|pattern| {
+                    Regex::new(&pattern).map_err(|e| {
+                        ParaSsgError::ParseError(format!(
+                            "invalid pattern for field '{}': {}",
+                            name, e
+                        ))
+                    })
+                })
+                .transpose()?;
+            let rule = FieldRule {
+                field_type: spec.field_type,
+                required: spec.required,
+                allowed: spec.allowed,
+                pattern,
+                severity: match spec.severity {
+                    SeveritySpec::Error => Severity::Error,
+                    SeveritySpec::Warning => Severity::Warning,
+                },
+            };
+            Ok((name, rule))
+        })
+        .collect()
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Find the line (1-based) where a top-level frontmatter key is defined
+///
+/// Recognises YAML (`key:`), TOML (`key =`) and JSON (`"key":`) syntax.
+pub(crate) fn key_line(frontmatter: &str, key: &str) -> Option<usize> {
+    frontmatter
+        .lines()
+        .position(|line| declares_key(line, key))
+        .map(|index| index + 1)
+}
+
+/// Check if a frontmatter line sets `key` (YAML `key:`, TOML `key =` or JSON `"key":`)
+pub(crate) fn declares_key(line: &str, key: &str) -> bool {
+    let line = line.trim_start();
+    let rest = line
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_prefix(key))
+        .and_then(|rest| rest.strip_prefix('"'))
+        .or_else(|| line.strip_prefix(key));
+    rest.is_some_and(|rest| {
+        let rest = rest.trim_start();
+        rest.starts_with(':') || rest.starts_with('=')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::frontmatter::parse_frontmatter;
+
+    const SCHEMA: &str = r#"
+[fields.title]
+type = "string"
+required = true
+
+[fields.status]
+enum = ["active", "completed", "draft", "archived"]
+
+[fields.tags]
+type = "list"
+pattern = "^[a-z0-9-]+$"
+severity = "warning"
+
+[[rules]]
+category = "projects"
+[rules.fields.project]
+type = "string"
+required = true
+
+[[rules]]
+glob = "**/commands/*.md"
+[rules.fields.command_type]
+required = true
+enum = ["design", "todo", "report"]
+"#;
+
+    fn validate(path: &str, category: &str, content: &str) -> Vec<Diagnostic> {
+        let schema = FrontmatterSchema::from_toml(SCHEMA).unwrap();
+        let (metadata, body) = parse_frontmatter(content).unwrap();
+        let frontmatter = &content[..content.len() - body.len()];
+        schema.validate(Path::new(path), category, &metadata, frontmatter)
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let content = "---\ntitle: Plan\nstatus: active\nproject: forge\n---\nBody";
+        assert!(validate("projects/plan.md", "projects", content).is_empty());
+    }
+
+    #[test]
+    fn test_required_enum_and_pattern_violations() {
+        let content = "---\nstatus: wip\ntags:\n  - Bad Tag\n---\nBody";
+        let diagnostics = validate("resources/note.md", "resources", content);
+
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics.iter().any(|d| d.line == Some(2)
+            && d.is_error()
+            && d.message.contains("'wip', expected one of: active")));
+        assert!(diagnostics.iter().any(|d| d.line == Some(1)
+            && d.message
+                .contains("Missing required frontmatter field 'title'")));
+        assert!(diagnostics.iter().any(|d| d.line == Some(3)
+            && !d.is_error()
+            && d.message.contains("does not match pattern")));
+    }
+
+    #[test]
+    fn test_scoped_rules() {
+        let content = "---\ntitle: Plan\n---\nBody";
+        let diagnostics = validate("projects/plan.md", "projects", content);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'project'"));
+
+        let diagnostics = validate("resources/commands/build.md", "resources", content);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'command_type'"));
+
+        assert!(validate("resources/plan.md", "resources", content).is_empty());
+    }
+
+    #[test]
+    fn test_type_mismatch_in_toml_frontmatter() {
+        let content = "+++\ntitle = \"Plan\"\nproject = 42\n+++\nBody";
+        let diagnostics = validate("projects/plan.md", "projects", content);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert!(diagnostics[0]
+            .message
+            .contains("'project' should be a string, found a number"));
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(FrontmatterSchema::from_toml("[fields.title]\ntype = \"text\"").is_err());
+        assert!(FrontmatterSchema::from_toml("[fields.x]\npattern = \"(\"").is_err());
+        assert!(FrontmatterSchema::from_toml("[[rules]]\nglob = \"[\"").is_err());
+    }
+}
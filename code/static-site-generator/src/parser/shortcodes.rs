@@ -0,0 +1,601 @@
+//! ABOUTME: Shortcodes for reusable content snippets in markdown
+//! ABOUTME: Expands {{< name key="value" >}} tags from theme templates or built-in handlers
+
+use super::diagnostics::Diagnostic;
+use super::markdown::markdown_to_html_with_diagnostics;
+use super::spans::SourceLocation;
+use super::wiki_links::{build_document_lookup, calculate_relative_path, resolve_target};
+use crate::config::ParserConfig;
+use crate::utils::html_escape;
+use crate::{ParaSsgError, Result};
+use lazy_static::lazy_static;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    /// Opening, closing or self-closing shortcode tag
+    static ref SHORTCODE_RE: Regex = Regex::new(
+        r#"\{\{<\s*(/)?\s*([A-Za-z][\w-]*)((?:\s+[\w-]+\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'/>]+))*)\s*(/)?\s*>\}\}"#
+    ).unwrap();
+    /// A `key="value"` shortcode parameter
+    static ref PARAM_RE: Regex =
+        Regex::new(r#"([\w-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'/>]+))"#).unwrap();
+    /// A `{name}` placeholder in a shortcode template
+    static ref PLACEHOLDER_RE: Regex = Regex::new(r"\{([A-Za-z_][\w-]*)\}").unwrap();
+    /// Token standing in for rendered shortcode HTML while markdown is parsed,
+    /// padded with one `\n\u{E002}` per line break in the shortcode
+    static ref TOKEN_RE: Regex = Regex::new(
+        "<p>\u{E000}(\\d+)(?:\n\u{E002})*\u{E001}</p>\n?|\u{E000}(\\d+)(?:\n\u{E002})*\u{E001}"
+    ).unwrap();
+}
+
+/// Directory of shortcode templates inside the theme directory
+pub const SHORTCODE_DIR: &str = "shortcodes";
+
+/// Theme directory used when none is configured, relative to the input directory
+pub const DEFAULT_THEME_DIR: &str = ".theme";
+
+/// Shortcode templates (`<name>.html`) loaded from a theme directory
+#[derive(Debug, Clone, Default)]
+pub struct ShortcodeTemplates {
+    templates: HashMap<String, (PathBuf, String)>,
+}
+
+impl ShortcodeTemplates {
+    /// Load every `.html` file in `dir` as a shortcode named after the file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the directory or a template cannot be read
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut templates = HashMap::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("html") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let source = fs::read_to_string(&path).map_err(|e| {
+                ParaSsgError::Io(std::io::Error::new(
+                    e.kind(),
+                    format!("Failed to read shortcode '{}': {}", path.display(), e),
+                ))
+            })?;
+            templates.insert(name.to_string(), (path.clone(), source));
+        }
+        Ok(Self { templates })
+    }
+
+    /// Add a template from a string
+    pub fn insert(&mut self, name: &str, path: PathBuf, source: &str) {
+        self.templates
+            .insert(name.to_string(), (path, source.to_string()));
+    }
+
+    /// File a template was loaded from
+    pub fn path(&self, name: &str) -> Option<&Path> {
+        self.templates.get(name).map(|(path, _)| path.as_path())
+    }
+
+    /// Number of templates
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Check if there are no templates
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    fn source(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(|(_, source)| source.as_str())
+    }
+}
+
+/// A shortcode tag found in markdown
+#[derive(Debug, Clone)]
+struct ShortcodeTag {
+    range: Range<usize>,
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    params: HashMap<String, String>,
+}
+
+/// Markdown with shortcodes replaced by tokens, and the HTML for each token
+#[derive(Debug, Default)]
+pub struct ExpandedShortcodes {
+    /// Markdown with each rendered shortcode replaced by a token
+    pub content: String,
+
+    /// Rendered HTML, indexed by token number
+    pub rendered: Vec<String>,
+
+    /// Unknown shortcodes, missing parameters and unmatched closing tags
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Names of the shortcodes used in markdown content, outside code
+pub fn shortcode_names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for tag in find_tags(content) {
+        if !tag.closing && !names.contains(&tag.name) {
+            names.push(tag.name);
+        }
+    }
+    names
+}
+
+/// Replace shortcodes with tokens that [`restore_shortcodes`] swaps for HTML
+///
+/// A token spans as many lines as the shortcode it replaces, so line
+/// numbers in the expanded content still match the original.
+///
+/// A shortcode without `/` before `>}}` takes a body when a matching
+/// `{{< /name >}}` follows; the body is rendered as markdown. Shortcodes in
+/// code spans and code blocks are left alone, as are unknown ones, which are
+/// reported with their line and column.
+pub fn expand_shortcodes(
+    content: &str,
+    current_doc_path: &Path,
+    config: &ParserConfig,
+) -> ExpandedShortcodes {
+    let tags = find_tags(content);
+    let mut expanded = ExpandedShortcodes::default();
+    if tags.is_empty() {
+        expanded.content = content.to_string();
+        return expanded;
+    }
+
+    let mut page_lookup = None;
+    let mut last = 0;
+    let mut i = 0;
+    while i < tags.len() {
+        let tag = &tags[i];
+        let SourceLocation { line, column } = SourceLocation::from_offset(content, tag.range.start);
+        if tag.closing {
+            expanded.diagnostics.push(
+                Diagnostic::at_line(
+                    line,
+                    format!("Closing shortcode '{}' has no opening tag", tag.name),
+                )
+                .with_column(column),
+            );
+            i += 1;
+            continue;
+        }
+
+        let closing = (!tag.self_closing)
+            .then(|| find_closing_tag(&tags, i))
+            .flatten();
+        let (end, body, next) = match closing {
+            Some(j) => (
+                tags[j].range.end,
+                Some(&content[tag.range.end..tags[j].range.start]),
+                j + 1,
+            ),
+            None => (tag.range.end, None, i + 1),
+        };
+
+        let mut context = RenderContext {
+            current_doc_path,
+            config,
+            page_lookup: &mut page_lookup,
+            body_line: line + content[tag.range.clone()].matches('\n').count(),
+            diagnostics: &mut expanded.diagnostics,
+        };
+        expanded.content.push_str(&content[last..tag.range.start]);
+        match render_shortcode(tag, body, &mut context) {
+            Some(Ok(html)) => {
+                let padding =
+                    "\n\u{E002}".repeat(content[tag.range.start..end].matches('\n').count());
+                expanded.content.push_str(&format!(
+                    "\u{E000}{}{}\u{E001}",
+                    expanded.rendered.len(),
+                    padding
+                ));
+                expanded.rendered.push(html);
+            }
+            Some(Err(message)) => {
+                expanded.content.push_str(&content[tag.range.start..end]);
+                expanded.diagnostics.push(
+                    Diagnostic::at_line(line, format!("Shortcode '{}': {}", tag.name, message))
+                        .with_column(column),
+                );
+            }
+            None => {
+                expanded.content.push_str(&content[tag.range.start..end]);
+                expanded.diagnostics.push(
+                    Diagnostic::at_line(line, format!("Unknown shortcode '{}'", tag.name))
+                        .with_column(column),
+                );
+            }
+        }
+        last = end;
+        i = next;
+    }
+    expanded.content.push_str(&content[last..]);
+
+    expanded
+}
+
+/// Swap shortcode tokens in rendered HTML for the shortcode output
+///
+/// A shortcode alone in a paragraph replaces the whole paragraph, so
+/// block-level output is not wrapped in `<p>`.
+pub fn restore_shortcodes(html: &str, rendered: &[String]) -> String {
+    if rendered.is_empty() {
+        return html.to_string();
+    }
+    TOKEN_RE
+        .replace_all(html, |captures: &Captures| {
+            let index = captures
+                .get(1)
+                .or_else(|| captures.get(2))
+                .and_then(|m| m.as_str().parse::<usize>().ok());
+            index
+                .and_then(|i| rendered.get(i))
+                .cloned()
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// State shared by shortcode handlers while expanding one document
+struct RenderContext<'a> {
+    current_doc_path: &'a Path,
+    config: &'a ParserConfig,
+    page_lookup: &'a mut Option<HashMap<String, PathBuf>>,
+    body_line: usize,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl RenderContext<'_> {
+    /// Render a shortcode body as markdown
+    ///
+    /// A body that is a single paragraph is rendered without the `<p>`.
+    fn render_body(&mut self, body: &str) -> String {
+        let (html, diagnostics) =
+            markdown_to_html_with_diagnostics(body, self.current_doc_path, self.config)
+                .unwrap_or_default();
+        self.diagnostics
+            .extend(diagnostics.into_iter().map(|mut diagnostic| {
+                diagnostic.line = diagnostic.line.map(|line| line + self.body_line - 1);
+                diagnostic
+            }));
+
+        let trimmed = html.trim();
+        match trimmed
+            .strip_prefix("<p>")
+            .and_then(|inner| inner.strip_suffix("</p>"))
+        {
+            Some(inner) if !inner.contains("<p>") => inner.to_string(),
+            _ => html,
+        }
+    }
+
+    /// Relative URL of a document found by title, filename or path
+    fn page_url(&mut self, target: &str) -> Option<(String, String)> {
+        let records = self.config.query_index.as_deref()?;
+        let lookup = self.page_lookup.get_or_insert_with(|| {
+            let documents: Vec<(PathBuf, String)> = records
+                .iter()
+                .map(|r| (r.output_path.clone(), r.title.clone()))
+                .collect();
+            build_document_lookup(&documents, self.config.unicode_matching)
+        });
+        let path = resolve_target(
+            target.trim_end_matches(".md"),
+            lookup,
+            self.config.unicode_matching,
+        )?;
+        let title = records
+            .iter()
+            .find(|r| r.output_path == path)
+            .map(|r| r.title.clone())
+            .unwrap_or_default();
+        let url = calculate_relative_path(self.current_doc_path, &path).ok()?;
+        Some((url, title))
+    }
+}
+
+/// Render a shortcode from a theme template or a built-in handler
+///
+/// Returns `None` for unknown shortcodes and an error message for invalid
+/// parameters.
+fn render_shortcode(
+    tag: &ShortcodeTag,
+    body: Option<&str>,
+    context: &mut RenderContext<'_>,
+) -> Option<std::result::Result<String, String>> {
+    if let Some(template) = context
+        .config
+        .shortcodes
+        .as_ref()
+        .and_then(|templates| templates.source(&tag.name))
+    {
+        let body_html = body.map(|b| context.render_body(b)).unwrap_or_default();
+        let html = PLACEHOLDER_RE.replace_all(template, |captures: &Captures| match &captures[1] {
+            "body" => body_html.clone(),
+            name => tag
+                .params
+                .get(name)
+                .map(|value| html_escape(value))
+                .unwrap_or_default(),
+        });
+        return Some(Ok(html.trim().to_string()));
+    }
+
+    let param = |name: &str| tag.params.get(name).map(|value| html_escape(value));
+    let required = |name: &str| param(name).ok_or(format!("missing required parameter '{}'", name));
+
+    let result = match tag.name.as_str() {
+        "figure" => required("src").map(|src| {
+            let caption = param("caption")
+                .or_else(|| body.map(|b| context.render_body(b)))
+                .map(|caption| format!("<figcaption>{}</figcaption>", caption))
+                .unwrap_or_default();
+            let width = param("width")
+                .map(|width| format!(r#" width="{}""#, width))
+                .unwrap_or_default();
+            format!(
+                r#"<figure class="shortcode-figure"><img src="{}" alt="{}"{} loading="lazy">{}</figure>"#,
+                src,
+                param("alt").unwrap_or_default(),
+                width,
+                caption
+            )
+        }),
+        "details" => {
+            let summary = param("summary").unwrap_or_else(|| "Details".to_string());
+            let open = if param("open").as_deref() == Some("true") {
+                " open"
+            } else {
+                ""
+            };
+            let content = body.map(|b| context.render_body(b)).unwrap_or_default();
+            Ok(format!(
+                r#"<details class="shortcode-details"{}><summary>{}</summary>{}</details>"#,
+                open, summary, content
+            ))
+        }
+        "video" => match (param("src"), param("youtube")) {
+            (Some(src), _) => Ok(format!(
+                r#"<video class="shortcode-video" src="{}" controls preload="metadata"></video>"#,
+                src
+            )),
+            (None, Some(id)) => Ok(format!(
+                r#"<div class="shortcode-video"><iframe src="https://www.youtube-nocookie.com/embed/{}" title="{}" loading="lazy" allowfullscreen></iframe></div>"#,
+                id,
+                param("title").unwrap_or_else(|| "YouTube video".to_string())
+            )),
+            (None, None) => Err("missing required parameter 'src' or 'youtube'".to_string()),
+        },
+        "badge" => {
+            let text = param("text").or_else(|| body.map(|b| html_escape(b.trim())));
+            text.ok_or("missing required parameter 'text'".to_string())
+                .map(|text| {
+                    let kind: String = param("type")
+                        .unwrap_or_else(|| "default".to_string())
+                        .chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                        .collect();
+                    format!(r#"<span class="badge badge-{}">{}</span>"#, kind, text)
+                })
+        }
+        "page-link" => {
+            let target = tag.params.get("page").cloned();
+            match target {
+                None => Err("missing required parameter 'page'".to_string()),
+                Some(target) => {
+                    let text = param("text").or_else(|| body.map(|b| html_escape(b.trim())));
+                    Ok(match context.page_url(&target) {
+                        Some((url, title)) => format!(
+                            r#"<a href="{}" class="wiki-link">{}</a>"#,
+                            html_escape(&url),
+                            text.unwrap_or_else(|| html_escape(&title))
+                        ),
+                        None => format!(
+                            r#"<span class="wiki-link broken" title="Link target not found: {}">{}</span>"#,
+                            html_escape(&target),
+                            text.unwrap_or_else(|| html_escape(&target))
+                        ),
+                    })
+                }
+            }
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Find shortcode tags outside code spans and code blocks
+fn find_tags(content: &str) -> Vec<ShortcodeTag> {
+    if !content.contains("{{<") {
+        return Vec::new();
+    }
+
+    let code_ranges: Vec<Range<usize>> = Parser::new_ext(content, Options::empty())
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::CodeBlock(_)) | Event::Code(_) => Some(range),
+            _ => None,
+        })
+        .collect();
+
+    SHORTCODE_RE
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let whole = captures.get(0)?;
+            if code_ranges
+                .iter()
+                .any(|range| range.contains(&whole.start()))
+            {
+                return None;
+            }
+            let params = captures
+                .get(3)
+                .map(|m| {
+                    PARAM_RE
+                        .captures_iter(m.as_str())
+                        .map(|param| {
+                            let value = param
+                                .get(2)
+                                .or_else(|| param.get(3))
+                                .or_else(|| param.get(4))
+                                .map(|v| v.as_str().to_string())
+                                .unwrap_or_default();
+                            (param[1].to_lowercase(), value)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(ShortcodeTag {
+                range: whole.range(),
+                name: captures[2].to_lowercase(),
+                closing: captures.get(1).is_some(),
+                self_closing: captures.get(4).is_some(),
+                params,
+            })
+        })
+        .collect()
+}
+
+/// Index of the tag closing `tags[open]`, allowing nested tags of the same name
+fn find_closing_tag(tags: &[ShortcodeTag], open: usize) -> Option<usize> {
+    let name = &tags[open].name;
+    let mut depth = 0;
+    for (j, tag) in tags.iter().enumerate().skip(open + 1) {
+        if tag.name != *name {
+            continue;
+        }
+        if tag.closing {
+            if depth == 0 {
+                return Some(j);
+            }
+            depth -= 1;
+        } else if !tag.self_closing {
+            depth += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::markdown::markdown_to_html_with_diagnostics;
+    use crate::parser::query::QueryRecord;
+    use crate::parser::DocumentMetadata;
+    use std::sync::Arc;
+
+    fn render(markdown: &str, config: &ParserConfig) -> (String, Vec<Diagnostic>) {
+        markdown_to_html_with_diagnostics(markdown, Path::new("notes/page.html"), config).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_shortcodes() {
+        let markdown = "{{< figure src=\"/img/a.png\" alt=\"A chart\" caption=\"Q1\" >}}\n\nStatus: {{< badge type=\"success\" >}}Done{{< /badge >}}\n\n{{< details summary=\"More\" >}}\nHidden **text**\n{{< /details >}}\n\n{{< video youtube=\"abc123\" />}}\n";
+        let (html, diagnostics) = render(markdown, &ParserConfig::default());
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert!(html.starts_with(
+            r#"<figure class="shortcode-figure"><img src="/img/a.png" alt="A chart" loading="lazy"><figcaption>Q1</figcaption></figure>"#
+        ));
+        assert!(html.contains(r#"<p>Status: <span class="badge badge-success">Done</span></p>"#));
+        assert!(html.contains(
+            r#"<details class="shortcode-details"><summary>More</summary>Hidden <strong>text</strong></details>"#
+        ));
+        assert!(html.contains("https://www.youtube-nocookie.com/embed/abc123"));
+    }
+
+    #[test]
+    fn test_unknown_shortcodes_and_code_are_left_alone() {
+        let markdown = "Intro\n\nSee {{< chart data=\"x\" >}} here.\n\n`{{< badge text=\"a\" >}}`\n\n```\n{{< figure >}}\n```\n\n{{< figure alt=\"no src\" >}}\n";
+        let (html, diagnostics) = render(markdown, &ParserConfig::default());
+
+        assert!(html.contains("See {{&lt; chart data="));
+        assert!(html.contains("<code>{{&lt; badge text=\"a\" &gt;}}</code>"));
+        assert!(html.contains("{{&lt; figure &gt;}}\n</code></pre>"));
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "line 3, column 5: Unknown shortcode 'chart'",
+                "line 11, column 1: Shortcode 'figure': missing required parameter 'src'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_after_multiline_shortcode() {
+        let markdown = "Intro\n\n{{< details summary=\"More\" >}}\nHidden\n{{< /details >}}\n\nSum $\\frac{1}$ here\n";
+        let (html, diagnostics) = render(markdown, &ParserConfig::default());
+
+        assert!(html.contains("<details class=\"shortcode-details\">"));
+        assert!(!html.contains('\u{E002}'));
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages.len(), 1);
+        assert!(
+            messages[0].starts_with("line 7, column 5:"),
+            "{:?}",
+            messages
+        );
+    }
+
+    #[test]
+    fn test_theme_template_shortcodes() {
+        let mut templates = ShortcodeTemplates::default();
+        templates.insert(
+            "note-box",
+            PathBuf::from("/theme/shortcodes/note-box.html"),
+            "<div class=\"note-box note-{kind}\">{body}</div>\n",
+        );
+        let config = ParserConfig {
+            shortcodes: Some(Arc::new(templates)),
+            ..ParserConfig::default()
+        };
+
+        let (html, _) = render(
+            "{{< note-box kind=\"<warn>\" >}}\nUse *care*.\n{{< /note-box >}}\n",
+            &config,
+        );
+
+        assert_eq!(
+            html,
+            "<div class=\"note-box note-&lt;warn&gt;\">Use <em>care</em>.</div>"
+        );
+    }
+
+    #[test]
+    fn test_page_link_shortcode() {
+        let record = QueryRecord {
+            source_path: PathBuf::from("/input/resources/spec.md"),
+            relative_path: PathBuf::from("resources/spec.md"),
+            output_path: PathBuf::from("resources/spec.html"),
+            title: "Markdown Spec".to_string(),
+            category: "resources".to_string(),
+            metadata: DocumentMetadata::default(),
+        };
+        let config = ParserConfig {
+            query_index: Some(Arc::new(vec![record])),
+            ..ParserConfig::default()
+        };
+
+        let (html, _) = render(
+            "Read {{< page-link page=\"markdown spec\" >}} and {{< page-link page=\"Missing\" >}}.",
+            &config,
+        );
+
+        assert!(html
+            .contains(r#"<a href="../resources/spec.html" class="wiki-link">Markdown Spec</a>"#));
+        assert!(html.contains(r#"<span class="wiki-link broken" title="Link target not found: Missing">Missing</span>"#));
+    }
+}
@@ -0,0 +1,125 @@
+//! ABOUTME: Source locations for links, embeds, includes and diagnostics
+//! ABOUTME: Maps positions in a processed document body back to the original file
+
+use std::fmt;
+
+/// A 1-based line and column (in characters) in a source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SourceLocation {
+    /// Line number (1-based)
+    pub line: usize,
+
+    /// Column on the line (1-based, in characters)
+    pub column: usize,
+}
+
+impl SourceLocation {
+    /// Create a location from a line and column
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// Locate a byte offset in `text`
+    pub fn from_offset(text: &str, offset: usize) -> Self {
+        let before = &text[..offset.min(text.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Default for SourceLocation {
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Maps lines of a processed document body to lines of the source file
+///
+/// The body a document is rendered from has its frontmatter removed and its
+/// code block includes substituted, so its line numbers differ from the
+/// file's. Lines of an included block map to the line of its fence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineMap {
+    /// File line for each body line (index 0 is body line 1)
+    lines: Vec<usize>,
+}
+
+impl LineMap {
+    /// Map for a body whose lines follow `line_offset` lines of frontmatter
+    pub fn offset(body: &str, line_offset: usize) -> Self {
+        Self {
+            lines: (1..=body.lines().count())
+                .map(|line| line + line_offset)
+                .collect(),
+        }
+    }
+
+    /// Record the file line of the next body line
+    pub fn push(&mut self, file_line: usize) {
+        self.lines.push(file_line);
+    }
+
+    /// File line of a body line; lines past the end continue from the last
+    /// known line
+    pub fn file_line(&self, line: usize) -> usize {
+        match self.lines.get(line.saturating_sub(1)) {
+            Some(&file_line) => file_line,
+            None => match self.lines.last() {
+                Some(&last) => last + line - self.lines.len(),
+                None => line,
+            },
+        }
+    }
+
+    /// Map a location in the body to the file
+    pub fn locate(&self, location: SourceLocation) -> SourceLocation {
+        SourceLocation::new(self.file_line(location.line), location.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_from_offset() {
+        let text = "first\nsecond [[link]]\nthird";
+        assert_eq!(
+            SourceLocation::from_offset(text, text.find("[[").unwrap()),
+            SourceLocation::new(2, 8)
+        );
+        assert_eq!(SourceLocation::from_offset(text, 0).to_string(), "1:1");
+        assert_eq!(
+            SourceLocation::from_offset("é [[x]]", "é ".len()),
+            SourceLocation::new(1, 3)
+        );
+    }
+
+    #[test]
+    fn test_line_map() {
+        let map = LineMap::offset("a\nb\n", 3);
+        assert_eq!(map.file_line(1), 4);
+        assert_eq!(map.file_line(2), 5);
+        assert_eq!(map.file_line(4), 7);
+
+        let mut map = LineMap::default();
+        for line in [4, 5, 5, 5, 6] {
+            map.push(line);
+        }
+        assert_eq!(
+            map.locate(SourceLocation::new(5, 2)),
+            SourceLocation::new(6, 2)
+        );
+        assert_eq!(map.file_line(6), 7);
+        assert_eq!(LineMap::default().file_line(3), 3);
+    }
+}
@@ -0,0 +1,179 @@
+//! ABOUTME: Content statistics for documents
+//! ABOUTME: Word count, reading time, code block and link counts from markdown
+
+use super::wiki_links::parse_wiki_links;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::ops::AddAssign;
+
+/// Reading speed for whitespace-separated words
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Reading speed for CJK text, counted in characters
+const CJK_CHARS_PER_MINUTE: usize = 500;
+
+/// Size statistics for a document's content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentStats {
+    /// Words of prose; each CJK character counts as one word
+    pub word_count: usize,
+
+    /// Estimated reading time in minutes (at least 1 for non-empty content)
+    pub reading_time: usize,
+
+    /// Number of fenced or indented code blocks
+    pub code_blocks: usize,
+
+    /// Number of markdown and wiki links
+    pub links: usize,
+}
+
+impl ContentStats {
+    /// Compute statistics from markdown content (without frontmatter)
+    ///
+    /// Code is excluded from the word count. CJK text has no spaces between
+    /// words, so it is counted and timed by character.
+    pub fn from_markdown(content: &str) -> Self {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        options.insert(Options::ENABLE_TASKLISTS);
+
+        let mut words = 0;
+        let mut cjk_chars = 0;
+        let mut code_blocks = 0;
+        let mut links = parse_wiki_links(content).len();
+        let mut in_code = false;
+
+        for event in Parser::new_ext(content, options) {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => {
+                    code_blocks += 1;
+                    in_code = true;
+                }
+                Event::End(TagEnd::CodeBlock) => in_code = false,
+                Event::Start(Tag::Link { .. }) => links += 1,
+                Event::Text(text) if !in_code => {
+                    let (w, c) = count_words(&text);
+                    words += w;
+                    cjk_chars += c;
+                }
+                _ => {}
+            }
+        }
+
+        let word_count = words + cjk_chars;
+        let reading_time = if word_count == 0 {
+            0
+        } else {
+            let seconds = words * 60 / WORDS_PER_MINUTE + cjk_chars * 60 / CJK_CHARS_PER_MINUTE;
+            seconds.div_ceil(60).max(1)
+        };
+
+        Self {
+            word_count,
+            reading_time,
+            code_blocks,
+            links,
+        }
+    }
+}
+
+/// Content statistics summed over a group of documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryStats {
+    /// Number of documents in the group
+    pub documents: usize,
+
+    /// Sum of the documents' statistics
+    pub totals: ContentStats,
+}
+
+impl CategoryStats {
+    /// Add a document's statistics to the group
+    pub fn add(&mut self, stats: ContentStats) {
+        self.documents += 1;
+        self.totals += stats;
+    }
+}
+
+impl AddAssign for ContentStats {
+    fn add_assign(&mut self, other: Self) {
+        self.word_count += other.word_count;
+        self.reading_time += other.reading_time;
+        self.code_blocks += other.code_blocks;
+        self.links += other.links;
+    }
+}
+
+/// Count (words, CJK characters) in a run of text
+fn count_words(text: &str) -> (usize, usize) {
+    let mut words = 0;
+    let mut cjk_chars = 0;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            cjk_chars += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else if c.is_whitespace() {
+            in_word = false;
+        }
+        // Other punctuation (apostrophes, hyphens) stays within the current word
+    }
+
+    (words, cjk_chars)
+}
+
+/// Check if a character belongs to a CJK script written without spaces
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'     // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}'   // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2A6DF}' // CJK Extension B
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_for_english() {
+        let content = "# Title\n\nIt's a well-known fact, see [docs](https://example.com) and [[Other Note]].\n\n```rust\nfn ignored_words() {}\n```\n";
+        let stats = ContentStats::from_markdown(content);
+
+        assert_eq!(stats.word_count, 10);
+        assert_eq!(stats.reading_time, 1);
+        assert_eq!(stats.code_blocks, 1);
+        assert_eq!(stats.links, 2);
+    }
+
+    #[test]
+    fn test_stats_for_cjk() {
+        let stats = ContentStats::from_markdown("東京は日本の首都です。Tokyo is big.");
+
+        // 10 CJK characters plus 3 English words
+        assert_eq!(stats.word_count, 13);
+    }
+
+    #[test]
+    fn test_reading_time() {
+        assert_eq!(ContentStats::from_markdown("").reading_time, 0);
+
+        let long = "word ".repeat(1000);
+        assert_eq!(ContentStats::from_markdown(&long).reading_time, 5);
+
+        let cjk = "字".repeat(1000);
+        assert_eq!(ContentStats::from_markdown(&cjk).reading_time, 2);
+    }
+}
@@ -0,0 +1,178 @@
+//! ABOUTME: Tag hierarchy built from document tags
+//! ABOUTME: Nests tags like area/health under their parents for the tag index and tag pages
+
+use super::document::Document;
+use super::hashtags::tag_slug;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Output directory of the tag index and tag pages
+pub const TAGS_DIR: &str = "tags";
+
+/// A tag and the tags nested below it
+#[derive(Debug, Clone)]
+pub struct TagNode<'a> {
+    /// Last segment of the tag as first written (`health` for `area/health`)
+    pub name: String,
+    /// Full tag as first written (`area/health`)
+    pub tag: String,
+    /// URL slug, with `/` between nesting levels (`area/health`)
+    pub slug: String,
+    /// Documents with exactly this tag
+    pub documents: Vec<&'a Document>,
+    /// Nested tags, sorted by slug
+    pub children: Vec<TagNode<'a>>,
+}
+
+impl<'a> TagNode<'a> {
+    /// Output directory of the tag's page, relative to the output root
+    pub fn dir(&self) -> PathBuf {
+        Path::new(TAGS_DIR).join(&self.slug)
+    }
+
+    /// Documents with this tag or one nested below it, each once
+    pub fn all_documents(&self) -> Vec<&'a Document> {
+        let mut documents = self.documents.clone();
+        for child in &self.children {
+            for doc in child.all_documents() {
+                if !documents.iter().any(|d| std::ptr::eq(*d, doc)) {
+                    documents.push(doc);
+                }
+            }
+        }
+        documents
+    }
+
+    /// Number of documents with this tag or one nested below it
+    pub fn count(&self) -> usize {
+        self.all_documents().len()
+    }
+
+    /// This tag followed by every tag nested below it, depth first
+    pub fn walk(&self) -> Vec<&TagNode<'a>> {
+        let mut nodes = vec![self];
+        for child in &self.children {
+            nodes.extend(child.walk());
+        }
+        nodes
+    }
+}
+
+/// A tag while the hierarchy is being built
+struct TagBuilder<'a> {
+    name: String,
+    tag: String,
+    slug: String,
+    documents: Vec<&'a Document>,
+    children: BTreeMap<String, TagBuilder<'a>>,
+}
+
+impl<'a> TagBuilder<'a> {
+    fn build(self) -> TagNode<'a> {
+        TagNode {
+            name: self.name,
+            tag: self.tag,
+            slug: self.slug,
+            documents: self.documents,
+            children: self.children.into_values().map(TagBuilder::build).collect(),
+        }
+    }
+}
+
+/// Build the tag hierarchy of `documents`, sorted by slug
+///
+/// Tags are matched by slug, so `Rust` and `rust` are the same tag. A
+/// nested tag creates its parents even when no document uses them directly.
+pub fn tag_tree<'a>(documents: impl IntoIterator<Item = &'a Document>) -> Vec<TagNode<'a>> {
+    let mut roots: BTreeMap<String, TagBuilder<'a>> = BTreeMap::new();
+
+    for doc in documents {
+        for tag in &doc.metadata.tags {
+            // Pair each segment as written with its slug, dropping empty ones
+            let segments: Vec<(&str, String)> = tag
+                .split('/')
+                .map(|segment| (segment.trim(), tag_slug(segment)))
+                .filter(|(_, slug)| !slug.is_empty())
+                .collect();
+
+            let mut level = &mut roots;
+            let mut node = None;
+            for depth in 0..segments.len() {
+                let (name, slug) = &segments[depth];
+                let current = level.entry(slug.clone()).or_insert_with(|| TagBuilder {
+                    name: name.to_string(),
+                    tag: segments[..=depth]
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    slug: segments[..=depth]
+                        .iter()
+                        .map(|(_, slug)| slug.as_str())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    documents: Vec::new(),
+                    children: BTreeMap::new(),
+                });
+                if depth + 1 == segments.len() {
+                    node = Some(current);
+                    break;
+                }
+                level = &mut current.children;
+            }
+
+            if let Some(node) = node {
+                if !node.documents.iter().any(|d| std::ptr::eq(*d, doc)) {
+                    node.documents.push(doc);
+                }
+            }
+        }
+    }
+
+    roots.into_values().map(TagBuilder::build).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(path: &str, tags: &[&str]) -> Document {
+        let mut doc = Document::new(
+            PathBuf::from(path),
+            PathBuf::from(path),
+            "areas".to_string(),
+        );
+        doc.metadata.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        doc
+    }
+
+    #[test]
+    fn test_tag_tree() {
+        let documents = vec![
+            document("areas/sleep.md", &["Area/Health", "rust"]),
+            document("areas/gym.md", &["area/health/fitness", "area/health"]),
+            document("areas/lib.md", &["Rust"]),
+        ];
+        let tree = tag_tree(&documents);
+
+        assert_eq!(tree.len(), 2);
+        let area = &tree[0];
+        assert_eq!(area.slug, "area");
+        assert_eq!(area.name, "Area");
+        assert!(area.documents.is_empty());
+        assert_eq!(area.count(), 2);
+
+        let health = &area.children[0];
+        assert_eq!(health.tag, "Area/Health");
+        assert_eq!(health.slug, "area/health");
+        assert_eq!(health.dir(), PathBuf::from("tags/area/health"));
+        assert_eq!(health.documents.len(), 2);
+        assert_eq!(health.children[0].slug, "area/health/fitness");
+
+        let slugs: Vec<&str> = area.walk().iter().map(|n| n.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["area", "area/health", "area/health/fitness"]);
+
+        assert_eq!(tree[1].name, "rust");
+        assert_eq!(tree[1].count(), 2);
+    }
+}
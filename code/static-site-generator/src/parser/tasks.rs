@@ -0,0 +1,183 @@
+//! ABOUTME: Task list item extraction from markdown
+//! ABOUTME: Collects `- [ ]` items with their heading, checked state and due date
+
+use super::dates::FrontmatterDate;
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    /// Obsidian Tasks due date: `📅 2025-07-01`
+    static ref DUE_EMOJI_RE: Regex = Regex::new(r"📅\s*(\d{4}-\d{2}-\d{2})").unwrap();
+    /// Due date annotation: `@due(2025-07-01)`
+    static ref DUE_ANNOTATION_RE: Regex = Regex::new(r"@due\(([^)]*)\)").unwrap();
+    /// Wiki link, keeping the display text or target for task text
+    static ref WIKI_LINK_TEXT_RE: Regex =
+        Regex::new(r"\[\[(?:[^\[\]|]+\|)?([^\[\]]+)\]\]").unwrap();
+}
+
+/// A GFM task list item (`- [ ] text` or `- [x] text`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    /// Item text with due date markers removed
+    pub text: String,
+
+    /// Whether the item is checked
+    pub checked: bool,
+
+    /// Text of the nearest heading above the item
+    pub heading: Option<String>,
+
+    /// Due date from `📅 YYYY-MM-DD` or `@due(...)`
+    pub due: Option<NaiveDate>,
+}
+
+/// Completed and total task counts for a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskProgress {
+    /// Number of checked tasks
+    pub completed: usize,
+
+    /// Number of tasks
+    pub total: usize,
+}
+
+impl TaskProgress {
+    /// Count the completed tasks in a list
+    pub fn from_tasks(tasks: &[Task]) -> Self {
+        Self {
+            completed: tasks.iter().filter(|t| t.checked).count(),
+            total: tasks.len(),
+        }
+    }
+
+    /// Completion as a whole percentage (0 when there are no tasks)
+    pub fn percent(&self) -> usize {
+        (self.completed * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+/// Extract task list items from markdown content (without frontmatter)
+///
+/// Text of nested items belongs to the nested task only. Items in code
+/// blocks are not tasks.
+pub fn extract_tasks(content: &str) -> Vec<Task> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut tasks = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut heading_text: Option<String> = None;
+    // Tasks being collected, one per open list item (`None` for plain items)
+    let mut items: Vec<Option<(bool, String)>> = Vec::new();
+
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading_text = Some(String::new()),
+            Event::End(TagEnd::Heading(_)) => {
+                heading = heading_text.take().map(|t| t.trim().to_string());
+            }
+            Event::Start(Tag::Item) => items.push(None),
+            Event::TaskListMarker(checked) => {
+                if let Some(item) = items.last_mut() {
+                    *item = Some((checked, String::new()));
+                }
+            }
+            Event::Start(Tag::List(_)) => {
+                // A nested list ends the text of its parent task
+                if let Some(Some((checked, text))) = items.last_mut().map(Option::take) {
+                    tasks.push(make_task(checked, &text, heading.clone()));
+                }
+            }
+            Event::End(TagEnd::Item) => {
+                if let Some(Some((checked, text))) = items.pop() {
+                    tasks.push(make_task(checked, &text, heading.clone()));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading_text) = heading_text.as_mut() {
+                    heading_text.push_str(&text);
+                } else if let Some(Some((_, task_text))) = items.last_mut() {
+                    task_text.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(Some((_, task_text))) = items.last_mut() {
+                    task_text.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tasks
+}
+
+/// Build a task from its collected text, pulling out the due date
+fn make_task(checked: bool, text: &str, heading: Option<String>) -> Task {
+    let due = DUE_EMOJI_RE
+        .captures(text)
+        .and_then(|captures| NaiveDate::parse_from_str(&captures[1], "%Y-%m-%d").ok())
+        .or_else(|| {
+            DUE_ANNOTATION_RE
+                .captures(text)
+                .and_then(|captures| FrontmatterDate::parse(&captures[1]))
+                .map(|date| date.date_naive())
+        });
+
+    let text = DUE_EMOJI_RE.replace_all(text, "");
+    let text = DUE_ANNOTATION_RE.replace_all(&text, "");
+    let text = WIKI_LINK_TEXT_RE.replace_all(&text, "$1");
+
+    Task {
+        text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+        checked,
+        heading,
+        due,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tasks() {
+        let content = "# Launch\n\n- [ ] Write [[Release Notes|notes]] 📅 2025-07-01\n- [x] Tag `v1.0`\n- Not a task\n\n## Follow-up\n\n1. [ ] Announce @due(2025-07-15)\n\n```\n- [ ] in code\n```\n";
+        let tasks = extract_tasks(content);
+
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].text, "Write notes");
+        assert!(!tasks[0].checked);
+        assert_eq!(tasks[0].heading.as_deref(), Some("Launch"));
+        assert_eq!(tasks[0].due, NaiveDate::from_ymd_opt(2025, 7, 1));
+        assert_eq!(tasks[1].text, "Tag v1.0");
+        assert!(tasks[1].checked);
+        assert_eq!(tasks[2].text, "Announce");
+        assert_eq!(tasks[2].heading.as_deref(), Some("Follow-up"));
+        assert_eq!(tasks[2].due, NaiveDate::from_ymd_opt(2025, 7, 15));
+    }
+
+    #[test]
+    fn test_nested_tasks() {
+        let content = "- [ ] Parent\n  - [x] Child one\n  - [ ] Child two\n";
+        let tasks = extract_tasks(content);
+
+        let texts: Vec<_> = tasks.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["Parent", "Child one", "Child two"]);
+        assert_eq!(TaskProgress::from_tasks(&tasks).completed, 1);
+    }
+
+    #[test]
+    fn test_task_progress() {
+        let progress = TaskProgress {
+            completed: 1,
+            total: 3,
+        };
+        assert_eq!(progress.percent(), 33);
+        assert_eq!(TaskProgress::default().percent(), 0);
+    }
+}
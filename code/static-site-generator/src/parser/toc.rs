@@ -0,0 +1,159 @@
+//! ABOUTME: Table of contents extraction from document headings
+//! ABOUTME: Lists headings with unique anchor IDs for navigation and the content API
+
+use super::markdown::{generate_heading_id, markdown_options};
+use super::wiki_links::parse_wiki_links;
+use crate::config::UnicodeMatching;
+use pulldown_cmark::{CowStr, Event, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A heading in a document's table of contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    /// Heading level, 1 to 6
+    pub level: u8,
+    /// Heading text
+    pub title: String,
+    /// Anchor ID from the heading text
+    pub id: String,
+}
+
+/// Headings of a markdown document, in order
+///
+/// IDs come from [`generate_heading_id`]; repeated headings get `-1`, `-2`
+/// suffixes so they stay unique. They match the `id` attributes
+/// [`add_heading_ids`] puts on the rendered headings.
+pub fn table_of_contents(markdown: &str, matching: UnicodeMatching) -> Vec<TocEntry> {
+    let events: Vec<Event> = Parser::new_ext(markdown, markdown_options()).collect();
+    let mut ids = HeadingIds::default();
+    headings(&events)
+        .into_iter()
+        .map(|heading| TocEntry {
+            level: heading.level,
+            id: ids.next(&heading.title, matching),
+            title: heading.title,
+        })
+        .collect()
+}
+
+/// Set an `id` on every heading without one, as listed by [`table_of_contents`]
+pub(crate) fn add_heading_ids(mut events: Vec<Event>, matching: UnicodeMatching) -> Vec<Event> {
+    let mut ids = HeadingIds::default();
+    for heading in headings(&events) {
+        let id = ids.next(&heading.title, matching);
+        if let Event::Start(Tag::Heading { id: heading_id, .. }) = &mut events[heading.start] {
+            heading_id.get_or_insert(CowStr::from(id));
+        }
+    }
+    events
+}
+
+/// A heading found in a markdown event stream
+struct Heading {
+    /// Index of its `Start` event
+    start: usize,
+    level: u8,
+    /// Plain text, with wiki links replaced by their display text
+    title: String,
+}
+
+/// Headings in an event stream, with their text as plain text
+///
+/// Inline HTML is skipped, so a wiki link gives the same text whether it is
+/// still `[[Page]]` or already rendered as `<a …>Page</a>`.
+fn headings(events: &[Event]) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut heading: Option<Heading> = None;
+
+    for (index, event) in events.iter().enumerate() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some(Heading {
+                    start: index,
+                    level: *level as u8,
+                    title: String::new(),
+                });
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = &mut heading {
+                    heading.title.push_str(text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(heading) = &mut heading {
+                    heading.title.push(' ');
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(mut heading) = heading.take() {
+                    heading.title = wiki_link_text(heading.title.trim());
+                    headings.push(heading);
+                }
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// Replace `[[Page|text]]` wiki links with the text they render as
+fn wiki_link_text(text: &str) -> String {
+    let mut result = text.to_string();
+    for link in parse_wiki_links(text).iter().rev() {
+        let display = link.display.as_ref().unwrap_or(&link.target);
+        result.replace_range(link.start..link.end, display);
+    }
+    result
+}
+
+/// Unique heading IDs, handed out in document order
+#[derive(Default)]
+struct HeadingIds {
+    used: HashMap<String, usize>,
+}
+
+impl HeadingIds {
+    fn next(&mut self, title: &str, matching: UnicodeMatching) -> String {
+        let base = generate_heading_id(title, matching);
+        let count = self.used.entry(base.clone()).or_insert(0);
+        let id = if *count == 0 {
+            base
+        } else {
+            format!("{}-{}", base, count)
+        };
+        *count += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_of_contents() {
+        let markdown = "# Intro\n\n## Set *up* & `run`\n\ntext\n\n```\n# not a heading\n```\n\n## Intro\n\nSetext\n------\n";
+        let toc = table_of_contents(markdown, UnicodeMatching::default());
+
+        assert_eq!(toc.len(), 4);
+        assert_eq!(toc[0].id, "intro");
+        assert_eq!(toc[1].level, 2);
+        assert_eq!(toc[1].title, "Set up & run");
+        assert_eq!(toc[1].id, "set-up-_-run");
+        assert_eq!(toc[2].id, "intro-1");
+        assert_eq!(toc[3].title, "Setext");
+    }
+
+    #[test]
+    fn test_wiki_links_in_headings() {
+        let toc = table_of_contents(
+            "## See [[Page]]\n\n## Read [[Spec|the spec]]\n",
+            UnicodeMatching::default(),
+        );
+
+        assert_eq!(toc[0].title, "See Page");
+        assert_eq!(toc[0].id, "see-page");
+        assert_eq!(toc[1].id, "read-the-spec");
+    }
+}
@@ -0,0 +1,78 @@
+//! ABOUTME: Unicode normalization for link lookup, heading IDs and search text
+//! ABOUTME: Applies NFC, full-width folding, case folding and optional diacritic removal
+
+use crate::config::UnicodeMatching;
+use caseless::default_case_fold_str;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalize text so that equivalent spellings compare equal
+///
+/// All modes apply NFC, so decomposed (macOS) and composed text match.
+/// `Normalized` also folds full-width forms to ASCII (`ＡＢＣ！` to `abc!`)
+/// and applies Unicode case folding (`Straße` to `strasse`);
+/// `FoldDiacritics` additionally removes accents (`Café` to `cafe`).
+pub fn fold_text(text: &str, matching: UnicodeMatching) -> String {
+    let text: String = text.nfc().collect();
+    match matching {
+        UnicodeMatching::Strict => text,
+        UnicodeMatching::Normalized => default_case_fold_str(&fold_width(&text)),
+        UnicodeMatching::FoldDiacritics => {
+            strip_diacritics(&default_case_fold_str(&fold_width(&text)))
+        }
+    }
+}
+
+/// Lowercase text for case-insensitive comparison under the given mode
+///
+/// Strict matching keeps the exact text for lookups, but search and heading
+/// IDs are still case-insensitive; this lowercases NFC text in that case.
+pub fn fold_case_insensitive(text: &str, matching: UnicodeMatching) -> String {
+    match matching {
+        UnicodeMatching::Strict => fold_text(text, matching).to_lowercase(),
+        _ => fold_text(text, matching),
+    }
+}
+
+/// Map full-width ASCII variants and the ideographic space to ASCII
+fn fold_width(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            '\u{3000}' => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Remove combining marks, keeping the base letters
+fn strip_diacritics(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .nfc()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_text_modes() {
+        let nfd = "Cafe\u{301}";
+        assert_eq!(fold_text(nfd, UnicodeMatching::Strict), "Café");
+        assert_eq!(fold_text(nfd, UnicodeMatching::Normalized), "café");
+        assert_eq!(fold_text(nfd, UnicodeMatching::FoldDiacritics), "cafe");
+
+        assert_eq!(fold_text("Straße", UnicodeMatching::Normalized), "strasse");
+        assert_eq!(
+            fold_text("ＰＡＲＡ！\u{3000}Notes", UnicodeMatching::Normalized),
+            "para! notes"
+        );
+        assert_eq!(fold_text("Ｎotes", UnicodeMatching::Strict), "Ｎotes");
+        assert_eq!(
+            fold_case_insensitive("Ｎotes", UnicodeMatching::Strict),
+            "ｎotes"
+        );
+    }
+}
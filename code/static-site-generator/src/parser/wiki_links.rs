@@ -1,6 +1,11 @@
 //! ABOUTME: Wiki link parsing and resolution functionality
 //! ABOUTME: Handles [[document-name]] style links from Obsidian
 
+use super::diagnostics::Diagnostic;
+use super::spans::SourceLocation;
+use super::unicode::fold_text;
+use crate::config::UnicodeMatching;
+use crate::utils::html_escape;
 use crate::{ParaSsgError, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -25,6 +30,11 @@ pub struct WikiLink {
     pub start: usize,
     /// Ending position in the source text
     pub end: usize,
+    /// Line and column of the opening brackets (or `!` of an embed), mapped
+    /// to the source file once the document is parsed
+    pub location: SourceLocation,
+    /// Whether the link is an embed (`![[target]]`)
+    pub embed: bool,
 }
 
 /// Result of link resolution
@@ -40,7 +50,8 @@ pub struct ResolvedLink {
 
 /// Parse wiki links from content
 ///
-/// Returns all wiki links found in the content with their positions
+/// Returns all wiki links found in the content with their positions.
+/// Locations are relative to `content`.
 pub fn parse_wiki_links(content: &str) -> Vec<WikiLink> {
     WIKI_LINK_RE
         .captures_iter(content)
@@ -49,12 +60,17 @@ pub fn parse_wiki_links(content: &str) -> Vec<WikiLink> {
             let target = cap.get(1).unwrap().as_str().trim().to_string();
             let display = cap.get(2).map(|m| m.as_str().trim().to_string());
 
+            let embed = content[..full_match.start()].ends_with('!');
+            let location_offset = full_match.start() - usize::from(embed);
+
             WikiLink {
                 full_match: full_match.as_str().to_string(),
                 target,
                 display,
                 start: full_match.start(),
                 end: full_match.end(),
+                location: SourceLocation::from_offset(content, location_offset),
+                embed,
             }
         })
         .collect()
@@ -62,18 +78,22 @@ pub fn parse_wiki_links(content: &str) -> Vec<WikiLink> {
 
 /// Build a lookup map for document resolution
 ///
-/// Maps normalized titles/filenames to their paths
-pub fn build_document_lookup(documents: &[(PathBuf, String)]) -> HashMap<String, PathBuf> {
+/// Maps normalized titles/filenames to their paths. Targets must be resolved
+/// with the same `matching` mode.
+pub fn build_document_lookup(
+    documents: &[(PathBuf, String)],
+    matching: UnicodeMatching,
+) -> HashMap<String, PathBuf> {
     let mut lookup = HashMap::new();
 
     for (path, title) in documents {
         // Add by title (normalized)
-        let normalized_title = normalize_for_lookup(title);
+        let normalized_title = normalize_for_lookup(title, matching);
         lookup.insert(normalized_title, path.clone());
 
         // Add by filename without extension (normalized)
         if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-            let normalized_stem = normalize_for_lookup(stem);
+            let normalized_stem = normalize_for_lookup(stem, matching);
             // Only insert if not already present (title takes precedence)
             lookup
                 .entry(normalized_stem)
@@ -84,7 +104,7 @@ pub fn build_document_lookup(documents: &[(PathBuf, String)]) -> HashMap<String,
         let mut path_without_ext = path.clone();
         path_without_ext.set_extension("");
         if let Some(path_str) = path_without_ext.to_str() {
-            let normalized_path = normalize_for_lookup(path_str);
+            let normalized_path = normalize_for_lookup(path_str, matching);
             lookup
                 .entry(normalized_path)
                 .or_insert_with(|| path.clone());
@@ -94,9 +114,15 @@ pub fn build_document_lookup(documents: &[(PathBuf, String)]) -> HashMap<String,
     lookup
 }
 
-/// Normalize a string for case-insensitive lookup
-fn normalize_for_lookup(s: &str) -> String {
-    s.to_lowercase()
+/// Normalize a string for lookup
+///
+/// Strict matching only applies NFC; otherwise the text is folded (see
+/// [`fold_text`]) and `-`, `_` and whitespace runs become single spaces.
+pub(crate) fn normalize_for_lookup(s: &str, matching: UnicodeMatching) -> String {
+    if matching == UnicodeMatching::Strict {
+        return fold_text(s, matching);
+    }
+    fold_text(s, matching)
         .replace('-', " ")
         .replace('_', " ")
         .split_whitespace()
@@ -110,12 +136,12 @@ fn normalize_for_lookup(s: &str) -> String {
 pub fn resolve_wiki_links(
     wiki_links: Vec<WikiLink>,
     document_lookup: &HashMap<String, PathBuf>,
+    matching: UnicodeMatching,
 ) -> Vec<ResolvedLink> {
     wiki_links
         .into_iter()
         .map(|wiki_link| {
-            let normalized_target = normalize_for_lookup(&wiki_link.target);
-            let resolved_path = document_lookup.get(&normalized_target).cloned();
+            let resolved_path = resolve_target(&wiki_link.target, document_lookup, matching);
             let is_broken = resolved_path.is_none();
 
             ResolvedLink {
@@ -127,6 +153,17 @@ pub fn resolve_wiki_links(
         .collect()
 }
 
+/// Resolve a single link target (title, filename or path) to a document path
+pub fn resolve_target(
+    target: &str,
+    document_lookup: &HashMap<String, PathBuf>,
+    matching: UnicodeMatching,
+) -> Option<PathBuf> {
+    document_lookup
+        .get(&normalize_for_lookup(target, matching))
+        .cloned()
+}
+
 /// Replace wiki links in content with HTML links
 ///
 /// Takes the original content and resolved links, returns content with HTML links
@@ -175,7 +212,7 @@ pub fn replace_wiki_links_with_html(
 }
 
 /// Calculate relative path between two document paths
-fn calculate_relative_path(from: &Path, to: &Path) -> Result<String> {
+pub(crate) fn calculate_relative_path(from: &Path, to: &Path) -> Result<String> {
     // Convert to HTML paths (always use forward slashes)
     let from_dir = from.parent().unwrap_or_else(|| Path::new(""));
 
@@ -215,15 +252,6 @@ fn calculate_relative_path(from: &Path, to: &Path) -> Result<String> {
         .replace('\\', "/"))
 }
 
-/// HTML escape a string
-fn html_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#39;")
-}
-
 /// Extract broken links from resolved links
 pub fn get_broken_links(resolved_links: &[ResolvedLink]) -> Vec<&WikiLink> {
     resolved_links
@@ -233,6 +261,21 @@ pub fn get_broken_links(resolved_links: &[ResolvedLink]) -> Vec<&WikiLink> {
         .collect()
 }
 
+/// Warnings for broken links and embeds, at their source locations
+pub fn broken_link_diagnostics(resolved_links: &[ResolvedLink]) -> Vec<Diagnostic> {
+    get_broken_links(resolved_links)
+        .into_iter()
+        .map(|link| {
+            let message = if link.embed {
+                format!("Broken embed !{}", link.full_match)
+            } else {
+                format!("Broken link {}", link.full_match)
+            };
+            Diagnostic::at_line(link.location.line, message).with_column(link.location.column)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,12 +312,77 @@ mod tests {
         assert_eq!(links[2].target, "link3");
     }
 
+    #[test]
+    fn test_wiki_link_locations() {
+        let content = "Intro\n\nSee [[first]] and ![[diagram.png]].\n";
+        let links = parse_wiki_links(content);
+
+        assert_eq!(links[0].location, SourceLocation::new(3, 5));
+        assert!(!links[0].embed);
+        assert_eq!(links[1].location, SourceLocation::new(3, 19));
+        assert!(links[1].embed);
+
+        let resolved = resolve_wiki_links(links, &HashMap::new(), UnicodeMatching::default());
+        let messages: Vec<_> = broken_link_diagnostics(&resolved)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "line 3, column 5: Broken link [[first]]",
+                "line 3, column 19: Broken embed ![[diagram.png]]",
+            ]
+        );
+    }
+
     #[test]
     fn test_normalize_for_lookup() {
-        assert_eq!(normalize_for_lookup("Test Document"), "test document");
-        assert_eq!(normalize_for_lookup("test-document"), "test document");
-        assert_eq!(normalize_for_lookup("test_document"), "test document");
-        assert_eq!(normalize_for_lookup("Test  Document"), "test document");
+        let matching = UnicodeMatching::default();
+        assert_eq!(
+            normalize_for_lookup("Test Document", matching),
+            "test document"
+        );
+        assert_eq!(
+            normalize_for_lookup("test-document", matching),
+            "test document"
+        );
+        assert_eq!(
+            normalize_for_lookup("test_document", matching),
+            "test document"
+        );
+        assert_eq!(
+            normalize_for_lookup("Test  Document", matching),
+            "test document"
+        );
+        assert_eq!(
+            normalize_for_lookup("Ｔｅｓｔ　Document", matching),
+            "test document"
+        );
+    }
+
+    #[test]
+    fn test_unicode_lookup_matching() {
+        let documents = vec![
+            (
+                PathBuf::from("areas/Cafe\u{301}.md"),
+                "Cafe\u{301}".to_string(),
+            ),
+            (PathBuf::from("areas/Straße.md"), "Straße".to_string()),
+        ];
+
+        // NFD file names match NFC links, and case folding maps ß to ss
+        let lookup = build_document_lookup(&documents, UnicodeMatching::Normalized);
+        assert!(resolve_target("café", &lookup, UnicodeMatching::Normalized).is_some());
+        assert!(resolve_target("STRASSE", &lookup, UnicodeMatching::Normalized).is_some());
+        assert!(resolve_target("Cafe", &lookup, UnicodeMatching::Normalized).is_none());
+
+        let lookup = build_document_lookup(&documents, UnicodeMatching::FoldDiacritics);
+        assert!(resolve_target("Cafe", &lookup, UnicodeMatching::FoldDiacritics).is_some());
+
+        let lookup = build_document_lookup(&documents, UnicodeMatching::Strict);
+        assert!(resolve_target("Café", &lookup, UnicodeMatching::Strict).is_some());
+        assert!(resolve_target("café", &lookup, UnicodeMatching::Strict).is_none());
     }
 
     #[test]
@@ -290,7 +398,7 @@ mod tests {
             ),
         ];
 
-        let lookup = build_document_lookup(&documents);
+        let lookup = build_document_lookup(&documents, UnicodeMatching::default());
 
         // Should find by title
         assert!(lookup.contains_key("test document"));
@@ -314,6 +422,8 @@ mod tests {
                 display: None,
                 start: 0,
                 end: 16,
+                location: SourceLocation::default(),
+                embed: false,
             },
             WikiLink {
                 full_match: "[[nonexistent]]".to_string(),
@@ -321,13 +431,15 @@ mod tests {
                 display: None,
                 start: 20,
                 end: 35,
+                location: SourceLocation::default(),
+                embed: false,
             },
         ];
 
         let mut lookup = HashMap::new();
         lookup.insert("test document".to_string(), PathBuf::from("test.md"));
 
-        let resolved = resolve_wiki_links(wiki_links, &lookup);
+        let resolved = resolve_wiki_links(wiki_links, &lookup, UnicodeMatching::default());
 
         assert_eq!(resolved.len(), 2);
         assert!(!resolved[0].is_broken);
@@ -380,6 +492,8 @@ mod tests {
                     display: None,
                     start: 6,
                     end: 14,
+                    location: SourceLocation::default(),
+                    embed: false,
                 },
                 resolved_path: Some(PathBuf::from("test.html")),
                 is_broken: false,
@@ -391,6 +505,8 @@ mod tests {
                     display: Some("display".to_string()),
                     start: 22,
                     end: 40,
+                    location: SourceLocation::default(),
+                    embed: false,
                 },
                 resolved_path: None,
                 is_broken: true,
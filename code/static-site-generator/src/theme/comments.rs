@@ -1,14 +1,17 @@
 // ABOUTME: This module provides GitHub Issues-based commenting functionality for blog posts
 // ABOUTME: It generates a JavaScript widget that loads and displays comments from GitHub
 
+use super::i18n::Messages;
+use crate::parser::DEFAULT_LANGUAGE;
+
 /// Comments widget template with GitHub API integration
 pub const COMMENTS_SCRIPT: &str = r##"
 <div id="blog-comments" class="comments-section">
-    <h2>Comments</h2>
+    <h2>{msg_comments}</h2>
     <div id="comments-list">
-        <div class="loading">Loading comments...</div>
+        <div class="loading">{msg_loading_comments}</div>
     </div>
-    <a id="add-comment-btn" href="#" class="btn-primary">Add Comment on GitHub</a>
+    <a id="add-comment-btn" href="#" class="btn-primary">{msg_add_comment}</a>
 </div>
 <script>
 (function() {
@@ -96,7 +99,25 @@ pub fn render_comments_widget(
     repo_name: &str,
     issue_number: Option<&str>,
 ) -> String {
-    COMMENTS_SCRIPT
+    render_localized_comments_widget(
+        repo_owner,
+        repo_name,
+        issue_number,
+        &Messages::default(),
+        DEFAULT_LANGUAGE,
+    )
+}
+
+/// Renders the comments widget with its UI strings in `lang`
+pub fn render_localized_comments_widget(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: Option<&str>,
+    messages: &Messages,
+    lang: &str,
+) -> String {
+    messages
+        .localize(COMMENTS_SCRIPT, lang)
         .replace("{repo_owner}", repo_owner)
         .replace("{repo_name}", repo_name)
         .replace("{issue_number}", issue_number.unwrap_or(""))
@@ -0,0 +1,226 @@
+//! ABOUTME: Message catalogs for localizing template UI strings per language
+//! ABOUTME: Built-in English and Spanish catalogs, overridable from theme TOML files
+
+use crate::{ParaSsgError, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Directory under the theme directory holding `<lang>.toml` catalogs
+pub const MESSAGES_DIR: &str = "i18n";
+
+/// Built-in English messages; every key used by the templates is here
+const ENGLISH: &[(&str, &str)] = &[
+    ("language_name", "English"),
+    ("skip_to_content", "Skip to main content"),
+    ("projects", "Projects"),
+    ("areas", "Areas"),
+    ("resources", "Resources"),
+    ("archives", "Archives"),
+    ("blog", "Blog"),
+    ("tasks", "Tasks"),
+    ("home", "Home"),
+    (
+        "search_hint",
+        "Press <kbd>Ctrl</kbd>+<kbd>K</kbd> to search",
+    ),
+    ("relationships", "Relationships"),
+    ("backlinks", "Backlinks"),
+    ("translations", "Translations"),
+    ("comments", "Comments"),
+    ("loading_comments", "Loading comments..."),
+    ("add_comment", "Add Comment on GitHub"),
+    ("pagination", "Pages"),
+    ("previous_page", "Previous"),
+    ("next_page", "Next"),
+    ("blog_archive", "Archive"),
+];
+
+/// Built-in Spanish messages
+const SPANISH: &[(&str, &str)] = &[
+    ("language_name", "Español"),
+    ("skip_to_content", "Saltar al contenido principal"),
+    ("projects", "Proyectos"),
+    ("areas", "Áreas"),
+    ("resources", "Recursos"),
+    ("archives", "Archivo"),
+    ("blog", "Blog"),
+    ("tasks", "Tareas"),
+    ("home", "Inicio"),
+    (
+        "search_hint",
+        "Pulsa <kbd>Ctrl</kbd>+<kbd>K</kbd> para buscar",
+    ),
+    ("relationships", "Relaciones"),
+    ("backlinks", "Enlaces entrantes"),
+    ("translations", "Traducciones"),
+    ("comments", "Comentarios"),
+    ("loading_comments", "Cargando comentarios..."),
+    ("add_comment", "Comentar en GitHub"),
+    ("pagination", "Páginas"),
+    ("previous_page", "Anterior"),
+    ("next_page", "Siguiente"),
+    ("blog_archive", "Archivo del blog"),
+];
+
+/// UI strings by language
+///
+/// Lookups fall back from `pt-BR` to `pt`, then to the default language,
+/// then to English.
+#[derive(Debug, Clone)]
+pub struct Messages {
+    default_language: String,
+    catalogs: HashMap<String, BTreeMap<String, String>>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::new(crate::parser::DEFAULT_LANGUAGE)
+    }
+}
+
+impl Messages {
+    /// Built-in catalogs with `default_language` as the fallback
+    pub fn new(default_language: &str) -> Self {
+        let builtin = |messages: &[(&str, &str)]| {
+            messages
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect()
+        };
+        Self {
+            default_language: default_language.to_string(),
+            catalogs: HashMap::from([
+                ("en".to_string(), builtin(ENGLISH)),
+                ("es".to_string(), builtin(SPANISH)),
+            ]),
+        }
+    }
+
+    /// Add `<lang>.toml` catalogs from a directory over the built-ins
+    ///
+    /// Each file maps message keys to text, e.g. `backlinks = "Rückverweise"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a catalog cannot be read or is not a table of strings
+    pub fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(lang) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)?;
+            let messages: BTreeMap<String, String> = toml::from_str(&content).map_err(|e| {
+                ParaSsgError::ParseError(format!(
+                    "Invalid message catalog '{}': {}",
+                    path.display(),
+                    e.message()
+                ))
+            })?;
+            self.catalogs
+                .entry(lang.to_string())
+                .or_default()
+                .extend(messages);
+        }
+        Ok(())
+    }
+
+    /// Language used when a message is missing in the requested one
+    pub fn default_language(&self) -> &str {
+        &self.default_language
+    }
+
+    /// Message `key` in `lang`
+    pub fn get(&self, lang: &str, key: &str) -> String {
+        let primary = lang.split('-').next().unwrap_or(lang);
+        [lang, primary, self.default_language.as_str(), "en"]
+            .iter()
+            .find_map(|lang| self.catalogs.get(*lang)?.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Name of a language in that language, for language switchers
+    pub fn language_name(&self, lang: &str) -> String {
+        self.catalogs
+            .get(lang)
+            .and_then(|catalog| catalog.get("language_name"))
+            .cloned()
+            .unwrap_or_else(|| lang.to_uppercase())
+    }
+
+    /// Replace `{msg_key}` placeholders in `text` with messages in `lang`
+    pub fn localize(&self, text: &str, lang: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{msg_") {
+            result.push_str(&rest[..start]);
+            let placeholder = &rest[start..];
+            match placeholder.find('}') {
+                Some(end)
+                    if placeholder[5..end]
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+                {
+                    result.push_str(&self.get(lang, &placeholder[5..end]));
+                    rest = &placeholder[end + 1..];
+                }
+                _ => {
+                    result.push_str("{msg_");
+                    rest = &placeholder[5..];
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_fallbacks() {
+        let messages = Messages::new("en");
+
+        assert_eq!(messages.get("es", "backlinks"), "Enlaces entrantes");
+        assert_eq!(messages.get("es-MX", "backlinks"), "Enlaces entrantes");
+        assert_eq!(messages.get("de", "backlinks"), "Backlinks");
+        assert_eq!(messages.get("en", "unknown_key"), "unknown_key");
+        assert_eq!(messages.language_name("es"), "Español");
+        assert_eq!(messages.language_name("de"), "DE");
+        assert_eq!(
+            messages.localize("<h2>{msg_backlinks}</h2> {msg_ x}", "es"),
+            "<h2>Enlaces entrantes</h2> {msg_ x}"
+        );
+    }
+
+    #[test]
+    fn test_load_catalogs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("de.toml"),
+            "language_name = \"Deutsch\"\nbacklinks = \"Rückverweise\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("es.toml"), "blog = \"Bitácora\"\n").unwrap();
+
+        let mut messages = Messages::new("en");
+        messages.load_dir(dir.path()).unwrap();
+
+        assert_eq!(messages.get("de", "backlinks"), "Rückverweise");
+        assert_eq!(messages.language_name("de"), "Deutsch");
+        assert_eq!(messages.get("es", "blog"), "Bitácora");
+        assert_eq!(messages.get("es", "home"), "Inicio");
+
+        fs::write(dir.path().join("fr.toml"), "home = 1\n").unwrap();
+        assert!(messages.load_dir(dir.path()).is_err());
+    }
+}
@@ -3,12 +3,14 @@
 
 pub mod comments;
 pub mod header;
+pub mod i18n;
 pub mod search;
 pub mod styles;
 pub mod templates;
 
 pub use comments::*;
 pub use header::*;
+pub use i18n::*;
 pub use search::*;
 pub use styles::*;
 pub use templates::*;
@@ -214,7 +214,8 @@ pub fn generate_search_script() -> String {
     }
 
     function loadSearchIndex() {
-        fetch('/search-index.json')
+        // Multilingual sites point each page at its language's index
+        fetch(document.documentElement.dataset.searchIndex || '/search-index.json')
             .then(response => response.json())
             .then(data => {
                 searchIndex = data;
@@ -256,7 +257,7 @@ pub fn generate_search_script() -> String {
     }
 
     function performSearch() {
-        const query = searchInput.value.toLowerCase().trim();
+        const query = normalizeText(searchInput.value).trim();
         
         if (!query) {
             searchResults.innerHTML = '';
@@ -272,6 +273,25 @@ pub fn generate_search_script() -> String {
         displayResults(results, query);
     }
 
+    // Fold text the way the index content was folded (see `normalization`)
+    function normalizeText(text) {
+        const mode = (searchIndex && searchIndex.normalization) || 'normalized';
+        let normalized = text.normalize('NFC');
+        if (mode === 'strict') {
+            return normalized.toLowerCase();
+        }
+        normalized = normalized
+            .replace(/[\uFF01-\uFF5E]/g, c => String.fromCharCode(c.charCodeAt(0) - 0xFEE0))
+            .replace(/\u3000/g, ' ')
+            .toLowerCase()
+            .replace(/ß/g, 'ss')
+            .replace(/ς/g, 'σ');
+        if (mode === 'fold-diacritics') {
+            normalized = normalized.normalize('NFD').replace(/\p{M}/gu, '').normalize('NFC');
+        }
+        return normalized;
+    }
+
     function searchDocuments(query) {
         const results = [];
         const queryWords = query.split(/\s+/).filter(w => w.length > 0);
@@ -282,7 +302,7 @@ pub fn generate_search_script() -> String {
 
             // Search in title (higher weight)
             for (const word of queryWords) {
-                if (entry.title.toLowerCase().includes(word)) {
+                if (normalizeText(entry.title).includes(word)) {
                     score += 10;
                     matches.push('title');
                 }
@@ -291,7 +311,7 @@ pub fn generate_search_script() -> String {
             // Search in tags (medium weight)
             for (const tag of entry.tags) {
                 for (const word of queryWords) {
-                    if (tag.toLowerCase().includes(word)) {
+                    if (normalizeText(tag).includes(word)) {
                         score += 5;
                         matches.push('tag');
                         break;
@@ -422,6 +442,10 @@ pub fn generate_search_script() -> String {
                                 ${tag}
                             </span>`
                         ).join('')}
+                        ${entry.reading_time ? `<span style="
+                            color: var(--text-muted, #737373);
+                            font-size: 12px;
+                        ">${entry.reading_time} min read</span>` : ''}
                     </div>
                 </div>
             `;
@@ -576,6 +600,7 @@ mod tests {
         assert!(script.contains("displayResults"));
         assert!(script.contains("highlightText"));
         assert!(script.contains("handleSearchNavigation"));
+        assert!(script.contains("normalizeText"));
     }
 
     #[test]
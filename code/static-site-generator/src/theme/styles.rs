@@ -565,6 +565,375 @@ pub fn get_default_styles() -> String {
         padding: 0;
     }
     
+    /* Math rendered to MathML at build time */
+    .document-content math[display="block"] {
+        display: block;
+        margin: var(--space-3) 0;
+        overflow-x: auto;
+        overflow-y: hidden;
+    }
+    
+    .document-content code.math-error {
+        color: var(--error);
+        background-color: var(--error-bg);
+        cursor: help;
+    }
+    
+    /* Inline #hashtags linking to tag pages */
+    .document-content a.hashtag {
+        color: var(--accent-primary);
+        background-color: var(--surface-raised);
+        padding: 0.1rem 0.35rem;
+        border-radius: 3px;
+        font-size: 0.9em;
+        text-decoration: none;
+    }
+    
+    /* Task progress and the open tasks page */
+    .task-progress {
+        display: inline-flex;
+        align-items: center;
+        gap: 0.35rem;
+        color: var(--text-muted);
+        font-size: 0.85rem;
+    }
+    
+    .task-progress progress {
+        width: 4rem;
+        height: 0.4rem;
+        accent-color: var(--accent-primary);
+    }
+    
+    .task-section h2 {
+        margin-top: var(--space-4);
+    }
+    
+    .task-list {
+        list-style: none;
+        padding-left: 0;
+    }
+    
+    .task-list .task {
+        padding: 0.25rem 0;
+    }
+    
+    .task-source {
+        color: var(--text-muted);
+        font-size: 0.85rem;
+    }
+    
+    .task-due {
+        font-size: 0.85rem;
+        color: var(--accent-primary);
+    }
+    
+    .task-due.overdue {
+        color: var(--error);
+        font-weight: 600;
+    }
+    
+    /* Built-in shortcodes */
+    .shortcode-figure {
+        margin: var(--space-3) 0;
+        text-align: center;
+    }
+    
+    .shortcode-figure img {
+        max-width: 100%;
+        height: auto;
+    }
+    
+    .shortcode-figure figcaption {
+        color: var(--text-muted);
+        font-size: 0.9rem;
+        margin-top: var(--space-1);
+    }
+    
+    .shortcode-details {
+        border: 1px solid var(--border-secondary);
+        border-radius: 4px;
+        padding: var(--space-1) var(--space-2);
+        margin: var(--space-2) 0;
+    }
+    
+    .shortcode-details summary {
+        cursor: pointer;
+        font-weight: 600;
+    }
+    
+    video.shortcode-video,
+    .shortcode-video iframe {
+        width: 100%;
+        aspect-ratio: 16 / 9;
+        border: 0;
+    }
+    
+    .badge {
+        display: inline-block;
+        padding: 0.1rem 0.5rem;
+        border-radius: 999px;
+        font-size: 0.8em;
+        background-color: var(--surface-raised);
+        color: var(--text-primary);
+    }
+    
+    .badge-success { color: #16A34A; }
+    .badge-warning { color: #D97706; }
+    .badge-danger { color: var(--error); }
+    .badge-info { color: var(--accent-primary); }
+    
+    /* Results of query blocks */
+    .document-content table.query-results {
+        width: 100%;
+    }
+    
+    .query-empty {
+        color: var(--text-muted);
+        font-style: italic;
+    }
+    
+    .query-error {
+        color: var(--error);
+        background-color: var(--error-bg);
+        border-radius: 4px;
+        padding: var(--space-2);
+    }
+    
+    /* Dev build banner for documents rendered without frontmatter */
+    .frontmatter-error {
+        color: var(--error);
+        background-color: var(--error-bg);
+        border-left: 4px solid var(--error);
+        border-radius: 4px;
+        padding: var(--space-2);
+        margin-bottom: var(--space-3);
+    }
+    
+    .frontmatter-error pre {
+        margin: var(--space-1) 0 0;
+        background: transparent;
+    }
+    
+    /* Citations and bibliographies */
+    .citation-unknown {
+        color: var(--error);
+    }
+    
+    .bibliography {
+        margin-top: var(--space-4);
+        border-top: 1px solid var(--border-secondary);
+        font-size: 0.95rem;
+    }
+    
+    .bibliography-list li,
+    .reference-list li {
+        margin-bottom: var(--space-1);
+    }
+    
+    ul.bibliography-list,
+    .reference-list {
+        list-style: none;
+        padding-left: 0;
+    }
+    
+    .reference-cited-by {
+        color: var(--text-muted);
+        font-size: 0.9rem;
+    }
+    
+    /* Glossary terms and page */
+    a.glossary-term {
+        color: inherit;
+        text-decoration: underline dotted;
+        cursor: help;
+    }
+    
+    .glossary-list dt {
+        font-weight: 600;
+        margin-top: var(--space-3);
+    }
+    
+    .glossary-list dd {
+        margin-left: 0;
+    }
+    
+    .glossary-source,
+    .glossary-used-in {
+        color: var(--text-muted);
+        font-size: 0.9rem;
+        margin: var(--space-1) 0 0;
+    }
+    
+    /* Language switcher */
+    .language-switcher {
+        display: flex;
+        flex-wrap: wrap;
+        gap: var(--space-2);
+        margin: var(--space-2) 0 var(--space-4);
+        font-size: 0.9rem;
+    }
+    
+    .language-switcher .current-language {
+        font-weight: 600;
+    }
+    
+    /* Listing pagination */
+    .pagination {
+        display: flex;
+        flex-wrap: wrap;
+        align-items: center;
+        gap: var(--space-3);
+        margin-top: var(--space-4);
+    }
+    
+    .pagination-pages {
+        display: flex;
+        gap: var(--space-2);
+        list-style: none;
+        padding: 0;
+        margin: 0;
+    }
+    
+    .pagination [aria-current="page"] {
+        font-weight: 600;
+    }
+    
+    /* Blog listing sidebar and archives */
+    .blog-layout {
+        display: grid;
+        grid-template-columns: minmax(0, 1fr) 14rem;
+        gap: var(--space-4);
+        align-items: start;
+    }
+    
+    .blog-sidebar h2 {
+        font-size: 1rem;
+        margin-top: 0;
+    }
+    
+    .archive-periods,
+    .archive-periods ul {
+        list-style: none;
+        padding-left: 0;
+    }
+    
+    .archive-periods ul {
+        padding-left: var(--space-3);
+        font-size: 0.9rem;
+    }
+    
+    .archive-count {
+        color: var(--text-muted);
+        font-size: 0.85rem;
+    }
+    
+    @media (max-width: 768px) {
+        .blog-layout {
+            grid-template-columns: 1fr;
+        }
+    }
+    
+    /* Tags */
+    a.tag-link {
+        text-decoration: none;
+    }
+    
+    a.tag-link:hover .tag {
+        color: var(--text-primary);
+    }
+    
+    .tag-cloud {
+        list-style: none;
+        padding: 0;
+        line-height: 2;
+    }
+    
+    .tag-cloud > li {
+        display: inline-block;
+        margin-right: var(--space-3);
+        vertical-align: top;
+    }
+    
+    .tag-cloud ul {
+        list-style: none;
+        padding-left: var(--space-3);
+        margin: 0;
+        font-size: 0.9rem;
+    }
+    
+    .tag-count {
+        color: var(--text-muted);
+        font-size: 0.8rem;
+    }
+    
+    .tag-weight-1 { font-size: 0.9rem; }
+    .tag-weight-2 { font-size: 1rem; }
+    .tag-weight-3 { font-size: 1.2rem; }
+    .tag-weight-4 { font-size: 1.4rem; }
+    .tag-weight-5 { font-size: 1.7rem; font-weight: 600; }
+    
+        /* Typed frontmatter relationships */
+    .relationships-list {
+        display: grid;
+        grid-template-columns: max-content 1fr;
+        gap: var(--space-1) var(--space-3);
+        margin: 0;
+    }
+    
+    .relationships-list dt {
+        color: var(--text-muted);
+        grid-column: 1;
+    }
+    
+    .relationships-list dd {
+        grid-column: 2;
+        margin: 0;
+    }
+    
+    .backlinks-relationship {
+        color: var(--text-muted);
+        font-size: 0.9rem;
+        margin-top: var(--space-2);
+    }
+    
+    /* Obsidian-style callouts */
+    .callout {
+        --callout-color: var(--accent-primary);
+        border-left: 3px solid var(--callout-color);
+        background-color: var(--bg-secondary);
+        border-radius: 4px;
+        padding: var(--space-2);
+        margin: var(--space-3) 0;
+    }
+    
+    .callout-note { --callout-color: var(--accent-primary); }
+    .callout-abstract, .callout-info, .callout-todo { --callout-color: var(--info); }
+    .callout-tip, .callout-success { --callout-color: var(--success); }
+    .callout-question, .callout-warning { --callout-color: var(--warning); }
+    .callout-failure, .callout-danger, .callout-bug { --callout-color: var(--error); }
+    .callout-example { --callout-color: #a78bfa; }
+    .callout-quote { --callout-color: var(--text-muted); }
+    
+    .callout-title {
+        display: flex;
+        align-items: center;
+        gap: 0.5rem;
+        color: var(--callout-color);
+        font-weight: 600;
+    }
+    
+    .callout-foldable summary.callout-title {
+        cursor: pointer;
+    }
+    
+    .callout-content > :first-child {
+        margin-top: var(--space-1);
+    }
+    
+    .callout-content > :last-child {
+        margin-bottom: 0;
+    }
+    
     /* ==========================================================================
        Category Pages
        ========================================================================== */
@@ -637,6 +1006,11 @@ pub fn get_default_styles() -> String {
         margin-bottom: var(--space-1);
     }
     
+    .reading-stats,
+    .reading-time {
+        white-space: nowrap;
+    }
+    
     .summary {
         color: var(--text-secondary);
         line-height: 1.5;
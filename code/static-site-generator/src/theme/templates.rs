@@ -1,33 +1,39 @@
 //! ABOUTME: HTML template system for generating static site pages
 //! ABOUTME: Provides string-based templates for documents and navigation
 
+use crate::parser::{tag_slug, ContentStats, TaskProgress};
+use crate::theme::i18n::Messages;
 use crate::theme::{header::generate_header_script, search::generate_search_script};
+use crate::utils::html_escape;
 use crate::Result;
 use std::collections::HashMap;
 
 /// Base HTML template for all pages
 const BASE_TEMPLATE: &str = r##"<!DOCTYPE html>
-<html lang="en">
+<html lang="{lang}" data-search-index="{search_index}">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>{title}{title_separator}{site_title}</title>
+    {alternate_links}
+    {feed_links}
     <style>
         {styles}
     </style>
 </head>
 <body>
-    <a href="#main-content" class="skip-link">Skip to main content</a>
+    <a href="#main-content" class="skip-link">{msg_skip_to_content}</a>
     <header class="site-header">
         <div class="header-inner">
             <a href="{base_url}" class="logo">forge</a>
             <nav class="site-nav">
-                <a href="{base_url}projects/" class="nav-item {projects_active}">Projects</a>
-                <a href="{base_url}areas/" class="nav-item {areas_active}">Areas</a>
-                <a href="{base_url}resources/" class="nav-item {resources_active}">Resources</a>
-                <a href="{base_url}archives/" class="nav-item {archives_active}">Archives</a>
+                <a href="{base_url}projects/" class="nav-item {projects_active}">{msg_projects}</a>
+                <a href="{base_url}areas/" class="nav-item {areas_active}">{msg_areas}</a>
+                <a href="{base_url}resources/" class="nav-item {resources_active}">{msg_resources}</a>
+                <a href="{base_url}archives/" class="nav-item {archives_active}">{msg_archives}</a>
                 <span class="nav-divider"></span>
-                <a href="{base_url}blog/" class="nav-item {blog_active}">Blog</a>
+                <a href="{base_url}blog/" class="nav-item {blog_active}">{msg_blog}</a>
+                <a href="{base_url}tasks/" class="nav-item {tasks_active}">{msg_tasks}</a>
             </nav>
             <button class="nav-toggle" aria-label="Open menu">
                 <span class="burger"></span>
@@ -44,7 +50,7 @@ const BASE_TEMPLATE: &str = r##"<!DOCTYPE html>
     
     <footer role="contentinfo">
         <div class="container">
-            <p>Generated by para-ssg | Press <kbd>Ctrl</kbd>+<kbd>K</kbd> to search</p>
+            <p>Generated by para-ssg | {msg_search_hint}</p>
         </div>
     </footer>
     
@@ -62,12 +68,14 @@ const DOCUMENT_TEMPLATE: &str = r#"
     <header class="document-header">
         <h1>{title}</h1>
         {document_meta}
+        {language_switcher}
     </header>
     
     <div class="document-content">
         {content}
     </div>
     
+    {relationships_section}
     {backlinks_section}
 </article>
 "#;
@@ -82,6 +90,91 @@ const CATEGORY_INDEX_TEMPLATE: &str = r#"
     <div class="document-list">
         {document_entries}
     </div>
+    {pagination}
+</div>
+"#;
+
+/// Open tasks page template
+const TASKS_TEMPLATE: &str = r#"
+<div class="tasks-index">
+    <h1>Open tasks</h1>
+    <p class="document-count">{task_count} open tasks across {document_count} documents</p>
+    
+    {task_sections}
+</div>
+"#;
+
+/// Site-wide references page template
+const REFERENCES_TEMPLATE: &str = r#"
+<div class="references-index">
+    <h1>References</h1>
+    <p class="document-count">{work_count} works cited in {document_count} documents</p>
+    
+    <ul class="reference-list">
+        {reference_entries}
+    </ul>
+</div>
+"#;
+
+/// Glossary page template
+const GLOSSARY_TEMPLATE: &str = r#"
+<div class="glossary-index">
+    <h1>Glossary</h1>
+    <p class="document-count">{term_count} terms</p>
+    
+    <dl class="glossary-list">
+        {glossary_entries}
+    </dl>
+</div>
+"#;
+
+/// Tag index page template
+const TAG_INDEX_TEMPLATE: &str = r#"
+<div class="tag-index">
+    <h1>Tags</h1>
+    <p class="document-count">{tag_count} tags</p>
+    
+    <ul class="tag-cloud">
+        {tag_entries}
+    </ul>
+</div>
+"#;
+
+/// Tag page template
+const TAG_PAGE_TEMPLATE: &str = r#"
+<div class="category-index tag-page">
+    <h1>#{tag}</h1>
+    <p class="document-count">{document_count} documents tagged {tag}</p>
+    
+    {subtags}
+    <div class="document-list">
+        {document_entries}
+    </div>
+</div>
+"#;
+
+/// Blog archive overview template
+const BLOG_ARCHIVE_TEMPLATE: &str = r#"
+<div class="blog-archive">
+    <h1>{title}</h1>
+    <p class="document-count">{post_count} posts</p>
+    
+    <ul class="archive-periods">
+        {archive_periods}
+    </ul>
+</div>
+"#;
+
+/// Blog archive page template for a year or month
+const ARCHIVE_PAGE_TEMPLATE: &str = r#"
+<div class="category-index blog-archive">
+    <h1>{title}</h1>
+    <p class="document-count">{post_count} posts</p>
+    
+    {archive_periods}
+    <div class="document-list">
+        {document_entries}
+    </div>
 </div>
 "#;
 
@@ -111,6 +204,7 @@ const SUBDIRECTORY_INDEX_TEMPLATE: &str = r#"
     <p class="item-count">{item_count} items in this directory</p>
     
     {content}
+    {pagination}
 </div>
 "#;
 
@@ -123,10 +217,41 @@ const HOME_PAGE_TEMPLATE: &str = r#"
 </div>
 "#;
 
+/// Language of a rendered page and the URLs of its translations
+#[derive(Debug, Clone, Default)]
+pub struct PageLanguage {
+    /// Language code for `<html lang>`; the site default when `None`
+    pub lang: Option<String>,
+    /// Language code and URL of each translation, for `hreflang` links
+    pub alternates: Vec<(String, String)>,
+}
+
+/// A feed advertised with an autodiscovery `<link>` in page heads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedLink {
+    /// Feed title
+    pub title: String,
+    /// Absolute feed URL
+    pub url: String,
+    /// `application/atom+xml` or `application/rss+xml`
+    pub media_type: String,
+    /// Active section whose pages advertise the feed, such as `projects`;
+    /// `None` for site-wide feeds
+    pub section: Option<String>,
+}
+
 /// Template engine that replaces placeholders with values
 pub struct TemplateEngine {
     /// Custom templates that override defaults
     custom_templates: HashMap<String, String>,
+    /// UI strings for `{msg_key}` placeholders
+    messages: Messages,
+    /// Whether each language has its own search index
+    localized_search: bool,
+    /// Feeds for autodiscovery links
+    feeds: Vec<FeedLink>,
+    /// URL of the tag index; tags link to their pages below it when set
+    tags_url: Option<String>,
 }
 
 impl TemplateEngine {
@@ -134,10 +259,55 @@ impl TemplateEngine {
     pub fn new() -> Self {
         Self {
             custom_templates: HashMap::new(),
+            messages: Messages::default(),
+            localized_search: false,
+            feeds: Vec::new(),
+            tags_url: None,
         }
     }
 
-    /// Render the base template with given parameters
+    /// Link tags to their pages under `tags_url` (`/tags/`)
+    pub fn with_tag_pages(mut self, tags_url: String) -> Self {
+        self.tags_url = Some(tags_url);
+        self
+    }
+
+    /// Advertise feeds with autodiscovery links on the pages of their section
+    pub fn with_feeds(mut self, feeds: Vec<FeedLink>) -> Self {
+        self.feeds = feeds;
+        self
+    }
+
+    /// Use the given message catalogs; with `localized_search`, pages load
+    /// the search index for their language (`search-index.<lang>.json`)
+    pub fn with_messages(mut self, messages: Messages, localized_search: bool) -> Self {
+        self.messages = messages;
+        self.localized_search = localized_search;
+        self
+    }
+
+    /// Site language, used for pages without a language of their own
+    pub fn default_language(&self) -> &str {
+        self.messages.default_language()
+    }
+
+    /// Message `key` in `lang`, or in the site language when `None`
+    pub fn message(&self, lang: Option<&str>, key: &str) -> String {
+        self.messages
+            .get(lang.unwrap_or(self.messages.default_language()), key)
+    }
+
+    /// Message catalogs used by this engine
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
+    /// Name of a language in that language
+    pub fn language_name(&self, lang: &str) -> String {
+        self.messages.language_name(lang)
+    }
+
+    /// Render the base template with given parameters in the site language
     pub fn render_base(
         &self,
         title: &str,
@@ -148,9 +318,81 @@ impl TemplateEngine {
         site_title: &str,
         base_url: &str,
     ) -> Result<String> {
+        self.render_localized_base(
+            &PageLanguage::default(),
+            title,
+            content,
+            active_category,
+            breadcrumbs,
+            styles,
+            site_title,
+            base_url,
+        )
+    }
+
+    /// Render the base template for a page in a given language
+    ///
+    /// Sets `<html lang>`, adds an `hreflang` alternate link for each
+    /// translation and localizes the template's UI strings.
+    pub fn render_localized_base(
+        &self,
+        language: &PageLanguage,
+        title: &str,
+        content: &str,
+        active_category: Option<&str>,
+        breadcrumbs: Option<&str>,
+        styles: &str,
+        site_title: &str,
+        base_url: &str,
+    ) -> Result<String> {
+        let lang = language
+            .lang
+            .as_deref()
+            .unwrap_or(self.messages.default_language());
+        let alternate_links = if language.alternates.is_empty() {
+            String::new()
+        } else {
+            language
+                .alternates
+                .iter()
+                .map(|(alternate, url)| {
+                    format!(
+                        r#"<link rel="alternate" hreflang="{}" href="{}">"#,
+                        html_escape(alternate),
+                        url
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n    ")
+        };
+        let feed_links = self
+            .feeds
+            .iter()
+            .filter(|feed| feed.section.is_none() || feed.section.as_deref() == active_category)
+            .map(|feed| {
+                format!(
+                    r#"<link rel="alternate" type="{}" title="{}" href="{}">"#,
+                    feed.media_type,
+                    html_escape(&feed.title),
+                    feed.url
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n    ");
+        let search_index = if self.localized_search {
+            format!("{}search-index.{}.json", base_url, lang)
+        } else {
+            format!("{}search-index.json", base_url)
+        };
+
         let title_separator = if title.is_empty() { "" } else { " | " };
         let mut html = self
-            .get_template("base")
+            .messages
+            .localize(self.get_template("base"), lang)
+            .replace("{lang}", &html_escape(lang))
+            .replace("{search_index}", &search_index)
+            .replace("{alternate_links}", &alternate_links)
+            .replace("{feed_links}", &feed_links)
             .replace("{title}", &html_escape(title))
             .replace("{title_separator}", title_separator)
             .replace("{site_title}", &html_escape(site_title))
@@ -161,7 +403,14 @@ impl TemplateEngine {
             .replace("{header_script}", &generate_header_script());
 
         // Set active navigation state
-        let categories = ["projects", "areas", "resources", "archives", "blog"];
+        let categories = [
+            "projects",
+            "areas",
+            "resources",
+            "archives",
+            "blog",
+            "tasks",
+        ];
         for category in &categories {
             let placeholder = format!("{{{}_active}}", category);
             let class = if Some(*category) == active_category {
@@ -180,29 +429,52 @@ impl TemplateEngine {
     }
 
     /// Render a document page
+    ///
+    /// `translations` is the language switcher, and `lang` the language
+    /// section headings are shown in (the site language when `None`).
     pub fn render_document(
         &self,
         title: &str,
         content: &str,
         metadata: &DocumentMetadata,
+        relationships: Option<&str>,
         backlinks: Option<&str>,
+        translations: Option<&str>,
+        lang: Option<&str>,
     ) -> Result<String> {
         let mut doc_html = self
             .get_template("document")
             .replace("{title}", &html_escape(title))
+            .replace("{language_switcher}", translations.unwrap_or(""))
             .replace("{content}", content);
 
         // Add document metadata
         let meta_html = self.render_document_meta(metadata);
         doc_html = doc_html.replace("{document_meta}", &meta_html);
 
+        // Add relationships section if present
+        let relationships_html = if let Some(relationships) = relationships {
+            format!(
+                r#"<section class="relationships">
+                <h2>{}</h2>
+                {}
+            </section>"#,
+                html_escape(&self.message(lang, "relationships")),
+                relationships
+            )
+        } else {
+            String::new()
+        };
+        doc_html = doc_html.replace("{relationships_section}", &relationships_html);
+
         // Add backlinks section if present
         let backlinks_html = if let Some(backlinks) = backlinks {
             format!(
                 r#"<section class="backlinks">
-                <h2>Backlinks</h2>
+                <h2>{}</h2>
                 {}
             </section>"#,
+                html_escape(&self.message(lang, "backlinks")),
                 backlinks
             )
         } else {
@@ -218,6 +490,18 @@ impl TemplateEngine {
         &self,
         category: &str,
         documents: &[DocumentSummary],
+    ) -> Result<String> {
+        self.render_paginated_category_index(category, documents, None, None)
+    }
+
+    /// Render one page of a category index, with page navigation in `lang`
+    /// (the site language when `None`) when the listing has several pages
+    pub fn render_paginated_category_index(
+        &self,
+        category: &str,
+        documents: &[DocumentSummary],
+        pagination: Option<&Pagination>,
+        lang: Option<&str>,
     ) -> Result<String> {
         let category_title = match category {
             "projects" => "Projects",
@@ -241,17 +525,152 @@ impl TemplateEngine {
             entries.push('\n');
         }
 
-        let document_count = documents.len();
+        let document_count = pagination.map_or(documents.len(), |p| p.total_items);
+        let pagination_html = pagination
+            .map(|p| self.render_pagination(p, lang))
+            .unwrap_or_default();
         let html = self
             .get_template("category_index")
             .replace("{category_title}", category_title)
             .replace("{category_description}", category_description)
             .replace("{document_count}", &document_count.to_string())
-            .replace("{document_entries}", &entries);
+            .replace("{document_entries}", &entries)
+            .replace("{pagination}", &pagination_html);
 
         Ok(html)
     }
 
+    /// Render the open tasks page
+    pub fn render_tasks_page(&self, sections: &[TaskSection]) -> Result<String> {
+        let mut sections_html = String::new();
+        let mut task_count = 0;
+        let mut documents = std::collections::HashSet::new();
+
+        for section in sections {
+            sections_html.push_str(&format!(
+                r#"<section class="task-section"><h2>{}</h2>"#,
+                html_escape(&section.title)
+            ));
+            for group in &section.groups {
+                let title = match &group.url {
+                    Some(url) => format!(r#"<a href="{}">{}</a>"#, url, html_escape(&group.title)),
+                    None => html_escape(&group.title),
+                };
+                sections_html.push_str(&format!(
+                    r#"<div class="task-group"><h3>{}</h3><ul class="task-list">"#,
+                    title
+                ));
+                for task in &group.tasks {
+                    task_count += 1;
+                    documents.insert(&task.source_url);
+                    sections_html.push_str(&self.render_task_entry(task));
+                }
+                sections_html.push_str("</ul></div>");
+            }
+            sections_html.push_str("</section>");
+        }
+
+        if sections.is_empty() {
+            sections_html.push_str(r#"<p class="empty-state">No open tasks.</p>"#);
+        }
+
+        Ok(self
+            .get_template("tasks")
+            .replace("{task_count}", &task_count.to_string())
+            .replace("{document_count}", &documents.len().to_string())
+            .replace("{task_sections}", &sections_html))
+    }
+
+    /// Render a single open task with its source and due date
+    fn render_task_entry(&self, task: &TaskEntry) -> String {
+        let mut source = format!(
+            r#"<a href="{}">{}</a>"#,
+            task.source_url,
+            html_escape(&task.source_title)
+        );
+        if let Some(heading) = &task.heading {
+            source.push_str(&format!(" › {}", html_escape(heading)));
+        }
+        let due = match &task.due {
+            Some(due) => format!(
+                r#" <time class="task-due{}">{}</time>"#,
+                if task.overdue { " overdue" } else { "" },
+                due
+            ),
+            None => String::new(),
+        };
+        format!(
+            r#"<li class="task"><input type="checkbox" disabled> <span class="task-text">{}</span>{} <span class="task-source">{}</span></li>"#,
+            html_escape(&task.text),
+            due,
+            source
+        )
+    }
+
+    /// Render the site-wide references page
+    pub fn render_references_page(&self, references: &[ReferenceEntry]) -> Result<String> {
+        let mut entries = String::new();
+        let mut documents = std::collections::HashSet::new();
+
+        for reference in references {
+            let citing: Vec<String> = reference
+                .cited_by
+                .iter()
+                .map(|(title, url)| {
+                    documents.insert(url);
+                    format!(r#"<a href="{}">{}</a>"#, url, html_escape(title))
+                })
+                .collect();
+            entries.push_str(&format!(
+                r#"<li id="{}" class="reference"><div class="reference-text">{}</div><div class="reference-cited-by">Cited by {}</div></li>"#,
+                html_escape(&reference.anchor),
+                reference.html,
+                citing.join(", ")
+            ));
+        }
+
+        Ok(self
+            .get_template("references")
+            .replace("{work_count}", &references.len().to_string())
+            .replace("{document_count}", &documents.len().to_string())
+            .replace("{reference_entries}", &entries))
+    }
+
+    /// Render the glossary page
+    pub fn render_glossary_page(&self, entries: &[GlossaryEntry]) -> Result<String> {
+        let mut entries_html = String::new();
+
+        for entry in entries {
+            let used_in = if entry.used_in.is_empty() {
+                String::new()
+            } else {
+                let links: Vec<String> = entry
+                    .used_in
+                    .iter()
+                    .map(|(title, url)| format!(r#"<a href="{}">{}</a>"#, url, html_escape(title)))
+                    .collect();
+                format!(
+                    r#"<p class="glossary-used-in">Used in {}</p>"#,
+                    links.join(", ")
+                )
+            };
+            entries_html.push_str(&format!(
+                r#"<dt id="{}">{}</dt><dd>{}<p class="glossary-source">Defined in <a href="{}">{}</a></p>{}</dd>"#,
+                html_escape(&entry.anchor),
+                html_escape(&entry.term),
+                entry.definition_html,
+                entry.source_url,
+                html_escape(&entry.source_title),
+                used_in
+            ));
+        }
+
+        Ok(self
+            .get_template("glossary")
+            .replace("{term_count}", &entries.len().to_string())
+            .replace("{glossary_entries}", &entries_html))
+    }
+
     /// Render a subdirectory index page
     pub fn render_subdirectory_index(
         &self,
@@ -274,7 +693,8 @@ impl TemplateEngine {
                 &format!("Documents in {}", subdirectory_name),
             )
             .replace("{document_count}", &document_count.to_string())
-            .replace("{document_entries}", &entries);
+            .replace("{document_entries}", &entries)
+            .replace("{pagination}", "");
 
         Ok(html)
     }
@@ -286,11 +706,31 @@ impl TemplateEngine {
         documents: &[DocumentSummary],
         subdirectories: &[&crate::utils::DirectoryInfo],
         base_url: &str,
+    ) -> Result<String> {
+        self.render_paginated_subdirectory_index(
+            subdirectory_name,
+            documents,
+            subdirectories,
+            base_url,
+            None,
+        )
+    }
+
+    /// Render one page of a subdirectory index; directory cards are shown
+    /// on the first page only
+    pub fn render_paginated_subdirectory_index(
+        &self,
+        subdirectory_name: &str,
+        documents: &[DocumentSummary],
+        subdirectories: &[&crate::utils::DirectoryInfo],
+        base_url: &str,
+        pagination: Option<&Pagination>,
     ) -> Result<String> {
         let mut content = String::new();
+        let first_page = pagination.map_or(true, |p| p.current == 1);
 
         // Add directory cards if there are subdirectories
-        if !subdirectories.is_empty() {
+        if first_page && !subdirectories.is_empty() {
             content.push_str("<div class=\"directory-cards\">\n");
             content.push_str("<h2>Subdirectories</h2>\n");
             content.push_str("<div class=\"directory-grid\">\n");
@@ -359,7 +799,11 @@ impl TemplateEngine {
         }
 
         // Create the full page with the custom content
-        let total_items = documents.len() + subdirectories.len();
+        let document_count = pagination.map_or(documents.len(), |p| p.total_items);
+        let total_items = document_count + subdirectories.len();
+        let pagination_html = pagination
+            .map(|p| self.render_pagination(p, None))
+            .unwrap_or_default();
         let html = self
             .get_template("subdirectory_index")
             .replace("{subdirectory_title}", subdirectory_name)
@@ -368,7 +812,8 @@ impl TemplateEngine {
                 &format!("Contents of {}", subdirectory_name),
             )
             .replace("{item_count}", &total_items.to_string())
-            .replace("{content}", &content);
+            .replace("{content}", &content)
+            .replace("{pagination}", &pagination_html);
 
         Ok(html)
     }
@@ -385,11 +830,20 @@ impl TemplateEngine {
         if let Some(date) = &doc.date {
             meta_parts.push(format!("<time>{}</time>", date));
         }
+        if let Some(stats) = doc.stats.filter(|s| s.reading_time > 0) {
+            meta_parts.push(format!(
+                r#"<span class="reading-time">{} min read</span>"#,
+                stats.reading_time
+            ));
+        }
+        if let Some(tasks) = doc.tasks {
+            meta_parts.push(render_task_progress(tasks));
+        }
         if !doc.tags.is_empty() {
             let tags_html = doc
                 .tags
                 .iter()
-                .map(|tag| format!(r#"<span class="tag">{}</span>"#, html_escape(tag)))
+                .map(|tag| self.render_tag(tag))
                 .collect::<Vec<_>>()
                 .join(" ");
             meta_parts.push(format!(r#"<div class="tags">{}</div>"#, tags_html));
@@ -413,6 +867,161 @@ impl TemplateEngine {
         Ok(entry)
     }
 
+    /// Render previous/next and numbered links between the pages of a
+    /// listing, labelled in `lang` (the site language when `None`)
+    pub fn render_pagination(&self, pagination: &Pagination, lang: Option<&str>) -> String {
+        let mut html = format!(
+            r#"<nav class="pagination" aria-label="{}">"#,
+            html_escape(&self.message(lang, "pagination"))
+        );
+        if let Some(url) = pagination.prev_url() {
+            html.push_str(&format!(
+                r#"<a href="{}" rel="prev" class="pagination-prev">&larr; {}</a>"#,
+                url,
+                html_escape(&self.message(lang, "previous_page"))
+            ));
+        }
+        html.push_str(r#"<ol class="pagination-pages">"#);
+        for (i, url) in pagination.page_urls.iter().enumerate() {
+            let page = i + 1;
+            if page == pagination.current {
+                html.push_str(&format!(
+                    r#"<li><span aria-current="page">{}</span></li>"#,
+                    page
+                ));
+            } else {
+                html.push_str(&format!(r#"<li><a href="{}">{}</a></li>"#, url, page));
+            }
+        }
+        html.push_str("</ol>");
+        if let Some(url) = pagination.next_url() {
+            html.push_str(&format!(
+                r#"<a href="{}" rel="next" class="pagination-next">{} &rarr;</a>"#,
+                url,
+                html_escape(&self.message(lang, "next_page"))
+            ));
+        }
+        html.push_str("</nav>");
+        html
+    }
+
+    /// Render the blog sidebar linking each year and month of the archive,
+    /// labelled in `lang` (the site language when `None`)
+    pub fn render_archive_sidebar(
+        &self,
+        periods: &[ArchivePeriod],
+        overview_url: &str,
+        lang: Option<&str>,
+    ) -> String {
+        format!(
+            r#"<aside class="blog-sidebar"><h2><a href="{}">{}</a></h2><ul class="archive-periods">{}</ul></aside>"#,
+            overview_url,
+            html_escape(&self.message(lang, "blog_archive")),
+            render_archive_periods(periods)
+        )
+    }
+
+    /// Render the blog archive overview with post counts per year and month
+    pub fn render_archive_overview(
+        &self,
+        periods: &[ArchivePeriod],
+        lang: Option<&str>,
+    ) -> Result<String> {
+        let post_count: usize = periods.iter().map(|period| period.count).sum();
+        Ok(self
+            .get_template("blog_archive")
+            .replace("{title}", &html_escape(&self.message(lang, "blog_archive")))
+            .replace("{post_count}", &post_count.to_string())
+            .replace("{archive_periods}", &render_archive_periods(periods)))
+    }
+
+    /// Render the archive page of a year or month, with the months of a
+    /// year as `periods`
+    pub fn render_archive_page(
+        &self,
+        title: &str,
+        documents: &[DocumentSummary],
+        periods: &[ArchivePeriod],
+    ) -> Result<String> {
+        let mut entries = String::new();
+        for doc in documents {
+            entries.push_str(&self.render_document_entry(doc)?);
+            entries.push('\n');
+        }
+
+        let periods_html = if periods.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"<ul class="archive-periods">{}</ul>"#,
+                render_archive_periods(periods)
+            )
+        };
+
+        Ok(self
+            .get_template("archive_page")
+            .replace("{title}", &html_escape(title))
+            .replace("{post_count}", &documents.len().to_string())
+            .replace("{archive_periods}", &periods_html)
+            .replace("{document_entries}", &entries))
+    }
+
+    /// Render a tag, linked to its tag page when tag pages are generated
+    pub fn render_tag(&self, tag: &str) -> String {
+        let span = format!(r#"<span class="tag">{}</span>"#, html_escape(tag));
+        match &self.tags_url {
+            Some(tags_url) => format!(
+                r#"<a href="{}{}/" class="tag-link">{}</a>"#,
+                tags_url,
+                html_escape(&tag_slug(tag)),
+                span
+            ),
+            None => span,
+        }
+    }
+
+    /// Render the tag index as a cloud, nesting tags under their parents
+    pub fn render_tag_index(&self, tags: &[TagCloudEntry]) -> Result<String> {
+        fn count_tags(tags: &[TagCloudEntry]) -> usize {
+            tags.iter().map(|tag| 1 + count_tags(&tag.children)).sum()
+        }
+
+        Ok(self
+            .get_template("tag_index")
+            .replace("{tag_count}", &count_tags(tags).to_string())
+            .replace("{tag_entries}", &render_tag_cloud_entries(tags)))
+    }
+
+    /// Render the page of a tag, with its nested tags and documents
+    pub fn render_tag_page(
+        &self,
+        tag: &str,
+        documents: &[DocumentSummary],
+        subtags: &[TagCloudEntry],
+    ) -> Result<String> {
+        let mut entries = String::new();
+        for doc in documents {
+            entries.push_str(&self.render_document_entry(doc)?);
+            entries.push('\n');
+        }
+
+        let subtags_html = if subtags.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"<ul class="tag-cloud subtags">{}</ul>"#,
+                render_tag_cloud_entries(subtags)
+            )
+        };
+
+        Ok(self
+            .get_template("tag_page")
+            .replace("{tag}", &html_escape(tag))
+            .replace("{document_count}", &documents.len().to_string())
+            .replace("{subtags}", &subtags_html)
+            .replace("{document_entries}", &entries))
+    }
+
     /// Render breadcrumb navigation
     pub fn render_breadcrumbs(&self, items: &[BreadcrumbItem]) -> Result<String> {
         if items.is_empty() {
@@ -484,7 +1093,7 @@ impl TemplateEngine {
             } else {
                 doc.tags
                     .iter()
-                    .map(|tag| format!(r#"<span class="tag">{}</span>"#, html_escape(tag)))
+                    .map(|tag| self.render_tag(tag))
                     .collect::<Vec<_>>()
                     .join(" ")
             };
@@ -536,6 +1145,13 @@ impl TemplateEngine {
             "document_entry" => DOCUMENT_ENTRY_TEMPLATE,
             "breadcrumb" => BREADCRUMB_TEMPLATE,
             "home_page" => HOME_PAGE_TEMPLATE,
+            "tasks" => TASKS_TEMPLATE,
+            "references" => REFERENCES_TEMPLATE,
+            "glossary" => GLOSSARY_TEMPLATE,
+            "tag_index" => TAG_INDEX_TEMPLATE,
+            "tag_page" => TAG_PAGE_TEMPLATE,
+            "blog_archive" => BLOG_ARCHIVE_TEMPLATE,
+            "archive_page" => ARCHIVE_PAGE_TEMPLATE,
             _ => BASE_TEMPLATE,
         }
     }
@@ -555,6 +1171,27 @@ impl TemplateEngine {
             meta_parts.push(format!(r#"<time class="date">{}</time>"#, date));
         }
 
+        if let Some(stats) = metadata.stats.filter(|s| s.word_count > 0) {
+            let mut parts = vec![
+                format!("{} words", stats.word_count),
+                format!("{} min read", stats.reading_time),
+            ];
+            if stats.code_blocks > 0 {
+                parts.push(format!("{} code blocks", stats.code_blocks));
+            }
+            if stats.links > 0 {
+                parts.push(format!("{} links", stats.links));
+            }
+            meta_parts.push(format!(
+                r#"<span class="reading-stats">{}</span>"#,
+                parts.join(" · ")
+            ));
+        }
+
+        if let Some(tasks) = metadata.tasks {
+            meta_parts.push(render_task_progress(tasks));
+        }
+
         if let Some(status) = &metadata.status {
             meta_parts.push(format!(
                 r#"<span class="status status-{}">{}</span>"#,
@@ -567,7 +1204,7 @@ impl TemplateEngine {
             let tags_html = metadata
                 .tags
                 .iter()
-                .map(|tag| format!(r#"<span class="tag">{}</span>"#, html_escape(tag)))
+                .map(|tag| self.render_tag(tag))
                 .collect::<Vec<_>>()
                 .join(" ");
             meta_parts.push(format!(r#"<div class="tags">{}</div>"#, tags_html));
@@ -596,6 +1233,8 @@ pub struct DocumentMetadata {
     pub date: Option<String>,
     pub status: Option<String>,
     pub tags: Vec<String>,
+    pub stats: Option<ContentStats>,
+    pub tasks: Option<TaskProgress>,
 }
 
 /// Summary information for document listings
@@ -605,6 +1244,100 @@ pub struct DocumentSummary {
     pub date: Option<String>,
     pub tags: Vec<String>,
     pub summary: Option<String>,
+    pub stats: Option<ContentStats>,
+    pub tasks: Option<TaskProgress>,
+}
+
+/// Category section of the open tasks page
+pub struct TaskSection {
+    pub title: String,
+    pub groups: Vec<TaskGroup>,
+}
+
+/// Tasks belonging to one project or document
+pub struct TaskGroup {
+    pub title: String,
+    pub url: Option<String>,
+    pub tasks: Vec<TaskEntry>,
+}
+
+/// An open task on the tasks page
+pub struct TaskEntry {
+    pub text: String,
+    pub source_title: String,
+    pub source_url: String,
+    pub heading: Option<String>,
+    pub due: Option<String>,
+    pub overdue: bool,
+}
+
+/// A cited work on the references page
+pub struct ReferenceEntry {
+    /// Anchor of the entry (`ref-smith2020`)
+    pub anchor: String,
+    /// Formatted reference (HTML)
+    pub html: String,
+    /// Titles and URLs of the documents citing the work
+    pub cited_by: Vec<(String, String)>,
+}
+
+/// A term on the glossary page
+pub struct GlossaryEntry {
+    /// Anchor of the term (`term-idempotency`)
+    pub anchor: String,
+    pub term: String,
+    /// Definition (HTML)
+    pub definition_html: String,
+    pub source_title: String,
+    pub source_url: String,
+    /// Titles and URLs of the documents linking the term
+    pub used_in: Vec<(String, String)>,
+}
+
+/// Position of a listing page among the pages of its listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    /// Number of this page, from 1
+    pub current: usize,
+    /// URL of every page, the first page first
+    pub page_urls: Vec<String>,
+    /// Entries across all pages
+    pub total_items: usize,
+}
+
+impl Pagination {
+    /// URL of the previous page, if this is not the first
+    pub fn prev_url(&self) -> Option<&str> {
+        (self.current > 1).then(|| self.page_urls[self.current - 2].as_str())
+    }
+
+    /// URL of the next page, if this is not the last
+    pub fn next_url(&self) -> Option<&str> {
+        self.page_urls.get(self.current).map(String::as_str)
+    }
+}
+
+/// A tag in the tag cloud
+pub struct TagCloudEntry {
+    /// Tag segment shown in the cloud (`health` for `area/health`)
+    pub name: String,
+    pub url: String,
+    /// Documents with the tag or one nested below it
+    pub count: usize,
+    /// Size class from 1 (rarest) to 5 (most used)
+    pub weight: u8,
+    pub children: Vec<TagCloudEntry>,
+}
+
+/// A year or month of the blog archive
+pub struct ArchivePeriod {
+    /// `2024` or `May 2024`
+    pub title: String,
+    pub url: String,
+    /// Posts in the period
+    pub count: usize,
+    /// Months of a year
+    pub children: Vec<ArchivePeriod>,
 }
 
 /// Breadcrumb navigation item
@@ -613,13 +1346,56 @@ pub struct BreadcrumbItem {
     pub url: Option<String>,
 }
 
-/// HTML escape special characters
-fn html_escape(text: &str) -> String {
-    text.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&#39;")
+/// Render task completion as a progress bar with a count
+fn render_task_progress(tasks: TaskProgress) -> String {
+    format!(
+        r#"<span class="task-progress" title="{0} of {1} tasks done ({2}%)"><progress value="{0}" max="{1}"></progress> {0}/{1} tasks</span>"#,
+        tasks.completed,
+        tasks.total,
+        tasks.percent()
+    )
+}
+
+/// Render tag cloud items, nesting each tag's children in a list of their own
+fn render_tag_cloud_entries(tags: &[TagCloudEntry]) -> String {
+    tags.iter()
+        .map(|tag| {
+            let children = if tag.children.is_empty() {
+                String::new()
+            } else {
+                format!("<ul>{}</ul>", render_tag_cloud_entries(&tag.children))
+            };
+            format!(
+                r#"<li><a href="{}" class="tag-weight-{}">{}</a> <span class="tag-count">{}</span>{}</li>"#,
+                tag.url,
+                tag.weight,
+                html_escape(&tag.name),
+                tag.count,
+                children
+            )
+        })
+        .collect()
+}
+
+/// Render archive periods with their post counts, nesting months in years
+fn render_archive_periods(periods: &[ArchivePeriod]) -> String {
+    periods
+        .iter()
+        .map(|period| {
+            let children = if period.children.is_empty() {
+                String::new()
+            } else {
+                format!("<ul>{}</ul>", render_archive_periods(&period.children))
+            };
+            format!(
+                r#"<li><a href="{}">{}</a> <span class="archive-count">({})</span>{}</li>"#,
+                period.url,
+                html_escape(&period.title),
+                period.count,
+                children
+            )
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -664,10 +1440,28 @@ mod tests {
             date: Some("2023-01-15".to_string()),
             status: Some("published".to_string()),
             tags: vec!["rust".to_string(), "testing".to_string()],
+            stats: Some(ContentStats {
+                word_count: 1200,
+                reading_time: 6,
+                code_blocks: 2,
+                links: 0,
+            }),
+            tasks: Some(TaskProgress {
+                completed: 1,
+                total: 4,
+            }),
         };
 
         let html = engine
-            .render_document("Test Document", "<p>Document content</p>", &metadata, None)
+            .render_document(
+                "Test Document",
+                "<p>Document content</p>",
+                &metadata,
+                None,
+                None,
+                None,
+                None,
+            )
             .unwrap();
 
         assert!(html.contains("<h1>Test Document</h1>"));
@@ -676,6 +1470,9 @@ mod tests {
         assert!(html.contains("2023-01-15"));
         assert!(html.contains("status-published"));
         assert!(html.contains(r#"<span class="tag">rust</span>"#));
+        assert!(html.contains("1200 words · 6 min read · 2 code blocks"));
+        assert!(!html.contains("links"));
+        assert!(html.contains(r#"<progress value="1" max="4"></progress> 1/4 tasks"#));
     }
 
     #[test]
@@ -687,6 +1484,8 @@ mod tests {
             date: Some("2023-01-15".to_string()),
             tags: vec!["important".to_string()],
             summary: Some("A test project".to_string()),
+            stats: None,
+            tasks: None,
         }];
 
         let html = engine
@@ -700,6 +1499,34 @@ mod tests {
         assert!(html.contains("A test project"));
     }
 
+    #[test]
+    fn test_render_tag_index_and_links() {
+        let engine = TemplateEngine::new().with_tag_pages("/tags/".to_string());
+        assert_eq!(
+            engine.render_tag("Area/Mental Health"),
+            r#"<a href="/tags/area/mental-health/" class="tag-link"><span class="tag">Area/Mental Health</span></a>"#
+        );
+
+        let tags = vec![TagCloudEntry {
+            name: "area".to_string(),
+            url: "/tags/area/".to_string(),
+            count: 3,
+            weight: 5,
+            children: vec![TagCloudEntry {
+                name: "health".to_string(),
+                url: "/tags/area/health/".to_string(),
+                count: 1,
+                weight: 1,
+                children: Vec::new(),
+            }],
+        }];
+        let html = engine.render_tag_index(&tags).unwrap();
+        assert!(html.contains("2 tags"));
+        assert!(html.contains(
+            r#"<li><a href="/tags/area/" class="tag-weight-5">area</a> <span class="tag-count">3</span><ul><li><a href="/tags/area/health/" class="tag-weight-1">health</a>"#
+        ));
+    }
+
     #[test]
     fn test_render_breadcrumbs() {
         let engine = TemplateEngine::new();
@@ -745,6 +1572,8 @@ mod tests {
                 date: Some("2023-01-15".to_string()),
                 tags: vec!["rust".to_string()],
                 summary: None,
+                stats: None,
+                tasks: None,
             },
             DocumentSummary {
                 url: "/static-site-generator/areas/health.html".to_string(),
@@ -752,6 +1581,8 @@ mod tests {
                 date: Some("2023-01-16".to_string()),
                 tags: vec![],
                 summary: None,
+                stats: None,
+                tasks: None,
             },
         ];
 
@@ -776,6 +1607,8 @@ mod tests {
             date: Some("2023-01-15".to_string()),
             tags: vec!["rust".to_string()],
             summary: None,
+            stats: None,
+            tasks: None,
         }];
 
         let html = engine.render_home_page(&documents, "/").unwrap();
@@ -1,8 +1,16 @@
 //! ABOUTME: Blog-specific utility functions for detecting and filtering blog posts
-//! ABOUTME: Provides helpers to identify blog posts in areas/blog/ directory
+//! ABOUTME: Provides helpers to identify blog posts in areas/blog/ and group them by date
 
 use crate::parser::document::Document;
-use std::path::Path;
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Output directory of the blog listing and its archives
+pub const BLOG_DIR: &str = "blog";
+
+/// Output directory of the blog archive overview, under [`BLOG_DIR`]
+pub const BLOG_ARCHIVE_DIR: &str = "archive";
 
 /// Determines if a given path represents a blog post
 pub fn is_blog_post(path: &Path) -> bool {
@@ -19,6 +27,92 @@ pub fn get_blog_posts(documents: &[Document]) -> Vec<&Document> {
         .collect()
 }
 
+/// Blog posts published in one month
+#[derive(Debug, Clone)]
+pub struct ArchiveMonth<'a> {
+    pub year: i32,
+    /// Month of the year, from 1
+    pub month: u32,
+    /// Posts, newest first
+    pub posts: Vec<&'a Document>,
+}
+
+impl ArchiveMonth<'_> {
+    /// Output directory of the month's archive page (`blog/2024/05`)
+    pub fn dir(&self) -> PathBuf {
+        Path::new(BLOG_DIR)
+            .join(self.year.to_string())
+            .join(format!("{:02}", self.month))
+    }
+
+    /// Month and year in English (`May 2024`)
+    pub fn title(&self) -> String {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .map(|date| date.format("%B %Y").to_string())
+            .unwrap_or_else(|| format!("{}-{:02}", self.year, self.month))
+    }
+}
+
+/// Blog posts published in one year
+#[derive(Debug, Clone)]
+pub struct ArchiveYear<'a> {
+    pub year: i32,
+    /// Months with posts, newest first
+    pub months: Vec<ArchiveMonth<'a>>,
+}
+
+impl<'a> ArchiveYear<'a> {
+    /// Output directory of the year's archive page (`blog/2024`)
+    pub fn dir(&self) -> PathBuf {
+        Path::new(BLOG_DIR).join(self.year.to_string())
+    }
+
+    /// Posts of the whole year, newest first
+    pub fn posts(&self) -> Vec<&'a Document> {
+        self.months
+            .iter()
+            .flat_map(|month| month.posts.iter().copied())
+            .collect()
+    }
+
+    /// Number of posts in the year
+    pub fn count(&self) -> usize {
+        self.months.iter().map(|month| month.posts.len()).sum()
+    }
+}
+
+/// Group blog posts by the year and month of [`Document::date`], newest
+/// first; undated posts are left out
+pub fn blog_archive<'a>(posts: impl IntoIterator<Item = &'a Document>) -> Vec<ArchiveYear<'a>> {
+    let mut years: BTreeMap<i32, BTreeMap<u32, Vec<&'a Document>>> = BTreeMap::new();
+    for post in posts {
+        if let Some(date) = post.date() {
+            years
+                .entry(date.year())
+                .or_default()
+                .entry(date.month())
+                .or_default()
+                .push(post);
+        }
+    }
+
+    years
+        .into_iter()
+        .rev()
+        .map(|(year, months)| ArchiveYear {
+            year,
+            months: months
+                .into_iter()
+                .rev()
+                .map(|(month, mut posts)| {
+                    posts.sort_by(|a, b| b.date().cmp(&a.date()));
+                    ArchiveMonth { year, month, posts }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +177,41 @@ mod tests {
         assert_eq!(blog_posts[1].title(), "Blog Post 2");
     }
 
+    #[test]
+    fn test_blog_archive() {
+        let post = |name: &str, date: Option<&str>| {
+            let mut doc = Document::new(
+                PathBuf::from(format!("/input/areas/blog/{}.md", name)),
+                PathBuf::from(format!("areas/blog/{}.md", name)),
+                "areas".to_string(),
+            );
+            doc.metadata.title = Some(name.to_string());
+            doc.metadata.date = date.and_then(crate::parser::FrontmatterDate::parse);
+            doc
+        };
+        let posts = vec![
+            post("first", Some("2023-12-24")),
+            post("early", Some("2024-05-01")),
+            post("late", Some("2024-05-20")),
+            post("summer", Some("2024-07-04")),
+            post("undated", None),
+        ];
+
+        let archive = blog_archive(&posts);
+        assert_eq!(archive.len(), 2);
+        assert_eq!(archive[0].year, 2024);
+        assert_eq!(archive[0].count(), 3);
+        assert_eq!(archive[0].dir(), PathBuf::from("blog/2024"));
+
+        let may = &archive[0].months[1];
+        assert_eq!(may.title(), "May 2024");
+        assert_eq!(may.dir(), PathBuf::from("blog/2024/05"));
+        let titles: Vec<&str> = may.posts.iter().map(|doc| doc.title()).collect();
+        assert_eq!(titles, vec!["late", "early"]);
+
+        assert_eq!(archive[1].posts()[0].title(), "first");
+    }
+
     #[test]
     fn test_get_blog_posts_empty() {
         let mut doc = Document::new(
@@ -0,0 +1,50 @@
+//! ABOUTME: Escaping of text for HTML and XML output, and of paths for URLs
+//! ABOUTME: Shared by the parser, templates, and the feed and sitemap generators
+
+/// Escape text for HTML or XML element content and quoted attributes
+///
+/// `'` becomes `&#39;`, which HTML and XML both accept.
+pub(crate) fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Percent-encode a `/`-separated output path for use in an absolute URL
+///
+/// Everything but unreserved characters (RFC 3986) and `/` is encoded as
+/// UTF-8 bytes, so `notes/Café ideas.html` becomes
+/// `notes/Caf%C3%A9%20ideas.html`. Backslashes are treated as `/`.
+pub(crate) fn url_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_encode_path() {
+        assert_eq!(
+            url_encode_path("projects/a-b_c.html"),
+            "projects/a-b_c.html"
+        );
+        assert_eq!(
+            url_encode_path("notes/Café ideas.html"),
+            "notes/Caf%C3%A9%20ideas.html"
+        );
+        assert_eq!(url_encode_path("100% #1?.html"), "100%25%20%231%3F.html");
+        assert_eq!(url_encode_path("a\\b.html"), "a/b.html");
+    }
+}
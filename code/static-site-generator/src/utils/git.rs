@@ -0,0 +1,184 @@
+//! ABOUTME: Git history lookup for document dates
+//! ABOUTME: Reads first and last commit times per file from the local repository
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// First and last commit times for a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GitFileDates {
+    /// Time of the oldest commit touching the file
+    pub created: DateTime<Utc>,
+    /// Time of the newest commit touching the file
+    pub modified: DateTime<Utc>,
+}
+
+/// Commit dates for every file under a directory, read with a single `git log`
+///
+/// Built once before documents are parsed and shared across the parallel
+/// parse, so each lookup is a hash map access rather than a git invocation.
+#[derive(Debug, Clone, Default)]
+pub struct GitHistory {
+    files: HashMap<PathBuf, GitFileDates>,
+}
+
+impl GitHistory {
+    /// Read the history of all files under `dir`
+    ///
+    /// Returns `None` if `dir` is not inside a git repository or git is not
+    /// available. Renames are not followed, and shallow clones only see the
+    /// commits they contain.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let root = run_git(dir, &["rev-parse", "--show-toplevel"])?;
+        let root = normalize(Path::new(root.trim()));
+        let log = run_git(
+            dir,
+            &[
+                "-c",
+                "core.quotepath=off",
+                "log",
+                "--no-renames",
+                "--format=commit %ct",
+                "--name-only",
+                "--",
+                ".",
+            ],
+        )?;
+        Some(Self::from_log(&root, &log))
+    }
+
+    /// Build the history from `git log --format="commit %ct" --name-only` output
+    ///
+    /// `root` must already be canonical; log paths are joined to it as is.
+    fn from_log(root: &Path, log: &str) -> Self {
+        let mut files: HashMap<PathBuf, GitFileDates> = HashMap::new();
+        let mut commit_time = None;
+
+        // Commits are listed newest first
+        for line in log.lines() {
+            if let Some(timestamp) = line.strip_prefix("commit ") {
+                commit_time = timestamp
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|t| DateTime::from_timestamp(t, 0));
+                continue;
+            }
+            let (Some(time), false) = (commit_time, line.trim().is_empty()) else {
+                continue;
+            };
+
+            files
+                .entry(root.join(line))
+                .and_modify(|dates| dates.created = time)
+                .or_insert(GitFileDates {
+                    created: time,
+                    modified: time,
+                });
+        }
+
+        Self { files }
+    }
+
+    /// Commit dates for a file, if it has any history
+    pub fn dates(&self, path: &Path) -> Option<GitFileDates> {
+        self.files.get(&normalize(path)).copied()
+    }
+
+    /// Number of files with history
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check if no file has history
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Canonicalize a path so symlinked checkouts and `..` segments compare equal
+///
+/// Only the repository root and looked-up paths are canonicalized, not every
+/// file in the log.
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_from_log() {
+        let log = "commit 1700000300\n\nnotes/a.md\n\ncommit 1700000200\n\nnotes/b.md\n\ncommit 1700000100\n\nnotes/a.md\nnotes/b.md\n";
+        let history = GitHistory::from_log(Path::new("/repo"), log);
+
+        assert_eq!(history.len(), 2);
+        let a = history.dates(Path::new("/repo/notes/a.md")).unwrap();
+        assert_eq!(a.created, Utc.timestamp_opt(1700000100, 0).unwrap());
+        assert_eq!(a.modified, Utc.timestamp_opt(1700000300, 0).unwrap());
+        let b = history.dates(Path::new("/repo/notes/b.md")).unwrap();
+        assert_eq!(b.modified, Utc.timestamp_opt(1700000200, 0).unwrap());
+        assert!(history.dates(Path::new("/repo/notes/c.md")).is_none());
+    }
+
+    #[test]
+    fn test_load_from_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str], date: &str| {
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"], "") {
+            // git is not installed
+            return;
+        }
+
+        std::fs::write(dir.join("note.md"), "first").unwrap();
+        git(&["add", "."], "");
+        git(&["commit", "-q", "-m", "first"], "2024-01-01T00:00:00Z");
+        std::fs::write(dir.join("note.md"), "second").unwrap();
+        git(&["commit", "-q", "-am", "second"], "2024-02-01T00:00:00Z");
+
+        let history = GitHistory::load(dir).unwrap();
+        let dates = history.dates(&dir.join("note.md")).unwrap();
+        assert_eq!(
+            dates.created,
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            dates.modified,
+            Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()
+        );
+
+        // Lookup paths are canonicalized before matching
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        assert_eq!(history.dates(&dir.join("sub/../note.md")), Some(dates));
+    }
+}
@@ -2,11 +2,15 @@
 //! ABOUTME: Provides common functionality used across the application
 
 pub mod blog;
+pub(crate) mod escape;
 pub mod fs;
+pub mod git;
 pub mod minify;
 pub mod para;
 
 pub use blog::*;
+pub(crate) use escape::*;
 pub use fs::*;
+pub use git::*;
 pub use minify::*;
 pub use para::*;
@@ -1,7 +1,9 @@
 // ABOUTME: Integration tests for blog feature error handling and edge cases
 // ABOUTME: Tests cover API failures, rate limiting, and network errors
 
-use para_ssg::{generate_site, BlogConfig, Config};
+use para_ssg::{
+    generate_site, BlogConfig, Config, FeedConfig, PaginationConfig, ParserConfig, SitemapConfig,
+};
 use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
@@ -63,10 +65,15 @@ fn test_blog_post_without_github_issue() {
         input_dir: content_dir.to_string_lossy().to_string(),
         output_dir: output_dir.to_string_lossy().to_string(),
         base_url: "https://example.com".to_string(),
+        site_url: None,
         site_title: "Test Blog".to_string(),
         verbose: false,
         watch: false,
         blog: blog_config,
+        feeds: FeedConfig::default(),
+        sitemap: SitemapConfig::default(),
+        pagination: PaginationConfig::default(),
+        parser: ParserConfig::default(),
     };
 
     generate_site(&config).unwrap();
@@ -109,10 +116,15 @@ fn test_blog_post_with_invalid_issue_number() {
         input_dir: content_dir.to_string_lossy().to_string(),
         output_dir: output_dir.to_string_lossy().to_string(),
         base_url: "https://example.com".to_string(),
+        site_url: None,
         site_title: "Test Blog".to_string(),
         verbose: false,
         watch: false,
         blog: blog_config,
+        feeds: FeedConfig::default(),
+        sitemap: SitemapConfig::default(),
+        pagination: PaginationConfig::default(),
+        parser: ParserConfig::default(),
     };
 
     generate_site(&config).unwrap();
@@ -178,10 +190,15 @@ date: 2024-01-15T10:00:00Z
         input_dir: content_dir.to_string_lossy().to_string(),
         output_dir: output_dir.to_string_lossy().to_string(),
         base_url: "https://example.com".to_string(),
+        site_url: None,
         site_title: "Test Blog".to_string(),
         verbose: false,
         watch: false,
         blog: blog_config,
+        feeds: FeedConfig::default(),
+        sitemap: SitemapConfig::default(),
+        pagination: PaginationConfig::default(),
+        parser: ParserConfig::default(),
     };
 
     generate_site(&config).unwrap();
@@ -241,10 +258,15 @@ fn test_blog_generation_with_missing_config() {
         input_dir: content_dir.to_string_lossy().to_string(),
         output_dir: output_dir.to_string_lossy().to_string(),
         base_url: "https://example.com".to_string(),
+        site_url: None,
         site_title: "Test Blog".to_string(),
         verbose: false,
         watch: false,
         blog: BlogConfig::default(),
+        feeds: FeedConfig::default(),
+        sitemap: SitemapConfig::default(),
+        pagination: PaginationConfig::default(),
+        parser: ParserConfig::default(),
     };
 
     // Should still generate successfully with default values